@@ -4,6 +4,57 @@ use tokenizer::position::Position;
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Program {
     pub functions: Vec<FunctionDefinition>,
+    pub structs: Vec<StructDefinition>,
+    pub enums: Vec<EnumDefinition>,
+    pub constants: Vec<ConstantDefinition>,
+    pub statics: Vec<StaticDefinition>,
+}
+
+/// `const NAME: type = value;`, whose value is evaluated at compile time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstantDefinition {
+    pub name: Identifier,
+    pub constant_type: Type,
+    pub value: Expression,
+    pub location: Location,
+}
+
+/// `static var name: type = value;`, a variable shared by every function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticDefinition {
+    pub name: Identifier,
+    pub static_type: Type,
+    pub value: Expression,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructDefinition {
+    pub name: Identifier,
+    pub fields: Vec<Field>,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: Identifier,
+    pub field_type: Type,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumDefinition {
+    pub name: Identifier,
+    pub variants: Vec<Variant>,
+    pub location: Location,
+}
+
+/// `Name` or `Name(type, ...)`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: Identifier,
+    pub fields: Vec<Type>,
+    pub location: Location,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -44,6 +95,7 @@ pub struct Statements {
 pub enum Statement {
     ExpressionStatement(ExpressionStatement),
     VariableDefinition(VariableDefinition),
+    DestructuringDefinition(DestructuringDefinition),
     IfStatement(IfStatement),
     Expression(Expression),
 }
@@ -58,11 +110,22 @@ pub struct ExpressionStatement {
 pub struct VariableDefinition {
     pub name: Identifier,
     pub mutable: bool,
-    pub variable_type: Type,
+
+    /// The type of the variable, or `None` if it is inferred.
+    pub variable_type: Option<Type>,
     pub value: Option<Expression>,
     pub location: Location,
 }
 
+/// `let (name, ...) = value;`, defining a variable for each value of a tuple.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DestructuringDefinition {
+    pub names: Vec<Identifier>,
+    pub mutable: bool,
+    pub value: Expression,
+    pub location: Location,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfStatement {
     pub condition: Expression,
@@ -76,10 +139,27 @@ pub enum Expression {
     BinaryExpression(BinaryExpression),
     UnaryExpression(UnaryExpression),
     AssignmentExpression(AssignmentExpression),
+    CompoundAssignmentExpression(CompoundAssignmentExpression),
+    IndexAssignmentExpression(IndexAssignmentExpression),
+    FieldAssignmentExpression(FieldAssignmentExpression),
     IfElseExpression(IfElseExpression),
+    CastExpression(CastExpression),
     Identifier(Identifier),
     IntegerLiteral(IntegerLiteral),
+    FloatLiteral(FloatLiteral),
+    BooleanLiteral(BooleanLiteral),
     FunctionCall(FunctionCall),
+    ArrayLiteral(ArrayLiteral),
+    ArrayRepeat(ArrayRepeat),
+    TupleLiteral(TupleLiteral),
+    IndexExpression(IndexExpression),
+    StructLiteral(StructLiteral),
+    FieldAccess(FieldAccess),
+    EnumVariant(EnumVariant),
+    MatchExpression(MatchExpression),
+
+    /// `{ statement* expression }`, evaluating to its last expression.
+    Block(Block),
 }
 
 impl Expression {
@@ -90,10 +170,31 @@ impl Expression {
             Expression::AssignmentExpression(assignment_expression) => {
                 &assignment_expression.location
             }
+            Expression::CompoundAssignmentExpression(compound_assignment_expression) => {
+                &compound_assignment_expression.location
+            }
+            Expression::IndexAssignmentExpression(index_assignment_expression) => {
+                &index_assignment_expression.location
+            }
+            Expression::FieldAssignmentExpression(field_assignment_expression) => {
+                &field_assignment_expression.location
+            }
             Expression::IfElseExpression(if_else_expression) => &if_else_expression.location,
+            Expression::CastExpression(cast_expression) => &cast_expression.location,
             Expression::Identifier(identifier) => &identifier.location,
             Expression::IntegerLiteral(integer_literal) => &integer_literal.location,
+            Expression::FloatLiteral(float_literal) => &float_literal.location,
+            Expression::BooleanLiteral(boolean_literal) => &boolean_literal.location,
             Expression::FunctionCall(function_call) => &function_call.location,
+            Expression::ArrayLiteral(array_literal) => &array_literal.location,
+            Expression::ArrayRepeat(array_repeat) => &array_repeat.location,
+            Expression::TupleLiteral(tuple_literal) => &tuple_literal.location,
+            Expression::IndexExpression(index_expression) => &index_expression.location,
+            Expression::StructLiteral(struct_literal) => &struct_literal.location,
+            Expression::FieldAccess(field_access) => &field_access.location,
+            Expression::EnumVariant(enum_variant) => &enum_variant.location,
+            Expression::Block(block) => &block.location,
+            Expression::MatchExpression(match_expression) => &match_expression.location,
         }
     }
 }
@@ -104,6 +205,12 @@ pub enum OperatorKind {
     Subtract,
     Multiply,
     Divide,
+    Remainder,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
     LessThan,
     LessThanOrEqual,
     GreaterThan,
@@ -122,6 +229,12 @@ impl std::fmt::Display for OperatorKind {
             OperatorKind::Subtract => write!(f, "-"),
             OperatorKind::Multiply => write!(f, "*"),
             OperatorKind::Divide => write!(f, "/"),
+            OperatorKind::Remainder => write!(f, "%"),
+            OperatorKind::BitAnd => write!(f, "&"),
+            OperatorKind::BitOr => write!(f, "|"),
+            OperatorKind::BitXor => write!(f, "^"),
+            OperatorKind::ShiftLeft => write!(f, "<<"),
+            OperatorKind::ShiftRight => write!(f, ">>"),
             OperatorKind::LessThan => write!(f, "<"),
             OperatorKind::LessThanOrEqual => write!(f, "<="),
             OperatorKind::GreaterThan => write!(f, ">"),
@@ -144,6 +257,12 @@ impl std::str::FromStr for OperatorKind {
             "-" => Ok(OperatorKind::Subtract),
             "*" => Ok(OperatorKind::Multiply),
             "/" => Ok(OperatorKind::Divide),
+            "%" => Ok(OperatorKind::Remainder),
+            "&" => Ok(OperatorKind::BitAnd),
+            "|" => Ok(OperatorKind::BitOr),
+            "^" => Ok(OperatorKind::BitXor),
+            "<<" => Ok(OperatorKind::ShiftLeft),
+            ">>" => Ok(OperatorKind::ShiftRight),
             "<" => Ok(OperatorKind::LessThan),
             "<=" => Ok(OperatorKind::LessThanOrEqual),
             ">" => Ok(OperatorKind::GreaterThan),
@@ -186,6 +305,33 @@ pub struct AssignmentExpression {
     pub location: Location,
 }
 
+/// `name += value`, where `operator` is the arithmetic operator applied before assigning
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompoundAssignmentExpression {
+    pub name: Identifier,
+    pub operator: Operator,
+    pub value: Box<Expression>,
+    pub location: Location,
+}
+
+/// `array[index] = value`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexAssignmentExpression {
+    pub array: Box<Expression>,
+    pub index: Box<Expression>,
+    pub value: Box<Expression>,
+    pub location: Location,
+}
+
+/// `object.field = value`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldAssignmentExpression {
+    pub object: Box<Expression>,
+    pub field: Identifier,
+    pub value: Box<Expression>,
+    pub location: Location,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IfElseExpression {
     pub condition: Box<Expression>,
@@ -195,6 +341,13 @@ pub struct IfElseExpression {
     pub location: Location,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CastExpression {
+    pub expression: Box<Expression>,
+    pub target_type: Type,
+    pub location: Location,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Identifier {
     pub name: String,
@@ -207,6 +360,20 @@ pub struct IntegerLiteral {
     pub location: Location,
 }
 
+/// A number with a fractional part, such as `1.5`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloatLiteral {
+    pub value: String,
+    pub location: Location,
+}
+
+/// `true` or `false`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BooleanLiteral {
+    pub value: bool,
+    pub location: Location,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FunctionCall {
     pub name: Identifier,
@@ -214,10 +381,153 @@ pub struct FunctionCall {
     pub location: Location,
 }
 
+/// `[element, ...]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArrayLiteral {
+    pub elements: Vec<Expression>,
+    pub location: Location,
+}
+
+/// `(element, element, ...)`, with at least two elements.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TupleLiteral {
+    pub elements: Vec<Expression>,
+    pub location: Location,
+}
+
+/// `[value; length]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArrayRepeat {
+    pub value: Box<Expression>,
+    pub length: Box<Expression>,
+    pub location: Location,
+}
+
+/// `array[index]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexExpression {
+    pub array: Box<Expression>,
+    pub index: Box<Expression>,
+    pub location: Location,
+}
+
+/// `Name { field: value, ... }`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructLiteral {
+    pub name: Identifier,
+    pub fields: Vec<FieldInitializer>,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldInitializer {
+    pub name: Identifier,
+    pub value: Expression,
+    pub location: Location,
+}
+
+/// `object.field`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldAccess {
+    pub object: Box<Expression>,
+    pub field: Identifier,
+    pub location: Location,
+}
+
+/// `Enum::Variant` or `Enum::Variant(value, ...)`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub enum_name: Identifier,
+    pub variant: Identifier,
+    pub arguments: Vec<Expression>,
+    pub location: Location,
+}
+
+/// `match value { pattern => expression, ... }`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchExpression {
+    pub value: Box<Expression>,
+    pub arms: Vec<MatchArm>,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub expression: Expression,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pattern {
+    /// `_`
+    Wildcard(Location),
+
+    /// A name bound to the matched value.
+    Binding(Identifier),
+
+    /// An integer literal, which may be negative.
+    IntegerLiteral(IntegerLiteral),
+
+    /// `start..end` or `start..=end`
+    Range(RangePattern),
+
+    /// `Enum::Variant` or `Enum::Variant(pattern, ...)`
+    Variant(VariantPattern),
+}
+
+impl Pattern {
+    pub fn location(&self) -> &Location {
+        match self {
+            Pattern::Wildcard(location) => location,
+            Pattern::Binding(identifier) => &identifier.location,
+            Pattern::IntegerLiteral(integer_literal) => &integer_literal.location,
+            Pattern::Range(range_pattern) => &range_pattern.location,
+            Pattern::Variant(variant_pattern) => &variant_pattern.location,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangePattern {
+    pub start: IntegerLiteral,
+    pub end: IntegerLiteral,
+    pub inclusive: bool,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariantPattern {
+    pub enum_name: Identifier,
+    pub variant: Identifier,
+    pub fields: Vec<Pattern>,
+    pub location: Location,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TypeKind {
     I32,
     I64,
+    U32,
+    U64,
+    F32,
+    F64,
+    Bool,
+
+    /// `()`, the type of functions without a return value.
+    Unit,
+
+    /// `[element; length]`, or `[element]` when the length is only known at runtime.
+    Array {
+        element: Box<Type>,
+        length: Option<IntegerLiteral>,
+    },
+
+    /// `(type, type, ...)`, with at least two elements.
+    Tuple(Vec<Type>),
+
+    /// A type defined by the program, such as a struct or an enum.
+    Named(String),
 }
 
 impl std::fmt::Display for TypeKind {
@@ -225,6 +535,28 @@ impl std::fmt::Display for TypeKind {
         match self {
             TypeKind::I32 => write!(f, "i32"),
             TypeKind::I64 => write!(f, "i64"),
+            TypeKind::U32 => write!(f, "u32"),
+            TypeKind::U64 => write!(f, "u64"),
+            TypeKind::F32 => write!(f, "f32"),
+            TypeKind::F64 => write!(f, "f64"),
+            TypeKind::Bool => write!(f, "bool"),
+            TypeKind::Unit => write!(f, "()"),
+            TypeKind::Array {
+                element,
+                length: Some(length),
+            } => write!(f, "[{}; {}]", element.name, length.value),
+            TypeKind::Array {
+                element,
+                length: None,
+            } => write!(f, "[{}]", element.name),
+            TypeKind::Tuple(elements) => {
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| element.name.to_string())
+                    .collect();
+                write!(f, "({})", elements.join(", "))
+            }
+            TypeKind::Named(name) => write!(f, "{}", name),
         }
     }
 }
@@ -236,6 +568,11 @@ impl std::str::FromStr for TypeKind {
         match s {
             "i32" => Ok(TypeKind::I32),
             "i64" => Ok(TypeKind::I64),
+            "u32" => Ok(TypeKind::U32),
+            "u64" => Ok(TypeKind::U64),
+            "f32" => Ok(TypeKind::F32),
+            "f64" => Ok(TypeKind::F64),
+            "bool" => Ok(TypeKind::Bool),
             _ => Err(format!("Invalid type: {}", s)),
         }
     }
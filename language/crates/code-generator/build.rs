@@ -0,0 +1,64 @@
+//! Encode the runtime component from `src/template.wat` once, at build time.
+//!
+//! The component is validated and split around its main module, the only core module importing
+//! `shuiro:std@0.0.0`. The code generator puts the generated module between the two parts.
+
+use std::{env, fs, path::Path};
+
+use wasm_encoder::Encode;
+
+const TEMPLATE: &str = "src/template.wat";
+
+fn main() {
+    println!("cargo::rerun-if-changed={}", TEMPLATE);
+
+    let wasm =
+        wat::parse_file(TEMPLATE).unwrap_or_else(|error| panic!("invalid template: {error}"));
+    wasmparser::Validator::new()
+        .validate_all(&wasm)
+        .unwrap_or_else(|error| panic!("invalid template: {error}"));
+
+    let mut main_modules = Vec::new();
+    for payload in wasmparser::Parser::new(0).parse_all(&wasm) {
+        if let wasmparser::Payload::ModuleSection {
+            unchecked_range, ..
+        } = payload.expect("the template is validated")
+        {
+            if imports_std(&wasm[unchecked_range.clone()]) {
+                main_modules.push(unchecked_range);
+            }
+        }
+    }
+    let [main_module] = main_modules.as_slice() else {
+        panic!(
+            "invalid template: expected one module importing `shuiro:std@0.0.0`, found {}",
+            main_modules.len()
+        );
+    };
+
+    // the section header is the core module section id followed by the size of the module
+    let mut header = vec![wasm_encoder::ComponentSectionId::CoreModule as u8];
+    (main_module.len() as u32).encode(&mut header);
+    let section_start = main_module.start - header.len();
+    assert_eq!(
+        wasm[section_start..main_module.start],
+        header,
+        "invalid template: unexpected header of the main module section"
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let out_dir = Path::new(&out_dir);
+    fs::write(out_dir.join("runtime_head.wasm"), &wasm[..section_start]).unwrap();
+    fs::write(out_dir.join("runtime_tail.wasm"), &wasm[main_module.end..]).unwrap();
+}
+
+fn imports_std(module: &[u8]) -> bool {
+    wasmparser::Parser::new(0)
+        .parse_all(module)
+        .any(|payload| match payload {
+            Ok(wasmparser::Payload::ImportSection(reader)) => reader
+                .into_iter()
+                .any(|import| import.is_ok_and(|import| import.module == "shuiro:std@0.0.0")),
+            _ => false,
+        })
+}
@@ -0,0 +1,362 @@
+//! Errors reported while generating code.
+
+use crate::Type;
+
+/// An error in the program being compiled, or in the generation of its code.
+///
+/// Errors caused by the source carry the location they were found at and are shown with its
+/// line and column. Errors in the encoding of the module are not caused by the source, so they
+/// have no location, and neither does a missing `main`.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CodegenError {
+    #[error(
+        "cannot find variable `{name}` in this scope at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnknownVariable {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "cannot find function `{name}` in this scope at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnknownFunction {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "cannot find type `{name}` in this scope at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnknownType {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "no field `{field}` on type `{ty}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnknownField {
+        ty: Type,
+        field: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "missing field `{field}` in initializer of `{name}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    MissingField {
+        name: String,
+        field: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "field `{field}` is specified more than once at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    DuplicateField {
+        field: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "no variant `{variant}` in enum `{ty}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnknownVariant {
+        ty: Type,
+        variant: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "the name `{name}` is defined multiple times at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    DuplicateDefinition {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "mismatched types: expected `{expected}`, found `{found}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    MismatchedTypes {
+        expected: Type,
+        found: Type,
+        location: ast::Location,
+    },
+
+    #[error(
+        "function `{function}` takes {expected} arguments but {found} were supplied at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+        location: ast::Location,
+    },
+
+    #[error(
+        "variant `{variant}` has {expected} values but {found} were supplied at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    FieldCount {
+        variant: String,
+        expected: usize,
+        found: usize,
+        location: ast::Location,
+    },
+
+    #[error(
+        "`{operator}` is not a binary operator at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnsupportedOperator {
+        operator: ast::OperatorKind,
+        location: ast::Location,
+    },
+
+    #[error(
+        "literal `{literal}` is out of range for `{ty}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    LiteralOutOfRange {
+        literal: String,
+        ty: Type,
+        location: ast::Location,
+    },
+
+    #[error(
+        "cannot apply `{operator}` to a value of type `{ty}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    InvalidOperand {
+        operator: ast::OperatorKind,
+        ty: Type,
+        location: ast::Location,
+    },
+
+    #[error(
+        "cannot cast `{from}` as `{to}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    InvalidCast {
+        from: Type,
+        to: Type,
+        location: ast::Location,
+    },
+
+    #[error(
+        "expected an array, found `{ty}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    NotAnArray { ty: Type, location: ast::Location },
+
+    #[error(
+        "arrays of `{ty}` are not supported at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnsupportedElementType { ty: Type, location: ast::Location },
+
+    #[error(
+        "non-exhaustive patterns: {missing} not covered at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    NonExhaustiveMatch {
+        missing: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "values of variants can only be matched by names or `_` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    UnsupportedPattern { location: ast::Location },
+
+    #[error(
+        "range pattern matches no values at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    EmptyRange { location: ast::Location },
+
+    #[error(
+        "the unit type `()` can only be the return type of a function at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    MisplacedUnitType { location: ast::Location },
+
+    #[error(
+        "type annotations needed for `{name}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    TypeAnnotationNeeded {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "function `{name}` does not return a value at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    NoValue {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "initial value of `{name}` cannot be evaluated at compile time at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    NotConstant {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "cannot assign to constant `{name}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    AssignmentToConstant {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "cannot assign to immutable variable `{name}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    AssignmentToImmutable {
+        name: String,
+        location: ast::Location,
+    },
+
+    #[error(
+        "tuple types can only be the return type of a function at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    MisplacedTupleType { location: ast::Location },
+
+    #[error(
+        "tuples can only be returned from a function or destructured at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    MisplacedTuple { location: ast::Location },
+
+    #[error(
+        "expected a tuple, found `{ty}` at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    NotATuple { ty: Type, location: ast::Location },
+
+    #[error(
+        "cannot destructure a tuple of {found} values into {expected} names at {}:{}",
+        .location.start.line,
+        .location.start.column
+    )]
+    DestructuringCount {
+        expected: usize,
+        found: usize,
+        location: ast::Location,
+    },
+
+    #[error("`main` function not found")]
+    MissingMain,
+
+    #[error("failed to encode the module: {message}")]
+    Encode { message: String },
+}
+
+impl CodegenError {
+    /// Location in the source the error was found at, if it was caused by the source.
+    pub fn location(&self) -> Option<&ast::Location> {
+        match self {
+            Self::UnknownVariable { location, .. }
+            | Self::UnknownFunction { location, .. }
+            | Self::UnknownType { location, .. }
+            | Self::UnknownField { location, .. }
+            | Self::MissingField { location, .. }
+            | Self::DuplicateField { location, .. }
+            | Self::UnknownVariant { location, .. }
+            | Self::DuplicateDefinition { location, .. }
+            | Self::MismatchedTypes { location, .. }
+            | Self::ArgumentCount { location, .. }
+            | Self::FieldCount { location, .. }
+            | Self::UnsupportedOperator { location, .. }
+            | Self::LiteralOutOfRange { location, .. }
+            | Self::InvalidOperand { location, .. }
+            | Self::InvalidCast { location, .. }
+            | Self::NotAnArray { location, .. }
+            | Self::UnsupportedElementType { location, .. }
+            | Self::NonExhaustiveMatch { location, .. }
+            | Self::UnsupportedPattern { location }
+            | Self::EmptyRange { location }
+            | Self::MisplacedUnitType { location }
+            | Self::TypeAnnotationNeeded { location, .. }
+            | Self::NoValue { location, .. }
+            | Self::NotConstant { location, .. }
+            | Self::AssignmentToConstant { location, .. }
+            | Self::AssignmentToImmutable { location, .. }
+            | Self::MisplacedTupleType { location }
+            | Self::MisplacedTuple { location }
+            | Self::NotATuple { location, .. }
+            | Self::DestructuringCount { location, .. } => Some(location),
+            Self::MissingMain | Self::Encode { .. } => None,
+        }
+    }
+
+    fn encode(error: impl ToString) -> Self {
+        Self::Encode {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<wast::Error> for CodegenError {
+    fn from(error: wast::Error) -> Self {
+        Self::encode(error)
+    }
+}
+
+impl From<wasmparser::BinaryReaderError> for CodegenError {
+    fn from(error: wasmparser::BinaryReaderError) -> Self {
+        Self::encode(error)
+    }
+}
@@ -1,23 +1,98 @@
-use anyhow::Result;
+mod error;
+mod lower;
+pub mod source_map;
+mod types;
+
+use wasm_encoder::{ComponentSection, Section};
 use wast::{
-    component,
     core::{self},
-    parser::ParseBuffer,
-    token::{Id, Span},
+    token::{Id, NameAnnotation, Span},
     Wat,
 };
 
-const TEMPLATE: &str = include_str!("template.wat");
+pub use error::CodegenError;
+pub use lower::{check, lower, lower_with_types, InferredType};
+pub use source_map::SourceMap;
+pub use types::Type;
+
+/// The runtime component encoded from `template.wat` by the build script, before and after the
+/// section of its main module.
+const RUNTIME_HEAD: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/runtime_head.wasm"));
+const RUNTIME_TAIL: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/runtime_tail.wasm"));
 
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error(transparent)]
-    Wast(#[from] wast::Error),
+/// Module the standard library functions are imported from.
+const STD_MODULE: &str = "shuiro:std@0.0.0";
 
-    #[error(transparent)]
-    Anyhow(#[from] anyhow::Error),
+/// Functions exported by the standard library (`$Std` in the template) and imported by `$Main`,
+/// with the types the program calls them with.
+///
+/// This is a function rather than a constant because array types cannot be built in constants.
+pub(crate) fn std_functions() -> Vec<(&'static str, Vec<Type>, Type)> {
+    let characters = Type::Array {
+        element: Box::new(Type::I32),
+        length: None,
+    };
+    vec![
+        ("print_int", vec![Type::I32], Type::I32),
+        ("print_i64", vec![Type::I64], Type::I32),
+        ("print_char", vec![Type::I32], Type::I32),
+        ("print_bool", vec![Type::Bool], Type::I32),
+        ("print_f64", vec![Type::F64], Type::I32),
+        ("read_int", vec![], Type::I32),
+        ("read_i64", vec![], Type::I64),
+        ("read_char", vec![], Type::I32),
+        ("read_line", vec![], characters),
+        ("alloc", vec![Type::I32], Type::I32),
+        ("free", vec![Type::I32], Type::I32),
+    ]
 }
 
+/// Functions of the standard library used by the generated code, which the program cannot call.
+pub(crate) const RUNTIME_FUNCTIONS: &[(&str, &[ir::Type], ir::Type)] = &[
+    (
+        "array_new_i32",
+        &[ir::Type::I32, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_new_i64",
+        &[ir::Type::I64, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_new_f32",
+        &[ir::Type::F32, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_new_f64",
+        &[ir::Type::F64, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_address_i32",
+        &[ir::Type::I32, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_address_i64",
+        &[ir::Type::I32, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_address_f32",
+        &[ir::Type::I32, ir::Type::I32],
+        ir::Type::I32,
+    ),
+    (
+        "array_address_f64",
+        &[ir::Type::I32, ir::Type::I32],
+        ir::Type::I32,
+    ),
+];
+
+type Result<T> = std::result::Result<T, CodegenError>;
+
 type CoreParameters<'a> = Box<
     [(
         Option<Id<'a>>,
@@ -26,339 +101,875 @@ type CoreParameters<'a> = Box<
     )],
 >;
 
-pub struct CodeGenerator<'a> {
-    ast: ast::Program,
-    buffer: ParseBuffer<'a>,
+pub struct CodeGenerator {
+    program: ir::Program,
     span: Span,
 }
 
-impl CodeGenerator<'_> {
-    pub fn new(ast: ast::Program) -> Result<Self> {
-        let buffer = ParseBuffer::new(TEMPLATE)?;
-        Ok(Self {
-            ast,
-            buffer,
+/// Instructions together with the source location each of them was generated from.
+#[derive(Default)]
+struct Instructions<'a> {
+    instructions: Vec<core::Instruction<'a>>,
+    locations: Vec<&'a ir::Location>,
+}
+
+impl<'a> Instructions<'a> {
+    fn push(&mut self, instruction: core::Instruction<'a>, location: &'a ir::Location) {
+        self.instructions.push(instruction);
+        self.locations.push(location);
+    }
+
+    fn append(&mut self, mut other: Instructions<'a>) {
+        self.instructions.append(&mut other.instructions);
+        self.locations.append(&mut other.locations);
+    }
+}
+
+impl CodeGenerator {
+    pub fn new(program: ir::Program) -> Self {
+        Self {
+            program,
             span: Span::from_offset(0),
+        }
+    }
+
+    /// Generate the component running the program on WASI, by putting the main module into the
+    /// prebuilt runtime component.
+    pub fn generate(&self) -> Result<Vec<u8>> {
+        let module = self.generate_main_module()?;
+        let mut component = RUNTIME_HEAD.to_vec();
+        wasm_encoder::RawSection {
+            id: wasm_encoder::ComponentSectionId::CoreModule as u8,
+            data: &module,
+        }
+        .append_to_component(&mut component);
+        component.extend_from_slice(RUNTIME_TAIL);
+        Ok(component)
+    }
+
+    /// Generate the main module, the core module containing only the functions of the program,
+    /// which `generate` links with the standard library into a component.
+    ///
+    /// The module exports `main` and cannot be instantiated without a host providing the imports
+    /// the standard library (`$Std` in the template) otherwise provides:
+    /// - `env.memory`, the memory of the program.
+    /// - From `shuiro:std@0.0.0`, the standard library functions `print_int`, `print_i64`,
+    ///   `print_char`, `print_bool`, `print_f64`, `read_int`, `read_i64`, `read_char`,
+    ///   `read_line`, `alloc` and `free`, with the types of `std_functions` in wasm.
+    /// - From `shuiro:std@0.0.0`, the runtime functions `array_new_{i32,i64,f32,f64}` and
+    ///   `array_address_{i32,i64,f32,f64}` of `RUNTIME_FUNCTIONS`.
+    ///
+    /// Every function is imported whether the program calls it or not. Arrays are pointers into
+    /// the memory to their length, followed 8 bytes later by their elements.
+    pub fn generate_main_module(&self) -> Result<Vec<u8>> {
+        let mut module = Wat::Module(core::Module {
+            span: self.span,
+            id: Some(Id::new("Main", self.span)),
+            name: None,
+            kind: core::ModuleKind::Text(self.generate_module_fields()),
         })
+        .encode()?;
+        // the source map is placed after the code, so its contents do not change the offsets
+        let source_map = self.generate_source_map(&module)?;
+        wasm_encoder::CustomSection {
+            name: source_map::SECTION_NAME.into(),
+            data: source_map.encode().into(),
+        }
+        .append_to(&mut module);
+        Ok(module)
     }
 
-    pub fn generate(&mut self) -> Result<Wat<'_>> {
-        let mut wat = wast::parser::parse::<Wat>(&self.buffer).unwrap();
-        match wat {
-            Wat::Component(ref mut component) => match component.kind {
-                component::ComponentKind::Text(ref mut items) => {
-                    if let Some(main_module) = items.iter_mut().find_map(|item| match item {
-                        component::ComponentField::CoreModule(module) => {
-                            if let Some(id) = module.id {
-                                if id.name() == "Main" {
-                                    return Some(module);
-                                }
-                            }
-                            None
-                        }
-                        _ => None,
-                    }) {
-                        if let component::CoreModuleKind::Inline { ref mut fields } =
-                            main_module.kind
-                        {
-                            fields.append(
-                                &mut self
-                                    .generate_functions()
-                                    .into_iter()
-                                    .map(core::ModuleField::Func)
-                                    .collect::<Vec<_>>(),
-                            );
-                        } else {
-                            unreachable!("expected the main module to be inline")
+    fn generate_module_fields(&self) -> Vec<core::ModuleField<'_>> {
+        let memory = core::Import {
+            span: self.span,
+            module: "env",
+            field: "memory",
+            item: core::ItemSig {
+                span: self.span,
+                id: None,
+                name: None,
+                kind: core::ItemKind::Memory(core::MemoryType {
+                    limits: core::Limits {
+                        is64: false,
+                        min: 0,
+                        max: None,
+                    },
+                    shared: false,
+                    page_size_log2: None,
+                }),
+            },
+        };
+        let std_functions = std_functions()
+            .into_iter()
+            .map(|(name, parameters, return_type)| {
+                let parameters: Vec<_> = parameters.iter().map(Type::ir_type).collect();
+                (name, parameters, return_type.ir_type())
+            });
+        let runtime_functions = RUNTIME_FUNCTIONS
+            .iter()
+            .map(|(name, parameters, return_type)| (*name, parameters.to_vec(), *return_type));
+        let functions =
+            std_functions
+                .chain(runtime_functions)
+                .map(|(name, parameters, return_type)| core::Import {
+                    span: self.span,
+                    module: STD_MODULE,
+                    field: name,
+                    item: core::ItemSig {
+                        span: self.span,
+                        id: Some(Id::new(name, self.span)),
+                        name: None,
+                        kind: core::ItemKind::Func(core::TypeUse {
+                            index: None,
+                            inline: Some(core::FunctionType {
+                                params: parameters
+                                    .iter()
+                                    .map(|ty| (None, None, self.generate_type(*ty)))
+                                    .collect(),
+                                results: Box::new([self.generate_type(return_type)]),
+                            }),
+                        }),
+                    },
+                });
+        std::iter::once(memory)
+            .chain(functions)
+            .map(core::ModuleField::Import)
+            .chain(
+                self.generate_globals()
+                    .into_iter()
+                    .map(core::ModuleField::Global),
+            )
+            .chain(
+                self.generate_functions()
+                    .into_iter()
+                    .map(core::ModuleField::Func),
+            )
+            .collect()
+    }
+
+    /// Map the code offsets of the instructions of each function to their source positions.
+    ///
+    /// The offsets are read back from the encoded main module.
+    fn generate_source_map(&self, module: &[u8]) -> Result<SourceMap> {
+        let mut imported_functions = 0;
+        let mut bodies = Vec::new();
+        for payload in wasmparser::Parser::new(0).parse_all(module) {
+            match payload? {
+                wasmparser::Payload::ImportSection(reader) => {
+                    for import in reader {
+                        if let wasmparser::TypeRef::Func(_) = import?.ty {
+                            imported_functions += 1;
                         }
-                    } else {
-                        unreachable!("unable to find main module in the template")
                     }
                 }
-                _ => unreachable!("expected the template to be written in text format"),
-            },
-            _ => unreachable!("expected the template to be component"),
-        };
-        Ok(wat)
+                wasmparser::Payload::CodeSectionEntry(body) => {
+                    let start = body.range().start;
+                    let mut offsets = Vec::new();
+                    let mut reader = body.get_operators_reader()?;
+                    while !reader.eof() {
+                        let (_, offset) = reader.read_with_offset()?;
+                        offsets.push((offset - start) as u32);
+                    }
+                    bodies.push(offsets);
+                }
+                _ => {}
+            }
+        }
+
+        let functions = self
+            .program
+            .functions
+            .iter()
+            .zip(bodies)
+            .enumerate()
+            .map(|(index, (function, offsets))| {
+                let instructions = self.generate_block(&function.body, true);
+                // code before the first instruction, such as the check for a stack overflow on
+                // entry, maps to the definition of the function
+                let mut entries = vec![source_map::SourceMapEntry {
+                    offset: 0,
+                    line: function.location.start.line as u32,
+                    column: function.location.start.column as u32,
+                }];
+                for (offset, location) in offsets.into_iter().zip(instructions.locations) {
+                    let line = location.start.line as u32;
+                    let column = location.start.column as u32;
+                    if entries
+                        .last()
+                        .is_some_and(|entry| entry.line == line && entry.column == column)
+                    {
+                        continue;
+                    }
+                    entries.push(source_map::SourceMapEntry {
+                        offset,
+                        line,
+                        column,
+                    });
+                }
+                source_map::FunctionSourceMap {
+                    function_index: imported_functions + index as u32,
+                    entries,
+                }
+            })
+            .collect();
+        Ok(SourceMap { functions })
+    }
+
+    fn generate_globals(&self) -> Vec<core::Global<'_>> {
+        self.program
+            .globals
+            .iter()
+            .map(|global| core::Global {
+                span: self.span,
+                id: None,
+                name: Some(NameAnnotation { name: &global.name }),
+                exports: core::InlineExport { names: Vec::new() },
+                ty: core::GlobalType {
+                    ty: self.generate_type(global.value.ty()),
+                    mutable: true,
+                    shared: false,
+                },
+                kind: core::GlobalKind::Inline(core::Expression {
+                    branch_hints: Box::new([]),
+                    instr_spans: None,
+                    instrs: Box::new([self.generate_constant(global.value)]),
+                }),
+            })
+            .collect()
     }
 
     fn generate_functions(&self) -> Vec<core::Func<'_>> {
-        self.ast
+        self.program
             .functions
             .iter()
             .map(|f| self.generate_function(f))
             .collect()
     }
 
-    fn generate_function<'a>(&self, function: &'a ast::FunctionDefinition) -> core::Func<'a> {
+    fn generate_function<'a>(&self, function: &'a ir::Function) -> core::Func<'a> {
+        let span = self.generate_span(&function.location);
+        let instructions = self.generate_block(&function.body, true);
         core::Func {
-            span: self.span,
-            id: Some(self.generate_identifier(&function.name)),
+            span,
+            id: Some(wast::token::Id::new(&function.name, span)),
             exports: core::InlineExport {
-                names: vec![&function.name.name],
+                names: vec![&function.name],
             },
             name: None,
             kind: core::FuncKind::Inline {
-                locals: self.generate_locals(&function.body),
-                expression: self.generate_body(&function.body),
+                locals: function
+                    .variables()
+                    .iter()
+                    .map(|variable| core::Local {
+                        id: None,
+                        name: Some(NameAnnotation {
+                            name: &variable.name,
+                        }),
+                        ty: self.generate_type(variable.ty),
+                    })
+                    .collect(),
+                expression: core::Expression {
+                    branch_hints: Box::new([]),
+                    instr_spans: Some(
+                        instructions
+                            .locations
+                            .iter()
+                            .map(|location| self.generate_span(location))
+                            .collect(),
+                    ),
+                    instrs: instructions.instructions.into_boxed_slice(),
+                },
             },
             ty: core::TypeUse {
                 index: None,
                 inline: Some(core::FunctionType {
-                    params: self.generate_parameters(&function.parameters),
-                    results: Box::new([self.generate_type(&function.return_type)]),
+                    params: self.generate_parameters(function.parameters()),
+                    results: function
+                        .results
+                        .iter()
+                        .map(|ty| self.generate_type(*ty))
+                        .collect(),
                 }),
             },
         }
     }
 
-    fn generate_locals<'a>(&self, body: &'a ast::Block) -> Box<[wast::core::Local<'a>]> {
-        body.statements
-            .statements
+    fn generate_parameters<'a>(&self, parameters: &'a [ir::Local]) -> CoreParameters<'a> {
+        parameters
             .iter()
-            .filter_map(|statement| {
-                if let ast::Statement::VariableDefinition(ref variable) = statement {
-                    Some(core::Local {
-                        id: Some(self.generate_identifier(&variable.name)),
-                        name: None,
-                        ty: self.generate_type(&variable.variable_type),
-                    })
-                } else {
-                    None
-                }
+            .map(|parameter| {
+                (
+                    None,
+                    Some(NameAnnotation {
+                        name: &parameter.name,
+                    }),
+                    self.generate_type(parameter.ty),
+                )
             })
             .collect()
     }
 
-    fn generate_body<'a>(&self, body: &'a ast::Block) -> core::Expression<'a> {
-        core::Expression {
-            branch_hints: Box::new([]),
-            instr_spans: None,
-            instrs: self.generate_instructions(body),
+    /// Generate the instructions of a block. When `tail` is set, the result of the block is the
+    /// return value of the function.
+    fn generate_block<'a>(&self, block: &'a ir::Block, tail: bool) -> Instructions<'a> {
+        let mut instructions = Instructions::default();
+        for statement in &block.statements {
+            instructions.append(self.generate_statement(statement));
         }
+        if let Some(result) = &block.result {
+            instructions.append(if tail {
+                self.generate_tail_expression(result)
+            } else {
+                self.generate_expression(result)
+            });
+        }
+        instructions
     }
 
-    fn generate_instructions<'a>(&self, body: &'a ast::Block) -> Box<[core::Instruction<'a>]> {
-        body.statements
-            .statements
-            .iter()
-            .flat_map(|statement| match statement {
-                ast::Statement::VariableDefinition(ref variable) => {
-                    if let Some(ref initial_value) = variable.value {
-                        let mut instructions = self.generate_expression(initial_value);
-                        instructions.push(core::Instruction::LocalSet(wast::token::Index::Id(
-                            self.generate_identifier(&variable.name),
-                        )));
-                        instructions
-                    } else {
-                        vec![]
+    fn generate_statement<'a>(&self, statement: &'a ir::Statement) -> Instructions<'a> {
+        match statement {
+            ir::Statement::Expression(expression) => {
+                let mut instructions = self.generate_expression(expression);
+                instructions.push(core::Instruction::Drop, &expression.location);
+                instructions
+            }
+            ir::Statement::LocalSet { local, value } => {
+                let mut instructions = self.generate_expression(value);
+                instructions.push(
+                    core::Instruction::LocalSet(self.generate_local(*local)),
+                    &value.location,
+                );
+                instructions
+            }
+            ir::Statement::GlobalSet { global, value } => {
+                let mut instructions = self.generate_expression(value);
+                instructions.push(
+                    core::Instruction::GlobalSet(self.generate_global(*global)),
+                    &value.location,
+                );
+                instructions
+            }
+            ir::Statement::Store {
+                address,
+                value,
+                offset,
+            } => {
+                let mut instructions = self.generate_expression(address);
+                instructions.append(self.generate_expression(value));
+                let memarg = self.generate_memarg(value.ty, *offset);
+                instructions.push(
+                    match value.ty {
+                        ir::Type::I32 => core::Instruction::I32Store(memarg),
+                        ir::Type::I64 => core::Instruction::I64Store(memarg),
+                        ir::Type::F32 => core::Instruction::F32Store(memarg),
+                        ir::Type::F64 => core::Instruction::F64Store(memarg),
+                    },
+                    &value.location,
+                );
+                instructions
+            }
+            ir::Statement::Call {
+                function,
+                arguments,
+                results,
+                location,
+            } => {
+                let mut instructions = Instructions::default();
+                for argument in arguments {
+                    instructions.append(self.generate_expression(argument));
+                }
+                instructions.push(
+                    core::Instruction::Call(wast::token::Index::Id(wast::token::Id::new(
+                        function,
+                        self.generate_span(location),
+                    ))),
+                    location,
+                );
+                // the values are popped from the last one, and `main` returns its exit code even
+                // when the program declares no return type
+                if results.is_empty() {
+                    for _ in 0..self.result_count(function) {
+                        instructions.push(core::Instruction::Drop, location);
                     }
                 }
-                ast::Statement::IfStatement(ref if_statement) => {
-                    let condition_instructions = self.generate_expression(&if_statement.condition);
-                    let then_instructions = self.generate_instructions(&if_statement.then_block);
-                    let else_instructions = if_statement
-                        .else_block
-                        .as_ref()
-                        .map(|else_block| self.generate_instructions(else_block));
-
-                    let mut instructions = Vec::with_capacity(
-                        condition_instructions.len()
-                            + then_instructions.len()
-                            + else_instructions
-                                .as_ref()
-                                .map_or(0, |instructions| instructions.len())
-                            + if else_instructions.is_some() { 3 } else { 2 },
-                    );
-
-                    instructions.extend(condition_instructions);
-                    instructions.push(core::Instruction::If(Box::new(core::BlockType {
-                        label: None,
-                        label_name: None,
-                        ty: core::TypeUse {
-                            index: None,
-                            inline: None,
+                for result in results.iter().rev() {
+                    instructions.push(
+                        match result {
+                            Some(local) => core::Instruction::LocalSet(self.generate_local(*local)),
+                            None => core::Instruction::Drop,
                         },
-                    })));
-                    instructions.extend(then_instructions);
-                    if let Some(else_instructions) = else_instructions {
-                        instructions.push(core::Instruction::Else(None));
-                        instructions.extend(else_instructions);
-                    };
-                    instructions.push(core::Instruction::End(None));
-                    instructions
+                        location,
+                    );
                 }
-                ast::Statement::ExpressionStatement(ref statement) => {
-                    let mut instructions = self.generate_expression(&statement.expression);
-                    instructions.push(core::Instruction::Drop);
-                    instructions
+                instructions
+            }
+            ir::Statement::Return { values, location } => {
+                let mut instructions = Instructions::default();
+                for value in values {
+                    instructions.append(self.generate_expression(value));
                 }
-                ast::Statement::Expression(ref expression) => self.generate_expression(expression),
-            })
-            .collect()
-    }
-
-    fn generate_expression<'a>(
-        &self,
-        expression: &'a ast::Expression,
-    ) -> Vec<core::Instruction<'a>> {
-        match expression {
-            ast::Expression::BinaryExpression(expr) => {
-                let lhs = self.generate_expression(&expr.left);
-                let rhs = self.generate_expression(&expr.right);
-
-                let mut instructions = Vec::with_capacity(
-                    lhs.len()
-                        + rhs.len()
-                        + if expr.operator.operator == ast::OperatorKind::LogicalAnd
-                            || expr.operator.operator == ast::OperatorKind::LogicalOr
-                        {
-                            5
-                        } else {
-                            1
-                        },
-                );
-
-                // calculate lhs and rhs
-                if expr.operator.operator == ast::OperatorKind::LogicalAnd
-                    || expr.operator.operator == ast::OperatorKind::LogicalOr
-                {
-                    // convert lhs and rhs to boolean
-                    instructions.extend(lhs);
-                    instructions.push(core::Instruction::I32Const(0));
-                    instructions.push(core::Instruction::I32Ne);
-                    instructions.extend(rhs);
-                    instructions.push(core::Instruction::I32Const(0));
-                    instructions.push(core::Instruction::I32Ne);
-                } else {
-                    instructions.extend(lhs);
-                    instructions.extend(rhs);
+                instructions.push(core::Instruction::Return, location);
+                instructions
+            }
+            // only functions of the program return several values
+            ir::Statement::ReturnCall {
+                function,
+                arguments,
+                location,
+            } => {
+                let mut instructions = Instructions::default();
+                for argument in arguments {
+                    instructions.append(self.generate_expression(argument));
                 }
-
-                // apply operator
-                match expr.operator.operator {
-                    ast::OperatorKind::Add => instructions.push(core::Instruction::I32Add),
-                    ast::OperatorKind::Subtract => instructions.push(core::Instruction::I32Sub),
-                    ast::OperatorKind::Multiply => instructions.push(core::Instruction::I32Mul),
-                    ast::OperatorKind::Divide => instructions.push(core::Instruction::I32DivS),
-                    ast::OperatorKind::Equal => instructions.push(core::Instruction::I32Eq),
-                    ast::OperatorKind::NotEqual => instructions.push(core::Instruction::I32Ne),
-                    ast::OperatorKind::LessThan => instructions.push(core::Instruction::I32LtS),
-                    ast::OperatorKind::LessThanOrEqual => {
-                        instructions.push(core::Instruction::I32LeS)
-                    }
-                    ast::OperatorKind::GreaterThan => instructions.push(core::Instruction::I32GtS),
-                    ast::OperatorKind::GreaterThanOrEqual => {
-                        instructions.push(core::Instruction::I32GeS)
-                    }
-                    ast::OperatorKind::LogicalAnd => instructions.push(core::Instruction::I32And),
-                    ast::OperatorKind::LogicalOr => instructions.push(core::Instruction::I32Or),
-                    _ => {}
+                instructions.push(
+                    core::Instruction::ReturnCall(wast::token::Index::Id(wast::token::Id::new(
+                        function,
+                        self.generate_span(location),
+                    ))),
+                    location,
+                );
+                instructions
+            }
+            ir::Statement::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                let location = &condition.location;
+                let mut instructions = self.generate_expression(condition);
+                instructions.push(
+                    core::Instruction::If(Box::new(self.generate_block_type(None))),
+                    location,
+                );
+                instructions.append(self.generate_block(then_block, false));
+                if let Some(else_block) = else_block {
+                    instructions.push(core::Instruction::Else(None), location);
+                    instructions.append(self.generate_block(else_block, false));
                 };
+                instructions.push(core::Instruction::End(None), location);
                 instructions
             }
-            ast::Expression::UnaryExpression(expr) => {
-                let operand = self.generate_expression(&expr.operand);
-                let mut instructions = Vec::with_capacity(operand.len() + 1);
+        }
+    }
 
-                // calculate operand
-                if expr.operator.operator == ast::OperatorKind::LogicalNot {
-                    // convert operand to boolean
-                    instructions.extend(operand);
-                    instructions.push(core::Instruction::I32Const(0));
-                    instructions.push(core::Instruction::I32Ne);
-                } else {
-                    instructions.extend(operand);
+    fn generate_expression<'a>(&self, expression: &'a ir::Expression) -> Instructions<'a> {
+        let location = &expression.location;
+        let mut instructions = Instructions::default();
+        match &expression.kind {
+            ir::ExpressionKind::Constant(constant) => {
+                instructions.push(self.generate_constant(*constant), location)
+            }
+            ir::ExpressionKind::LocalGet(local) => {
+                instructions.push(
+                    core::Instruction::LocalGet(self.generate_local(*local)),
+                    location,
+                );
+            }
+            ir::ExpressionKind::GlobalGet(global) => {
+                instructions.push(
+                    core::Instruction::GlobalGet(self.generate_global(*global)),
+                    location,
+                );
+            }
+            ir::ExpressionKind::LocalTee { local, value } => {
+                instructions.append(self.generate_expression(value));
+                instructions.push(
+                    core::Instruction::LocalTee(self.generate_local(*local)),
+                    location,
+                );
+            }
+            ir::ExpressionKind::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                instructions.append(self.generate_expression(left));
+                instructions.append(self.generate_expression(right));
+                instructions.push(self.generate_binary_operator(*operator, left.ty), location);
+            }
+            ir::ExpressionKind::Unary { operator, operand } => {
+                instructions.append(self.generate_expression(operand));
+                instructions.push(
+                    match (operator, operand.ty) {
+                        (ir::UnaryOperator::Eqz, ir::Type::I32) => core::Instruction::I32Eqz,
+                        (ir::UnaryOperator::Eqz, ir::Type::I64) => core::Instruction::I64Eqz,
+                        (ir::UnaryOperator::Eqz, ir::Type::F32 | ir::Type::F64) => {
+                            unreachable!("eqz is only defined on integers")
+                        }
+                    },
+                    location,
+                );
+            }
+            ir::ExpressionKind::Convert { operand, signed } => {
+                instructions.append(self.generate_expression(operand));
+                if let Some(instruction) =
+                    self.generate_conversion(operand.ty, expression.ty, *signed)
+                {
+                    instructions.push(instruction, location);
                 }
-
-                // apply operator
-                if expr.operator.operator == ast::OperatorKind::LogicalNot {
-                    instructions.push(core::Instruction::I32Eqz)
-                };
-                instructions
             }
-            ast::Expression::IfElseExpression(expr) => {
-                let condition = self.generate_expression(&expr.condition);
-                let then_block = self.generate_instructions(&expr.then_block);
-                let else_block = self.generate_instructions(&expr.else_block);
-                let return_type = self.generate_type(&expr.return_type);
-
-                let mut instructions =
-                    Vec::with_capacity(condition.len() + then_block.len() + else_block.len() + 3);
-                instructions.extend(condition);
-                instructions.push(core::Instruction::If(Box::new(core::BlockType {
-                    label: None,
-                    label_name: None,
-                    ty: core::TypeUse {
-                        index: None,
-                        inline: Some(core::FunctionType {
-                            params: Box::new([]),
-                            results: Box::new([return_type]),
-                        }),
+            ir::ExpressionKind::Load { address, offset } => {
+                instructions.append(self.generate_expression(address));
+                let memarg = self.generate_memarg(expression.ty, *offset);
+                instructions.push(
+                    match expression.ty {
+                        ir::Type::I32 => core::Instruction::I32Load(memarg),
+                        ir::Type::I64 => core::Instruction::I64Load(memarg),
+                        ir::Type::F32 => core::Instruction::F32Load(memarg),
+                        ir::Type::F64 => core::Instruction::F64Load(memarg),
                     },
-                })));
-                instructions.extend(then_block);
-                instructions.push(core::Instruction::Else(None));
-                instructions.extend(else_block);
-                instructions.push(core::Instruction::End(None));
-                instructions
+                    location,
+                );
             }
-            ast::Expression::AssignmentExpression(expr) => {
-                let value = self.generate_expression(&expr.value);
-                let mut instuctions = Vec::with_capacity(value.len() + 2);
-                instuctions.extend_from_slice(&value);
-                instuctions.push(core::Instruction::LocalSet(wast::token::Index::Id(
-                    self.generate_identifier(&expr.name),
-                )));
-                instuctions.push(core::Instruction::LocalGet(wast::token::Index::Id(
-                    self.generate_identifier(&expr.name),
-                )));
-                instuctions
-            }
-            ast::Expression::FunctionCall(call) => {
-                let mut instructions = Vec::new();
-                for arg in &call.arguments {
-                    instructions.extend(self.generate_expression(arg));
+            ir::ExpressionKind::Call {
+                function,
+                arguments,
+            } => {
+                for argument in arguments {
+                    instructions.append(self.generate_expression(argument));
                 }
-                instructions.push(core::Instruction::Call(wast::token::Index::Id(
-                    self.generate_identifier(&call.name),
-                )));
+                instructions.push(
+                    core::Instruction::Call(wast::token::Index::Id(wast::token::Id::new(
+                        function,
+                        self.generate_span(location),
+                    ))),
+                    location,
+                );
+            }
+            ir::ExpressionKind::If { .. }
+            | ir::ExpressionKind::Block(_)
+            | ir::ExpressionKind::Switch { .. } => {
+                instructions.append(self.generate_structured_expression(expression, false));
+            }
+        }
+        instructions
+    }
+
+    /// Generate an expression whose value is returned from the function.
+    ///
+    /// Calls to functions of the program in tail position become `return_call`s, so that tail
+    /// recursion runs in constant stack space.
+    fn generate_tail_expression<'a>(&self, expression: &'a ir::Expression) -> Instructions<'a> {
+        match &expression.kind {
+            ir::ExpressionKind::Call {
+                function,
+                arguments,
+            } if self.is_program_function(function) => {
+                let mut instructions = Instructions::default();
+                for argument in arguments {
+                    instructions.append(self.generate_expression(argument));
+                }
+                instructions.push(
+                    core::Instruction::ReturnCall(wast::token::Index::Id(wast::token::Id::new(
+                        function,
+                        self.generate_span(&expression.location),
+                    ))),
+                    &expression.location,
+                );
                 instructions
             }
-            ast::Expression::Identifier(identifier) => {
-                vec![core::Instruction::LocalGet(wast::token::Index::Id(
-                    self.generate_identifier(identifier),
-                ))]
+            ir::ExpressionKind::If { .. }
+            | ir::ExpressionKind::Block(_)
+            | ir::ExpressionKind::Switch { .. } => {
+                self.generate_structured_expression(expression, true)
             }
-            ast::Expression::IntegerLiteral(literal) => {
-                let value: i32 = literal.value.parse().unwrap();
-                vec![core::Instruction::I32Const(value)]
+            _ => self.generate_expression(expression),
+        }
+    }
+
+    /// Generate an `if`, block or switch expression, whose blocks are in tail position if `tail` is
+    /// set.
+    fn generate_structured_expression<'a>(
+        &self,
+        expression: &'a ir::Expression,
+        tail: bool,
+    ) -> Instructions<'a> {
+        let location = &expression.location;
+        let mut instructions = Instructions::default();
+        match &expression.kind {
+            ir::ExpressionKind::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                instructions.append(self.generate_expression(condition));
+                instructions.push(
+                    core::Instruction::If(Box::new(self.generate_block_type(Some(expression.ty)))),
+                    location,
+                );
+                instructions.append(self.generate_block(then_block, tail));
+                instructions.push(core::Instruction::Else(None), location);
+                instructions.append(self.generate_block(else_block, tail));
+                instructions.push(core::Instruction::End(None), location);
+            }
+            ir::ExpressionKind::Block(block) => {
+                instructions.push(
+                    core::Instruction::Block(Box::new(
+                        self.generate_block_type(Some(expression.ty)),
+                    )),
+                    location,
+                );
+                instructions.append(self.generate_block(block, tail));
+                instructions.push(core::Instruction::End(None), location);
+            }
+            // every case follows the end of a block, which the branch table breaks out of:
+            //   block (result T)
+            //     block                  ;; case n - 1
+            //       ...
+            //         block              ;; case 0
+            //           index
+            //           br_table ...
+            //         end
+            //         case 0
+            //         br n - 1
+            //       ...
+            //     end
+            //     case n - 1
+            //   end
+            ir::ExpressionKind::Switch {
+                index,
+                table,
+                default,
+                cases,
+            } => {
+                let label = |case: usize| wast::token::Index::Num(case as u32, self.span);
+                instructions.push(
+                    core::Instruction::Block(Box::new(
+                        self.generate_block_type(Some(expression.ty)),
+                    )),
+                    location,
+                );
+                for _ in cases {
+                    instructions.push(
+                        core::Instruction::Block(Box::new(self.generate_block_type(None))),
+                        location,
+                    );
+                }
+                instructions.append(self.generate_expression(index));
+                instructions.push(
+                    core::Instruction::BrTable(core::BrTableIndices {
+                        labels: table.iter().map(|case| label(*case)).collect(),
+                        default: label(*default),
+                    }),
+                    location,
+                );
+                for (case, block) in cases.iter().enumerate() {
+                    instructions.push(core::Instruction::End(None), location);
+                    instructions.append(self.generate_block(block, tail));
+                    if case + 1 < cases.len() {
+                        instructions.push(
+                            core::Instruction::Br(label(cases.len() - 1 - case)),
+                            location,
+                        );
+                    }
+                }
+                instructions.push(core::Instruction::End(None), location);
             }
+            _ => unreachable!("expected an if, block or switch expression"),
         }
+        instructions
     }
 
-    fn generate_parameters<'a>(&self, parameters: &'a ast::Parameters) -> CoreParameters<'a> {
-        parameters
-            .parameters
+    /// Whether the function is defined in the program, as opposed to imported from the standard
+    /// library.
+    fn is_program_function(&self, name: &str) -> bool {
+        self.program
+            .functions
             .iter()
-            .map(|param| {
-                (
-                    Some(self.generate_identifier(&param.name)),
-                    None,
-                    self.generate_type(&param.parameter_type),
-                )
-            })
-            .collect()
+            .any(|function| function.name == name)
+    }
+
+    /// Number of values returned by the function of the program, or 0 for functions of the
+    /// standard library, whose calls are only statements when they return no value.
+    fn result_count(&self, name: &str) -> usize {
+        self.program
+            .functions
+            .iter()
+            .find(|function| function.name == name)
+            .map_or(0, |function| function.results.len())
+    }
+
+    /// Select the instruction of a binary operator for operands of the given type.
+    fn generate_binary_operator<'a>(
+        &self,
+        operator: ir::BinaryOperator,
+        operand_type: ir::Type,
+    ) -> core::Instruction<'a> {
+        match operand_type {
+            ir::Type::I32 => match operator {
+                ir::BinaryOperator::Add => core::Instruction::I32Add,
+                ir::BinaryOperator::Subtract => core::Instruction::I32Sub,
+                ir::BinaryOperator::Multiply => core::Instruction::I32Mul,
+                ir::BinaryOperator::Divide => core::Instruction::I32DivS,
+                ir::BinaryOperator::DivideUnsigned => core::Instruction::I32DivU,
+                ir::BinaryOperator::Remainder => core::Instruction::I32RemS,
+                ir::BinaryOperator::RemainderUnsigned => core::Instruction::I32RemU,
+                ir::BinaryOperator::And => core::Instruction::I32And,
+                ir::BinaryOperator::Or => core::Instruction::I32Or,
+                ir::BinaryOperator::Xor => core::Instruction::I32Xor,
+                ir::BinaryOperator::ShiftLeft => core::Instruction::I32Shl,
+                ir::BinaryOperator::ShiftRight => core::Instruction::I32ShrS,
+                ir::BinaryOperator::ShiftRightUnsigned => core::Instruction::I32ShrU,
+                ir::BinaryOperator::Equal => core::Instruction::I32Eq,
+                ir::BinaryOperator::NotEqual => core::Instruction::I32Ne,
+                ir::BinaryOperator::LessThan => core::Instruction::I32LtS,
+                ir::BinaryOperator::LessThanUnsigned => core::Instruction::I32LtU,
+                ir::BinaryOperator::LessThanOrEqual => core::Instruction::I32LeS,
+                ir::BinaryOperator::LessThanOrEqualUnsigned => core::Instruction::I32LeU,
+                ir::BinaryOperator::GreaterThan => core::Instruction::I32GtS,
+                ir::BinaryOperator::GreaterThanUnsigned => core::Instruction::I32GtU,
+                ir::BinaryOperator::GreaterThanOrEqual => core::Instruction::I32GeS,
+                ir::BinaryOperator::GreaterThanOrEqualUnsigned => core::Instruction::I32GeU,
+            },
+            ir::Type::I64 => match operator {
+                ir::BinaryOperator::Add => core::Instruction::I64Add,
+                ir::BinaryOperator::Subtract => core::Instruction::I64Sub,
+                ir::BinaryOperator::Multiply => core::Instruction::I64Mul,
+                ir::BinaryOperator::Divide => core::Instruction::I64DivS,
+                ir::BinaryOperator::DivideUnsigned => core::Instruction::I64DivU,
+                ir::BinaryOperator::Remainder => core::Instruction::I64RemS,
+                ir::BinaryOperator::RemainderUnsigned => core::Instruction::I64RemU,
+                ir::BinaryOperator::And => core::Instruction::I64And,
+                ir::BinaryOperator::Or => core::Instruction::I64Or,
+                ir::BinaryOperator::Xor => core::Instruction::I64Xor,
+                ir::BinaryOperator::ShiftLeft => core::Instruction::I64Shl,
+                ir::BinaryOperator::ShiftRight => core::Instruction::I64ShrS,
+                ir::BinaryOperator::ShiftRightUnsigned => core::Instruction::I64ShrU,
+                ir::BinaryOperator::Equal => core::Instruction::I64Eq,
+                ir::BinaryOperator::NotEqual => core::Instruction::I64Ne,
+                ir::BinaryOperator::LessThan => core::Instruction::I64LtS,
+                ir::BinaryOperator::LessThanUnsigned => core::Instruction::I64LtU,
+                ir::BinaryOperator::LessThanOrEqual => core::Instruction::I64LeS,
+                ir::BinaryOperator::LessThanOrEqualUnsigned => core::Instruction::I64LeU,
+                ir::BinaryOperator::GreaterThan => core::Instruction::I64GtS,
+                ir::BinaryOperator::GreaterThanUnsigned => core::Instruction::I64GtU,
+                ir::BinaryOperator::GreaterThanOrEqual => core::Instruction::I64GeS,
+                ir::BinaryOperator::GreaterThanOrEqualUnsigned => core::Instruction::I64GeU,
+            },
+            ir::Type::F32 => match operator {
+                ir::BinaryOperator::Add => core::Instruction::F32Add,
+                ir::BinaryOperator::Subtract => core::Instruction::F32Sub,
+                ir::BinaryOperator::Multiply => core::Instruction::F32Mul,
+                ir::BinaryOperator::Divide => core::Instruction::F32Div,
+                ir::BinaryOperator::Equal => core::Instruction::F32Eq,
+                ir::BinaryOperator::NotEqual => core::Instruction::F32Ne,
+                ir::BinaryOperator::LessThan => core::Instruction::F32Lt,
+                ir::BinaryOperator::LessThanOrEqual => core::Instruction::F32Le,
+                ir::BinaryOperator::GreaterThan => core::Instruction::F32Gt,
+                ir::BinaryOperator::GreaterThanOrEqual => core::Instruction::F32Ge,
+                operator => unreachable!("`{}` is only defined on integers", operator),
+            },
+            ir::Type::F64 => match operator {
+                ir::BinaryOperator::Add => core::Instruction::F64Add,
+                ir::BinaryOperator::Subtract => core::Instruction::F64Sub,
+                ir::BinaryOperator::Multiply => core::Instruction::F64Mul,
+                ir::BinaryOperator::Divide => core::Instruction::F64Div,
+                ir::BinaryOperator::Equal => core::Instruction::F64Eq,
+                ir::BinaryOperator::NotEqual => core::Instruction::F64Ne,
+                ir::BinaryOperator::LessThan => core::Instruction::F64Lt,
+                ir::BinaryOperator::LessThanOrEqual => core::Instruction::F64Le,
+                ir::BinaryOperator::GreaterThan => core::Instruction::F64Gt,
+                ir::BinaryOperator::GreaterThanOrEqual => core::Instruction::F64Ge,
+                operator => unreachable!("`{}` is only defined on integers", operator),
+            },
+        }
+    }
+
+    /// Select the instruction converting a value between types, if their representation differs.
+    ///
+    /// Floats are truncated with saturation, like `as` casts in Rust.
+    fn generate_conversion<'a>(
+        &self,
+        from: ir::Type,
+        to: ir::Type,
+        signed: bool,
+    ) -> Option<core::Instruction<'a>> {
+        use ir::Type::*;
+        Some(match (from, to, signed) {
+            (I32, I64, true) => core::Instruction::I64ExtendI32S,
+            (I32, I64, false) => core::Instruction::I64ExtendI32U,
+            (I64, I32, _) => core::Instruction::I32WrapI64,
+            (I32, F32, true) => core::Instruction::F32ConvertI32S,
+            (I32, F32, false) => core::Instruction::F32ConvertI32U,
+            (I64, F32, true) => core::Instruction::F32ConvertI64S,
+            (I64, F32, false) => core::Instruction::F32ConvertI64U,
+            (I32, F64, true) => core::Instruction::F64ConvertI32S,
+            (I32, F64, false) => core::Instruction::F64ConvertI32U,
+            (I64, F64, true) => core::Instruction::F64ConvertI64S,
+            (I64, F64, false) => core::Instruction::F64ConvertI64U,
+            (F32, I32, true) => core::Instruction::I32TruncSatF32S,
+            (F32, I32, false) => core::Instruction::I32TruncSatF32U,
+            (F64, I32, true) => core::Instruction::I32TruncSatF64S,
+            (F64, I32, false) => core::Instruction::I32TruncSatF64U,
+            (F32, I64, true) => core::Instruction::I64TruncSatF32S,
+            (F32, I64, false) => core::Instruction::I64TruncSatF32U,
+            (F64, I64, true) => core::Instruction::I64TruncSatF64S,
+            (F64, I64, false) => core::Instruction::I64TruncSatF64U,
+            (F32, F64, _) => core::Instruction::F64PromoteF32,
+            (F64, F32, _) => core::Instruction::F32DemoteF64,
+            (I32, I32, _) | (I64, I64, _) | (F32, F32, _) | (F64, F64, _) => return None,
+        })
+    }
+
+    fn generate_block_type<'a>(&self, result: Option<ir::Type>) -> core::BlockType<'a> {
+        core::BlockType {
+            label: None,
+            label_name: None,
+            ty: core::TypeUse {
+                index: None,
+                inline: result.map(|result| core::FunctionType {
+                    params: Box::new([]),
+                    results: Box::new([self.generate_type(result)]),
+                }),
+            },
+        }
+    }
+
+    /// Span pointing at the start of the location in the source.
+    fn generate_span(&self, location: &ir::Location) -> Span {
+        Span::from_offset(location.start.index)
+    }
+
+    /// Memory argument of a naturally aligned access to a value of the given type.
+    fn generate_memarg<'a>(&self, ty: ir::Type, offset: u32) -> core::MemArg<'a> {
+        core::MemArg {
+            align: match ty {
+                ir::Type::I32 | ir::Type::F32 => 4,
+                ir::Type::I64 | ir::Type::F64 => 8,
+            },
+            offset: offset.into(),
+            memory: wast::token::Index::Num(0, self.span),
+        }
+    }
+
+    fn generate_local<'a>(&self, local: ir::LocalId) -> wast::token::Index<'a> {
+        wast::token::Index::Num(local.0 as u32, self.span)
     }
 
-    fn generate_identifier<'a>(&self, identifier: &'a ast::Identifier) -> wast::token::Id<'a> {
-        wast::token::Id::new(&identifier.name, self.span)
+    /// The module imports no globals, so globals are numbered like in the IR.
+    fn generate_global<'a>(&self, global: ir::GlobalId) -> wast::token::Index<'a> {
+        wast::token::Index::Num(global.0 as u32, self.span)
     }
 
-    fn generate_type<'a>(&self, ast_type: &'a ast::Type) -> core::ValType<'a> {
-        match ast_type.name {
-            ast::TypeKind::I32 => core::ValType::I32,
-            ast::TypeKind::I64 => core::ValType::I64,
+    fn generate_constant<'a>(&self, constant: ir::Constant) -> core::Instruction<'a> {
+        match constant {
+            ir::Constant::I32(value) => core::Instruction::I32Const(value),
+            ir::Constant::I64(value) => core::Instruction::I64Const(value),
+            ir::Constant::F32(value) => core::Instruction::F32Const(wast::token::F32 {
+                bits: value.to_bits(),
+            }),
+            ir::Constant::F64(value) => core::Instruction::F64Const(wast::token::F64 {
+                bits: value.to_bits(),
+            }),
+        }
+    }
+
+    fn generate_type<'a>(&self, ty: ir::Type) -> core::ValType<'a> {
+        match ty {
+            ir::Type::I32 => core::ValType::I32,
+            ir::Type::I64 => core::ValType::I64,
+            ir::Type::F32 => core::ValType::F32,
+            ir::Type::F64 => core::ValType::F64,
         }
     }
 }
@@ -367,14 +978,18 @@ impl CodeGenerator<'_> {
 mod tests {
     use super::*;
     use ::core::str;
-    use anyhow::Context;
+    use anyhow::{Context, Result};
     use indoc::indoc;
+    use optimizer::{optimize, OptimizationLevel};
     use pretty_assertions::assert_eq;
     use wasmtime::{
         component::{Component, Linker, Val},
         Config, Engine, Store,
     };
-    use wasmtime_wasi::{pipe::MemoryOutputPipe, ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
+    use wasmtime_wasi::{
+        pipe::{MemoryInputPipe, MemoryOutputPipe},
+        ResourceTable, WasiCtx, WasiCtxBuilder, WasiView,
+    };
 
     struct State {
         ctx: WasiCtx,
@@ -391,13 +1006,31 @@ mod tests {
         }
     }
 
-    fn compile(source: &str) -> Result<Vec<u8>> {
+    fn lower_optimized(source: &str, level: OptimizationLevel) -> Result<ir::Program> {
         let tokens = tokenizer::tokenize(source.to_string());
         let ast = parser::parse(tokens);
-        let mut generator = CodeGenerator::new(ast)?;
-        let mut wat = generator.generate()?;
-        let wasm = wat.encode()?;
-        Ok(wasm)
+        let mut ir = lower(&ast)?;
+        optimize(&mut ir, level);
+        Ok(ir)
+    }
+
+    fn compile(source: &str, level: OptimizationLevel) -> Result<Vec<u8>> {
+        let ir = lower_optimized(source, level)?;
+        Ok(CodeGenerator::new(ir).generate()?)
+    }
+
+    /// Number of instructions in the functions generated from the source.
+    fn instruction_count(source: &str, level: OptimizationLevel) -> usize {
+        let ir = lower_optimized(source, level).unwrap();
+        let generator = CodeGenerator::new(ir);
+        generator
+            .generate_functions()
+            .iter()
+            .map(|function| match &function.kind {
+                core::FuncKind::Inline { expression, .. } => expression.instrs.len(),
+                core::FuncKind::Import(_) => 0,
+            })
+            .sum()
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -407,8 +1040,17 @@ mod tests {
     }
 
     fn run(source: &str) -> Result<Output> {
+        run_optimized(source, OptimizationLevel::O0)
+    }
+
+    fn run_optimized(source: &str, level: OptimizationLevel) -> Result<Output> {
+        run_with_input(source, level, "")
+    }
+
+    fn run_with_input(source: &str, level: OptimizationLevel, input: &str) -> Result<Output> {
         let mut config = Config::new();
         config.wasm_component_model(true);
+        config.wasm_tail_call(true);
 
         let engine = Engine::new(&config)?;
 
@@ -419,6 +1061,7 @@ mod tests {
         let stderr_stream = MemoryOutputPipe::new(1024);
 
         let mut builder = WasiCtxBuilder::new();
+        builder.stdin(MemoryInputPipe::new(input.to_string()));
         builder.stdout(stdout_steram.clone());
         builder.stderr(stderr_stream.clone());
         let wasi_ctx = builder.build();
@@ -431,7 +1074,7 @@ mod tests {
             },
         );
 
-        let wasm = compile(source)?;
+        let wasm = compile(source, level)?;
         let component = Component::from_binary(&engine, &wasm)?;
 
         let instance = linker.instantiate(&mut store, &component)?;
@@ -478,6 +1121,13 @@ mod tests {
         assert_eq!(stdout, "1234");
     }
 
+    #[test]
+    fn print_i64() {
+        let source = "fn main() -> i32 { print_i64(-1234567890123); 0 }";
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "-1234567890123");
+    }
+
     #[test]
     fn print_char() {
         let source = "fn main() -> i32 { print_char(65); 0 }";
@@ -485,6 +1135,122 @@ mod tests {
         assert_eq!(stdout, "A");
     }
 
+    #[test]
+    fn alloc_and_free() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: i32 = alloc(16);
+                let b: i32 = alloc(10);
+                print_int(b - a);
+                print_char(32);
+                free(a);
+                print_int(alloc(12) - a);
+                print_char(32);
+                let c: i32 = alloc(200000);
+                let d: i32 = alloc(1);
+                print_int(d - c);
+                print_char(32);
+                print_int(c - c / 8 * 8 + d - d / 8 * 8);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "24 0 200008 0");
+    }
+
+    #[test]
+    fn read_int() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: i32 = read_int();
+                let b: i32 = read_int();
+                let c: i64 = read_i64();
+                print_int(a + b);
+                print_char(32);
+                print_i64(c * 2 as i64);
+                print_char(32);
+                print_int(read_int());
+                print_char(32);
+                print_int(read_int());
+                print_char(32);
+                print_i64(read_i64());
+                print_char(32);
+                print_i64(read_i64());
+                0
+            }
+        "};
+        let input = "  12\n-5\t4294967296\n -2147483648 2147483647\n\
+                     -9223372036854775808 9223372036854775807";
+        let stdout = run_with_input(source, OptimizationLevel::O0, input)
+            .unwrap()
+            .stdout;
+        assert_eq!(
+            stdout,
+            "7 8589934592 -2147483648 2147483647 -9223372036854775808 9223372036854775807"
+        );
+    }
+
+    #[test]
+    fn read_char_and_line() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                print_int(read_int());
+                print_char(32);
+                let rest: [i32] = read_line();
+                print_int(len(rest));
+                print_char(rest[1]);
+                print_char(32);
+                print_char(read_char());
+                let line: [i32] = read_line();
+                print_int(len(line));
+                print_char(line[1]);
+                print_char(32);
+                print_int(len(read_line()));
+                print_char(32);
+                let long: [i32] = read_line();
+                print_int(len(long));
+                print_char(long[35]);
+                print_char(32);
+                print_int(read_char());
+                0
+            }
+        "};
+        let input = "3 abc\r\nxyz\n\nabcdefghijklmnopqrstuvwxyz0123456789";
+        let stdout = run_with_input(source, OptimizationLevel::O0, input)
+            .unwrap()
+            .stdout;
+        assert_eq!(stdout, "3 4a x2z 0 369 -1");
+    }
+
+    #[test]
+    fn read_lines_until_end_of_input() {
+        let source = indoc! {"
+            fn count_lines(count: i32, characters: i32) -> i32 {
+                let line: [i32] = read_line();
+                if len(line) == 1 && line[0] < 0 {
+                    print_int(count);
+                    print_char(32);
+                    characters
+                } else {
+                    count_lines(count + 1, characters + len(line))
+                } as i32
+            }
+
+            fn main() -> i32 {
+                print_int(count_lines(0, 0));
+                print_char(32);
+                print_int(len(read_line()));
+                0
+            }
+        "};
+        for (input, expected) in [("", "0 0 1"), ("a\n\nbc", "3 3 1"), ("a\n\nbc\n", "3 3 1")] {
+            let stdout = run_with_input(source, OptimizationLevel::O0, input)
+                .unwrap()
+                .stdout;
+            assert_eq!(stdout, expected, "{:?}", input);
+        }
+    }
+
     #[test]
     fn hello_world() {
         let source = indoc! {"
@@ -546,16 +1312,43 @@ mod tests {
     }
 
     #[test]
-    fn if_statement() {
+    fn compound_assignment() {
         let source = indoc! {"
             fn main() -> i32 {
-                if 1 {
-                    if 0 {
-                        print_int(1);
-                    } else {
-                        if 0 {
+                var a: i32 = 10;
+                var b: u32 = 4294967295;
+                var c: f64 = 1.5;
+                a += 5;
+                a -= 3;
+                a *= 4;
+                print_int(a /= 5);
+                print_char(32); // ' '
+                a %= 5;
+                print_int(a);
+                print_char(32); // ' '
+                b /= 2;
+                print_i64(b as i64);
+                print_char(32); // ' '
+                c *= 3;
+                print_f64(c);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "9 4 2147483647 4.500000");
+    }
+
+    #[test]
+    fn if_statement() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                if true {
+                    if false {
+                        print_int(1);
+                    } else {
+                        if false {
                             print_int(2);
-                        } else if 1 {
+                        } else if true {
                             print_int(3);
                         } else {
                             print_int(4);
@@ -575,11 +1368,11 @@ mod tests {
     fn if_expression() {
         let source = indoc! {"
             fn main() -> i32 {
-                print_int(if 1 { 1 } else { 2 } as i32);
+                print_int(if true { 1 } else { 2 } as i32);
                 print_char(32); // ' '
-                print_int(if 0 { 3 } else { 4 } as i32);
+                print_int(if false { 3 } else { 4 } as i32);
                 print_char(32); // ' '
-                print_int(if 0 { 5 } else if 1 { 6 } else { 7 } as i32);
+                print_int(if false { 5 } else if true { 6 } else { 7 } as i32);
                 0
             }
         "};
@@ -591,94 +1384,93 @@ mod tests {
     fn comparison_expression() {
         let source = indoc! {"
             fn main() -> i32 {
-                print_int(1 == 1);
+                print_bool(1 == 1);
                 print_char(32); // ' '
-                print_int(1 != 1);
+                print_bool(1 != 1);
                 print_char(32); // ' '
-                print_int(1 < 1);
+                print_bool(1 < 1);
                 print_char(32); // ' '
-                print_int(1 <= 1);
+                print_bool(1 <= 1);
                 print_char(32); // ' '
-                print_int(1 > 1);
+                print_bool(1 > 1);
                 print_char(32); // ' '
-                print_int(1 >= 1);
+                print_bool(1 >= 1);
+                print_char(32); // ' '
+                print_bool(true == (1 < 2));
+                print_char(32); // ' '
+                print_bool(true != true);
                 0
             }
         "};
         let stdout = run(source).unwrap().stdout;
-        assert_eq!(stdout, "1 0 0 1 0 1");
+        assert_eq!(stdout, "true false false true false true true false");
     }
 
     #[test]
     fn logical_expression_with_boolean() {
         let source = indoc! {"
             fn main() -> i32 {
-                print_int(1 && 1);
+                print_bool(true && true);
                 print_char(32); // ' '
-                print_int(1 && 0);
+                print_bool(true && false);
                 print_char(32); // ' '
-                print_int(0 && 1);
+                print_bool(false && true);
                 print_char(32); // ' '
-                print_int(0 && 0);
+                print_bool(false && false);
                 print_char(32); // ' '
-                print_int(1 || 1);
+                print_bool(true || true);
                 print_char(32); // ' '
-                print_int(1 || 0);
+                print_bool(true || false);
                 print_char(32); // ' '
-                print_int(0 || 1);
+                print_bool(false || true);
                 print_char(32); // ' '
-                print_int(0 || 0);
+                print_bool(false || false);
                 print_char(32); // ' '
-                print_int(!1);
+                print_bool(!true);
                 print_char(32); // ' '
-                print_int(!0);
+                print_bool(!!true);
                 0
             }
         "};
         let stdout = run(source).unwrap().stdout;
-        assert_eq!(stdout, "1 0 0 0 1 1 1 0 0 1");
+        assert_eq!(
+            stdout,
+            "true false false false true true true false false true"
+        );
     }
 
     #[test]
-    fn logical_expression_with_i32() {
+    fn bool_variables_and_casts() {
         let source = indoc! {"
+            fn is_positive(n: i32) -> bool {
+                n > 0
+            }
+
             fn main() -> i32 {
-                print_int(2 && -3);
-                print_char(32); // ' '
-                print_int(2 && 0);
-                print_char(32); // ' '
-                print_int(0 && -3);
-                print_char(32); // ' '
-                print_int(0 && 0);
-                print_char(32); // ' '
-                print_int(2 || -3);
-                print_char(32); // ' '
-                print_int(2 || 0);
-                print_char(32); // ' '
-                print_int(0 || -3);
-                print_char(32); // ' '
-                print_int(0 || 0);
-                print_char(32); // ' '
-                print_int(!2);
-                print_char(32); // ' '
-                print_int(!-3);
+                var found: bool = false;
+                let n: i32 = read_int();
+                if is_positive(n) && n != 2 {
+                    found = true;
+                }
+                print_bool(found);
                 print_char(32); // ' '
-                print_int(!!--3);
+                print_int(found as i32 + is_positive(n) as i32);
                 print_char(32); // ' '
-                print_int(!0);
+                print_i64(!found as i64);
                 0
             }
         "};
-        let stdout = run(source).unwrap().stdout;
-        assert_eq!(stdout, "1 0 0 0 1 1 1 0 0 0 1 1");
+        let stdout = run_with_input(source, OptimizationLevel::O0, "3")
+            .unwrap()
+            .stdout;
+        assert_eq!(stdout, "true 2 0");
     }
 
     #[test]
     fn functions() {
         let source = indoc! {"
-            fn print_space() -> i32 {
+            fn print_space() {
                 print_char(32); // ' '
-                0
             }
 
             fn add(a: i32, b: i32) -> i32 {
@@ -716,6 +1508,170 @@ mod tests {
         assert_eq!(stdout, "6912 4444 7006652 4 5678 5678");
     }
 
+    #[test]
+    fn type_inference() {
+        let source = indoc! {"
+            fn main() {
+                let big = 3000000000;
+                var sum = 0;
+                sum = sum + big * 2;
+                print_i64(sum);
+                print_char(32); // ' '
+                let x = 1.5;
+                print_f64(x * 2.0);
+                print_char(32); // ' '
+                let values = [1, 2, 3];
+                var total = values[0];
+                total += values[1] * values[2];
+                print_int(total);
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "6000000000 3.000000 7");
+    }
+
+    #[test]
+    fn constants_and_statics() {
+        let source = indoc! {"
+            const LIMIT: i32 = 3 * 4;
+            const TOTAL: i64 = LIMIT as i64 / 2;
+            const SCALE: f64 = 1.5 * 2.0;
+            static var calls: i32 = 0;
+            static var total: i64 = TOTAL;
+
+            fn count(n: i32) -> i32 {
+                calls += 1;
+                total = total + n as i64;
+                calls
+            }
+
+            fn main() {
+                count(1);
+                count(2);
+                print_int(count(LIMIT));
+                print_char(32); // ' '
+                print_i64(total);
+                print_char(32); // ' '
+                print_int(calls = calls * 2);
+                print_char(32); // ' '
+                let values = [0; LIMIT];
+                print_int(len(values));
+                print_char(32); // ' '
+                print_f64(SCALE);
+            }
+        "};
+        for level in [
+            OptimizationLevel::O0,
+            OptimizationLevel::O1,
+            OptimizationLevel::O2,
+        ] {
+            let stdout = run_optimized(source, level).unwrap().stdout;
+            assert_eq!(stdout, "3 21 6 12 3.000000", "at -O{}", level);
+        }
+    }
+
+    #[test]
+    fn tuples_and_multiple_return_values() {
+        let source = indoc! {"
+            fn divmod(a: i32, b: i32) -> (i32, i32) {
+                (a / b, a % b)
+            }
+
+            fn scale(a: i64, b: i64) -> (i64, i64) {
+                (a * 2, b * 3)
+            }
+
+            fn swapped(a: i32, b: i32) -> (i32, i32) {
+                let (q, r) = divmod(b, a);
+                divmod(q + r, 1)
+            }
+
+            fn main() {
+                let (q, r) = divmod(17, 5);
+                print_int(q);
+                print_char(32); // ' '
+                print_int(r);
+                print_char(32); // ' '
+                let (x, y) = scale(4, 9);
+                print_i64(y - x);
+                print_char(32); // ' '
+                var (a, b) = (1, 2);
+                let (a, b) = (b, a);
+                print_int(a * 10 + b);
+                print_char(32); // ' '
+                let (x, _y) = swapped(3, 10);
+                print_int(x);
+                divmod(1, 1);
+            }
+        "};
+        for level in [
+            OptimizationLevel::O0,
+            OptimizationLevel::O1,
+            OptimizationLevel::O2,
+        ] {
+            let stdout = run_optimized(source, level).unwrap().stdout;
+            assert_eq!(stdout, "3 2 19 21 4", "at -O{}", level);
+        }
+    }
+
+    #[test]
+    fn block_expressions() {
+        let source = indoc! {"
+            fn main() {
+                var a = { let x = 3; let y = 4; x * x + y * y };
+                print_int(a);
+                print_char(32); // ' '
+                let b: i64 = { let x = 5; x * 2 } + 1;
+                print_i64(b);
+                print_char(32); // ' '
+                print_int({ a = a + 1; a } * 2);
+                print_char(32); // ' '
+                let x = 7;
+                let y = { let x = x * 2; x + 1 };
+                print_int(y + x);
+                print_char(32); // ' '
+                print_int({ print_int(1); 2 });
+            }
+        "};
+        for level in [
+            OptimizationLevel::O0,
+            OptimizationLevel::O1,
+            OptimizationLevel::O2,
+        ] {
+            let stdout = run_optimized(source, level).unwrap().stdout;
+            assert_eq!(stdout, "25 11 52 22 12", "at -O{}", level);
+        }
+    }
+
+    #[test]
+    fn functions_without_return_values() {
+        let source = indoc! {"
+            fn print_pair(a: i32, b: i32) {
+                print_int(a);
+                print_char(44); // ','
+                print_int(b)
+            }
+
+            fn countdown(n: i32) {
+                if n > 0 {
+                    print_int(n);
+                    print_char(32); // ' '
+                    countdown(n - 1);
+                }
+            }
+
+            fn main() {
+                print_pair(1, 2);
+                print_char(32); // ' '
+                countdown(3);
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "1,2 3 2 1 ");
+        let stdout = run_optimized(source, OptimizationLevel::O2).unwrap().stdout;
+        assert_eq!(stdout, "1,2 3 2 1 ");
+    }
+
     #[test]
     fn calculate() {
         let source = indoc! {"
@@ -728,4 +1684,714 @@ mod tests {
         let stdout = run(source).unwrap().stdout;
         assert_eq!(stdout, "5");
     }
+
+    #[test]
+    fn i64_arithmetic() {
+        let source = indoc! {"
+            fn square(x: i64) -> i64 {
+                x * x
+            }
+
+            fn main() -> i32 {
+                let a: i64 = 4294967296;
+                let b: i64 = 3000000000;
+                print_i64(a + b);
+                print_char(32); // ' '
+                print_i64(a - b * 2);
+                print_char(32); // ' '
+                print_i64(square(b));
+                print_char(32); // ' '
+                print_i64(square(b) / -a);
+                print_char(32); // ' '
+                print_i64(if a > b { a } else { b } as i64);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(
+            stdout,
+            "7294967296 -1705032704 9000000000000000000 -2095475792 4294967296"
+        );
+    }
+
+    #[test]
+    fn i64_comparison_and_logical_expression() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: i64 = 4294967296;
+                let b: i64 = 4294967297;
+                print_bool(a < b);
+                print_char(32); // ' '
+                print_bool(a >= b);
+                print_char(32); // ' '
+                print_bool(a == b - 1);
+                print_char(32); // ' '
+                print_bool(a != 4294967296);
+                print_char(32); // ' '
+                print_bool(a != 0 && b == 0);
+                print_char(32); // ' '
+                print_bool(a != 0 || b == 0);
+                print_char(32); // ' '
+                print_bool(!(a < b));
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "true false true false false true false");
+    }
+
+    #[test]
+    fn cast_expression() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: i32 = 2147483647;
+                let b: i64 = 4294967298;
+                print_i64(a as i64 + 1);
+                print_char(32); // ' '
+                print_int(b as i32);
+                print_char(32); // ' '
+                print_i64(-a as i64 * 2);
+                print_char(32); // ' '
+                print_int(a + 1 as i64 as i32);
+                print_char(32); // ' '
+                print_i64(5000000000 as i64);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "2147483648 2 -4294967294 -2147483648 5000000000");
+    }
+
+    #[test]
+    fn unsigned_integers() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: u32 = 4294967295;
+                let b: u64 = 18446744073709551615;
+                print_i64(a as i64);
+                print_char(32); // ' '
+                print_bool(a > 1);
+                print_char(32); // ' '
+                print_i64((a / 2) as i64);
+                print_char(32); // ' '
+                print_i64(b as i64);
+                print_char(32); // ' '
+                print_i64((b / 2) as i64);
+                print_char(32); // ' '
+                print_bool(b >= 9223372036854775808);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(
+            stdout,
+            "4294967295 true 2147483647 -1 9223372036854775807 true"
+        );
+    }
+
+    #[test]
+    fn floats() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let x: f64 = 1.5;
+                let y: f32 = 2.25;
+                let z: f64 = 0.0 - 3.7;
+                print_f64(x * 2.0 + 1);
+                print_char(32); // ' '
+                print_f64(y as f64 / 2.0);
+                print_char(32); // ' '
+                print_f64(z);
+                print_char(32); // ' '
+                print_bool(x < 2.0);
+                print_char(32); // ' '
+                print_f64(0.1 + 0.2);
+                print_char(32); // ' '
+                print_f64(0.9999999);
+                print_char(32); // ' '
+                print_f64(100000000000000000000.0);
+                print_char(32); // ' '
+                print_f64(1.0 / 0.0);
+                print_char(32); // ' '
+                print_f64(0.0 / 0.0);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(
+            stdout,
+            "4.000000 1.125000 -3.700000 true 0.300000 1.000000 100000000000000000000.000000 inf NaN"
+        );
+    }
+
+    #[test]
+    fn large_floats() {
+        let values = [1e18, 2f64.powi(63), u64::MAX as f64, 1e23, 1e300, f64::MAX];
+        let mut source = "fn main() -> i32 {\n".to_string();
+        for value in values {
+            source += &format!("    print_f64({:.1});\n    print_char(32);\n", value);
+        }
+        source +=
+            "    let max: u64 = 18446744073709551615;\n    print_f64(max as f64);\n    0\n}\n";
+        let expected: Vec<String> = values
+            .iter()
+            .chain([u64::MAX as f64].iter())
+            .map(|value| format!("{:.6}", value))
+            .collect();
+        let stdout = run(&source).unwrap().stdout;
+        assert_eq!(stdout, expected.join(" "));
+        assert!(stdout.ends_with(" 18446744073709551616.000000"));
+    }
+
+    #[test]
+    fn float_casts() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let z: f64 = 0.0 - 3.7;
+                let big: f64 = 10000000000.0;
+                let a: u32 = 4294967295;
+                print_int(z as i32);
+                print_char(32); // ' '
+                print_int(big as i32);
+                print_char(32); // ' '
+                print_i64(z as u32 as i64);
+                print_char(32); // ' '
+                print_f64(a as f64);
+                print_char(32); // ' '
+                print_f64(7 as f64 / 2);
+                print_char(32); // ' '
+                print_f64(big as f32 as f64);
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(
+            stdout,
+            "-3 2147483647 0 4294967295.000000 3.500000 10000000000.000000"
+        );
+    }
+
+    #[test]
+    fn remainder_bitwise_and_shift_operators() {
+        let source = indoc! {"
+            fn digit_sum(n: i32) -> i32 {
+                if n == 0 { 0 } else { n % 10 + digit_sum(n / 10) } as i32
+            }
+
+            fn main() -> i32 {
+                let a: i32 = 0 - 7;
+                let b: u32 = 4294967295;
+                let c: i64 = 1;
+                print_int(a % 3);
+                print_char(32); // ' '
+                print_i64((b % 10) as i64);
+                print_char(32); // ' '
+                print_int(digit_sum(9875));
+                print_char(32); // ' '
+                print_int(12 & 10 | 1);
+                print_char(32); // ' '
+                print_int(12 ^ 10);
+                print_char(32); // ' '
+                print_i64(c << 40);
+                print_char(32); // ' '
+                print_int(a >> 1);
+                print_char(32); // ' '
+                print_i64((b >> 28) as i64);
+                print_char(32); // ' '
+                print_bool(true ^ true | false);
+                print_char(32); // ' '
+                print_bool((17 % 2 == 1) & (5 > 3));
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "-1 5 29 9 6 1099511627776 -4 15 false true");
+    }
+
+    #[test]
+    fn arrays() {
+        let source = indoc! {"
+            fn sum(values: [i64], index: i32) -> i64 {
+                if index < len(values) { values[index] + sum(values, index + 1) } else { 0 } as i64
+            }
+
+            fn main() -> i32 {
+                var a: [i32; 3] = [1, 2, 3];
+                a[1] = a[0] + a[2];
+                print_int(a[1]);
+                print_char(32); // ' '
+                let n: i32 = read_int();
+                let b: [i64] = [5000000000; n];
+                b[n - 1] = 1;
+                print_i64(sum(b, 0));
+                print_char(32); // ' '
+                print_int(a[2] = 7);
+                print_int(len(b));
+                0
+            }
+        "};
+        for level in [OptimizationLevel::O0, OptimizationLevel::O2] {
+            let stdout = run_with_input(source, level, "4").unwrap().stdout;
+            assert_eq!(stdout, "4 15000000001 74");
+        }
+    }
+
+    #[test]
+    fn float_arrays() {
+        let source = indoc! {"
+            fn sum(values: [f64], index: i32) -> f64 {
+                if index < len(values) { values[index] + sum(values, index + 1) } else { 0.0 } as f64
+            }
+
+            fn main() -> i32 {
+                var a: [f64; 3] = [1.5, 2.25, 0.0];
+                a[2] = a[0] * a[1];
+                print_f64(sum(a, 0));
+                print_char(32); // ' '
+                let n: i32 = read_int();
+                let b: [f32] = [0.5; n];
+                b[n - 1] = b[0] + 2.0;
+                print_f64(b[0] as f64 + b[n - 1] as f64);
+                print_char(32); // ' '
+                print_int(len(b));
+                0
+            }
+        "};
+        for level in [OptimizationLevel::O0, OptimizationLevel::O2] {
+            let stdout = run_with_input(source, level, "4").unwrap().stdout;
+            assert_eq!(stdout, "7.125000 3.000000 4");
+        }
+    }
+
+    #[test]
+    fn structs() {
+        let source = indoc! {"
+            struct Point {
+                x: i32,
+                y: i64,
+            }
+
+            struct Segment {
+                start: Point,
+                end: Point,
+                lengths: [i32],
+            }
+
+            fn length(segment: Segment) -> i64 {
+                segment.end.y - segment.start.y + (segment.end.x - segment.start.x) as i64
+            }
+
+            fn main() -> i32 {
+                let start: Point = Point { x: 1, y: 2 };
+                let segment: Segment = Segment {
+                    start: start,
+                    end: Point { y: 5000000000, x: read_int() },
+                    lengths: [0; 2],
+                };
+                segment.lengths[1] = 3;
+                start.x = 0;
+                print_i64(length(segment));
+                print_char(32); // ' '
+                print_int(segment.end.x = 9);
+                print_int(segment.lengths[1]);
+                0
+            }
+        "};
+        for level in [OptimizationLevel::O0, OptimizationLevel::O2] {
+            let stdout = run_with_input(source, level, "4").unwrap().stdout;
+            assert_eq!(stdout, "5000000002 93");
+        }
+    }
+
+    #[test]
+    fn enums_and_match() {
+        let source = indoc! {"
+            enum Color { Red, Green, Blue }
+
+            enum Shape {
+                Circle(i32),
+                Rectangle(i32, i64),
+                Empty,
+            }
+
+            fn area(shape: Shape) -> i64 {
+                match shape {
+                    Shape::Circle(r) => 3 * r as i64 * r as i64,
+                    Shape::Rectangle(width, height) => width as i64 * height,
+                    Shape::Empty => 0,
+                }
+            }
+
+            fn grade(score: i32) -> i32 {
+                match score {
+                    100 => 65, // 'A'
+                    90..=99 => 65,
+                    80..90 => 66,
+                    0..80 => 70,
+                    _ => 63, // '?'
+                }
+            }
+
+            fn sign(n: i64) -> i32 {
+                match n {
+                    -9223372036854775808..0 => -1,
+                    0 => 0,
+                    1..=9223372036854775807 => 1,
+                }
+            }
+
+            fn main() -> i32 {
+                let color: Color = Color::Green;
+                print_int(match color { Color::Red => 1, Color::Green => 2, Color::Blue => 3 });
+                print_int(Color::Blue as i32);
+                print_char(32); // ' '
+                print_i64(area(Shape::Circle(2)) + area(Shape::Rectangle(3, 5000000000)) + area(Shape::Empty));
+                print_char(32); // ' '
+                print_char(grade(read_int()));
+                print_char(grade(100));
+                print_char(grade(-5));
+                print_char(32); // ' '
+                print_int(sign(-7));
+                print_int(sign(0));
+                print_int(sign(42));
+                0
+            }
+        "};
+        for level in [OptimizationLevel::O0, OptimizationLevel::O2] {
+            let stdout = run_with_input(source, level, "85").unwrap().stdout;
+            assert_eq!(stdout, "22 15000000012 BA? -101");
+        }
+    }
+
+    #[test]
+    fn array_index_out_of_bounds() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: [i32] = [0; 2];
+                print_int(a[1]);
+                print_int(a[read_int()]);
+                0
+            }
+        "};
+        let error = run_with_input(source, OptimizationLevel::O0, "-1").unwrap_err();
+        assert!(format!("{:?}", error).contains("index_out_of_bounds"));
+    }
+
+    #[test]
+    fn logical_expression_short_circuit() {
+        let source = indoc! {"
+            fn print_and_return(value: i32) -> bool {
+                print_int(value);
+                value != 0
+            }
+
+            fn main() -> i32 {
+                let x: i32 = 0;
+                print_bool(x != 0 && 10 / x > 1);
+                print_char(32); // ' '
+                print_bool(x == 0 || 10 / x > 1);
+                print_char(32); // ' '
+                print_bool(print_and_return(0) && print_and_return(1));
+                print_char(32); // ' '
+                print_bool(print_and_return(2) || print_and_return(3));
+                print_char(32); // ' '
+                print_bool(print_and_return(4) && print_and_return(0));
+                print_char(32); // ' '
+                print_bool(print_and_return(0) || print_and_return(5));
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "false true 0false 2true 40false 05true");
+    }
+
+    #[test]
+    fn optimization_levels() {
+        let source = indoc! {"
+            fn square(x: i32) -> i32 {
+                x * x
+            }
+
+            fn main() -> i32 {
+                let unused: i32 = 1 + 2;
+                let a: i32 = square(3) + 0;
+                if 1 < 2 {
+                    print_int(a * 1);
+                } else {
+                    print_int(0);
+                }
+                print_char(32); // ' '
+                print_int(square(a) + square(2));
+                0
+            }
+        "};
+        let levels = [
+            OptimizationLevel::O0,
+            OptimizationLevel::O1,
+            OptimizationLevel::O2,
+        ];
+        for level in levels {
+            let stdout = run_optimized(source, level).unwrap().stdout;
+            assert_eq!(stdout, "9 85", "at -O{}", level);
+        }
+        let counts = levels.map(|level| instruction_count(source, level));
+        assert!(
+            counts[0] > counts[1] && counts[1] > counts[2],
+            "instruction counts: {:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn tail_calls() {
+        let source = indoc! {"
+            fn sum(n: i64, acc: i64) -> i64 {
+                if n == 0 {
+                    acc
+                } else {
+                    sum(n - 1, acc + n)
+                } as i64
+            }
+
+            fn is_even(n: i32) -> i32 {
+                if n == 0 { 1 } else { is_odd(n - 1) } as i32
+            }
+
+            fn is_odd(n: i32) -> i32 {
+                if n == 0 { 0 } else { is_even(n - 1) } as i32
+            }
+
+            fn main() -> i32 {
+                print_i64(sum(1000000 as i64, 0 as i64));
+                print_char(32); // ' '
+                print_int(is_even(1000001));
+                0
+            }
+        "};
+        let stdout = run(source).unwrap().stdout;
+        assert_eq!(stdout, "500000500000 0");
+    }
+
+    #[test]
+    fn main_module() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                print_int(42);
+                print_char(32); // ' '
+                print_i64(0 as i64 - 5 as i64);
+                7
+            }
+        "};
+        let ir = lower_optimized(source, OptimizationLevel::O0).unwrap();
+        let wasm = CodeGenerator::new(ir).generate_main_module().unwrap();
+
+        let engine = Engine::default();
+        let module = wasmtime::Module::new(&engine, &wasm).unwrap();
+        let imports: Vec<_> = module
+            .imports()
+            .map(|import| format!("{}.{}", import.module(), import.name()))
+            .collect();
+        let expected: Vec<_> = ["env.memory".to_string()]
+            .into_iter()
+            .chain(
+                [
+                    "print_int",
+                    "print_i64",
+                    "print_char",
+                    "print_bool",
+                    "print_f64",
+                    "read_int",
+                    "read_i64",
+                    "read_char",
+                    "read_line",
+                    "alloc",
+                    "free",
+                    "array_new_i32",
+                    "array_new_i64",
+                    "array_new_f32",
+                    "array_new_f64",
+                    "array_address_i32",
+                    "array_address_i64",
+                    "array_address_f32",
+                    "array_address_f64",
+                ]
+                .map(|name| format!("shuiro:std@0.0.0.{name}")),
+            )
+            .collect();
+        assert_eq!(imports, expected);
+
+        let mut store = Store::new(&engine, String::new());
+        let memory = wasmtime::Memory::new(&mut store, wasmtime::MemoryType::new(0, None)).unwrap();
+        let mut linker = wasmtime::Linker::new(&engine);
+        linker.define(&store, "env", "memory", memory).unwrap();
+        linker
+            .func_wrap(
+                "shuiro:std@0.0.0",
+                "print_int",
+                |mut caller: wasmtime::Caller<'_, String>, value: i32| {
+                    caller.data_mut().push_str(&value.to_string());
+                    0
+                },
+            )
+            .unwrap();
+        linker
+            .func_wrap(
+                "shuiro:std@0.0.0",
+                "print_i64",
+                |mut caller: wasmtime::Caller<'_, String>, value: i64| {
+                    caller.data_mut().push_str(&value.to_string());
+                    0
+                },
+            )
+            .unwrap();
+        linker
+            .func_wrap(
+                "shuiro:std@0.0.0",
+                "print_char",
+                |mut caller: wasmtime::Caller<'_, String>, value: i32| {
+                    caller.data_mut().extend(char::from_u32(value as u32));
+                    0
+                },
+            )
+            .unwrap();
+        linker.define_unknown_imports_as_traps(&module).unwrap();
+        let instance = linker.instantiate(&mut store, &module).unwrap();
+        let main = instance
+            .get_typed_func::<(), i32>(&mut store, "main")
+            .unwrap();
+
+        assert_eq!(main.call(&mut store, ()).unwrap(), 7);
+        assert_eq!(store.data(), "42 -5");
+    }
+
+    /// Instantiate the core module of the standard library, `$Std` in the template, on its own,
+    /// with the memory `$Env` defines and its WASI imports trapping.
+    fn instantiate_std() -> (Store<()>, wasmtime::Instance) {
+        let std_module = wasmparser::Parser::new(0)
+            .parse_all(RUNTIME_HEAD)
+            .find_map(|payload| match payload {
+                Ok(wasmparser::Payload::ModuleSection {
+                    unchecked_range, ..
+                }) => {
+                    let module = &RUNTIME_HEAD[unchecked_range];
+                    let exports_stack =
+                        wasmparser::Parser::new(0)
+                            .parse_all(module)
+                            .any(|payload| match payload {
+                                Ok(wasmparser::Payload::ExportSection(reader)) => {
+                                    reader.into_iter().any(|export| {
+                                        export.is_ok_and(|export| export.name == "stack_alloc")
+                                    })
+                                }
+                                _ => false,
+                            });
+                    exports_stack.then_some(module)
+                }
+                _ => None,
+            })
+            .unwrap();
+
+        let engine = Engine::default();
+        let module = wasmtime::Module::new(&engine, std_module).unwrap();
+        let mut store = Store::new(&engine, ());
+        let memory = wasmtime::Memory::new(&mut store, wasmtime::MemoryType::new(2, None)).unwrap();
+        let mut linker = wasmtime::Linker::new(&engine);
+        linker.define(&store, "env", "memory", memory).unwrap();
+        linker.define_unknown_imports_as_traps(&module).unwrap();
+        let instance = linker.instantiate(&mut store, &module).unwrap();
+        (store, instance)
+    }
+
+    #[test]
+    fn shadow_stack_grows_into_the_heap() {
+        let (mut store, instance) = instantiate_std();
+        let stack_alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "stack_alloc")
+            .unwrap();
+        let stack_restore = instance
+            .get_typed_func::<i32, ()>(&mut store, "stack_restore")
+            .unwrap();
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .unwrap();
+
+        // the first segment is 65536..131072, followed by the heap, where the second segment
+        // takes 65536 bytes
+        let first = stack_alloc.call(&mut store, 60000).unwrap();
+        assert_eq!(first, 65536);
+        let large = stack_alloc.call(&mut store, 8000).unwrap();
+        assert!(large > 131072, "{}", large);
+        let next = stack_alloc.call(&mut store, 8).unwrap();
+        assert_eq!(next, large + 8000);
+
+        // restoring into the new segment keeps it, restoring before it frees it
+        stack_restore.call(&mut store, next).unwrap();
+        assert_eq!(stack_alloc.call(&mut store, 8).unwrap(), next);
+        stack_restore.call(&mut store, first).unwrap();
+        assert_eq!(stack_alloc.call(&mut store, 16).unwrap(), first);
+        assert_eq!(alloc.call(&mut store, 65536).unwrap(), large - 8);
+    }
+
+    #[test]
+    fn source_map_and_names() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: i32 = 10;
+                print_int(a / 0);
+                0
+            }
+        "};
+        let ir = lower_optimized(source, OptimizationLevel::O0).unwrap();
+        let wasm = CodeGenerator::new(ir).generate_main_module().unwrap();
+
+        let mut source_map = None;
+        let mut function_names = Vec::new();
+        let mut local_names = Vec::new();
+        for payload in wasmparser::Parser::new(0).parse_all(&wasm) {
+            let wasmparser::Payload::CustomSection(reader) = payload.unwrap() else {
+                continue;
+            };
+            if reader.name() == source_map::SECTION_NAME {
+                source_map = Some(SourceMap::decode(reader.data()).unwrap());
+            }
+            if let wasmparser::KnownCustom::Name(names) = reader.as_known() {
+                for name in names {
+                    match name.unwrap() {
+                        wasmparser::Name::Function(map) => {
+                            for naming in map {
+                                function_names.push(naming.unwrap().name.to_string());
+                            }
+                        }
+                        wasmparser::Name::Local(map) => {
+                            for function in map {
+                                for naming in function.unwrap().names {
+                                    local_names.push(naming.unwrap().name.to_string());
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        assert!(function_names.contains(&"main".to_string()));
+        assert_eq!(local_names, vec!["a"]);
+
+        // `main` comes after the functions imported from the standard library
+        let source_map = source_map.unwrap();
+        assert_eq!(source_map.functions.len(), 1);
+        assert_eq!(
+            source_map.functions[0].function_index as usize,
+            std_functions().len() + RUNTIME_FUNCTIONS.len()
+        );
+        let positions: Vec<_> = source_map.functions[0]
+            .entries
+            .iter()
+            .map(|entry| (entry.line, entry.column))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![(1, 4), (2, 18), (3, 15), (3, 19), (3, 15), (3, 5), (4, 5)]
+        );
+    }
 }
@@ -0,0 +1,3269 @@
+//! Lowering from the AST to the IR.
+//!
+//! This stage resolves identifiers to locals, checks and decides the type of every expression and
+//! makes implicit control flow, such as short-circuit evaluation, explicit.
+//!
+//! Arrays are allocated on the heap by the standard library, which holds their length at offset
+//! 0 and their elements from offset 8. Every access goes through `array_address_*`, which traps
+//! when the index is out of bounds.
+//!
+//! Structs are allocated on the heap with `alloc`, with their fields in order of declaration,
+//! each aligned to its size.
+//!
+//! Enums whose variants hold no values are represented by the discriminant of their variant.
+//! Otherwise every value is allocated on the heap, with the discriminant at offset 0 followed by
+//! the values of the variant laid out like the fields of a struct. A `match` becomes a `switch`
+//! over the discriminant, or over the integer itself when its patterns are close enough to each
+//! other, and falls back to a chain of comparisons computing the index of the arm otherwise.
+//!
+//! Tuples are never stored as a whole: a function returning one returns each of its values, and
+//! destructuring a tuple stores each value into its own local.
+//!
+//! Constants are evaluated at compile time and replaced by their value wherever they are used,
+//! while statics become globals of the module, initialized with the value of their definition.
+//!
+//! The types of variables defined without one are inferred by the [`infer`] pass before each
+//! function is lowered.
+
+mod infer;
+
+use std::collections::HashMap;
+
+use crate::{std_functions, CodegenError, Type, RUNTIME_FUNCTIONS};
+
+type Result<T> = std::result::Result<T, CodegenError>;
+
+/// Builtin function evaluating to the length of an array, unless the program defines a function
+/// with the same name.
+const LEN: &str = "len";
+
+/// Offset of the first element of an array from its address.
+const ARRAY_ELEMENTS_OFFSET: u32 = 8;
+
+/// Largest number of values between the smallest and the largest integer patterns of a match for
+/// which it dispatches on the value directly.
+const MAX_TABLE_LENGTH: u128 = 256;
+
+/// Parameter and return types of a callable function.
+struct Signature {
+    parameters: Vec<Type>,
+    return_type: Type,
+}
+
+/// Types defined by the program, by name.
+struct Types<'a> {
+    structs: HashMap<&'a str, StructLayout>,
+    enums: HashMap<&'a str, EnumLayout>,
+}
+
+/// Fields of a struct and their offsets from the address of the struct.
+struct StructLayout {
+    fields: Vec<FieldLayout>,
+    size: u32,
+}
+
+struct FieldLayout {
+    name: String,
+    ty: Type,
+    offset: u32,
+}
+
+impl StructLayout {
+    fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Add a field after the others, aligned to its size.
+    fn push(&mut self, name: String, ty: Type) {
+        let size = value_size(ty.ir_type());
+        let offset = self.size.next_multiple_of(size);
+        self.size = offset + size;
+        self.fields.push(FieldLayout { name, ty, offset });
+    }
+}
+
+/// Variants of an enum, in order of declaration.
+///
+/// The discriminant of a variant is its index. Enums whose variants hold no values are
+/// represented by their discriminant, while the others are allocated on the heap like structs,
+/// with the discriminant at offset 0 followed by the values of the variant.
+struct EnumLayout {
+    variants: Vec<VariantLayout>,
+}
+
+struct VariantLayout {
+    name: String,
+
+    /// Values of the variant, named by their position.
+    fields: StructLayout,
+}
+
+impl EnumLayout {
+    /// Whether values of the enum are pointers to the heap rather than discriminants.
+    fn is_boxed(&self) -> bool {
+        self.variants
+            .iter()
+            .any(|variant| !variant.fields.fields.is_empty())
+    }
+
+    /// Discriminant and layout of the variant with the name.
+    fn variant(&self, name: &str) -> Option<(usize, &VariantLayout)> {
+        self.variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| variant.name == name)
+    }
+}
+
+/// What a name refers to in an expression.
+#[derive(Clone, Copy, Debug)]
+enum Variable {
+    Local(ir::LocalId),
+
+    /// A constant, replaced by its value wherever it is used.
+    Constant(ir::Constant),
+    Static(ir::GlobalId),
+}
+
+/// A tuple lowered to the values it is made of.
+enum Tuple {
+    /// Elements of a tuple literal, in order.
+    Elements(Vec<ir::Expression>),
+
+    /// A call to a function returning several values, which a call statement stores into locals.
+    Call {
+        function: String,
+        arguments: Vec<ir::Expression>,
+        location: ast::Location,
+    },
+}
+
+/// A value to store into memory, lowered from an assignment to an element or a field.
+struct Store {
+    address: ir::Expression,
+    value: ir::Expression,
+    offset: u32,
+    ty: Type,
+}
+
+impl Store {
+    fn into_statement(self) -> ir::Statement {
+        ir::Statement::Store {
+            address: self.address,
+            value: self.value,
+            offset: self.offset,
+        }
+    }
+}
+
+/// Type inferred for a variable defined without one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InferredType {
+    pub name: ast::Identifier,
+    pub ty: Type,
+}
+
+/// Lower a program to the IR.
+pub fn lower(program: &ast::Program) -> Result<ir::Program> {
+    lower_with_types(program).map(|(program, _)| program)
+}
+
+/// Check the types of a program without generating its code.
+///
+/// Returns the types inferred for the variables defined without one, in the order of the
+/// functions and of the definitions.
+pub fn check(program: &ast::Program) -> Result<Vec<InferredType>> {
+    lower_with_types(program).map(|(_, inferred_types)| inferred_types)
+}
+
+/// Lower a program to the IR, also returning the types inferred for the variables defined without
+/// one, as [`check`] does.
+pub fn lower_with_types(program: &ast::Program) -> Result<(ir::Program, Vec<InferredType>)> {
+    let mut names: HashMap<&str, Type> = HashMap::new();
+    let definitions = program
+        .structs
+        .iter()
+        .map(|definition| (&definition.name, Type::Struct(definition.name.name.clone())))
+        .chain(
+            program
+                .enums
+                .iter()
+                .map(|definition| (&definition.name, Type::Enum(definition.name.name.clone()))),
+        );
+    for (name, ty) in definitions {
+        if names.insert(name.name.as_str(), ty).is_some() {
+            return Err(CodegenError::DuplicateDefinition {
+                name: name.name.clone(),
+                location: name.location.clone(),
+            });
+        }
+    }
+    let types = Types {
+        structs: program
+            .structs
+            .iter()
+            .map(|definition| {
+                Ok((
+                    definition.name.name.as_str(),
+                    lower_struct(definition, &names)?,
+                ))
+            })
+            .collect::<Result<_>>()?,
+        enums: program
+            .enums
+            .iter()
+            .map(|definition| {
+                Ok((
+                    definition.name.name.as_str(),
+                    lower_enum(definition, &names)?,
+                ))
+            })
+            .collect::<Result<_>>()?,
+    };
+
+    let mut functions: HashMap<&str, Signature> = std_functions()
+        .into_iter()
+        .map(|(name, parameters, return_type)| {
+            (
+                name,
+                Signature {
+                    parameters,
+                    return_type,
+                },
+            )
+        })
+        .collect();
+    for function in &program.functions {
+        let signature = Signature {
+            parameters: function
+                .parameters
+                .parameters
+                .iter()
+                .map(|parameter| lower_type(&parameter.parameter_type, &names))
+                .collect::<Result<_>>()?,
+            return_type: lower_return_type(&function.return_type, &names)?,
+        };
+        // the functions of the standard library and of the runtime share the namespace of the
+        // functions of the program
+        let name = function.name.name.as_str();
+        if functions.insert(name, signature).is_some()
+            || RUNTIME_FUNCTIONS
+                .iter()
+                .any(|(runtime, _, _)| *runtime == name)
+        {
+            return Err(CodegenError::DuplicateDefinition {
+                name: function.name.name.clone(),
+                location: function.name.location.clone(),
+            });
+        }
+    }
+    if !program
+        .functions
+        .iter()
+        .any(|function| function.name.name == "main")
+    {
+        return Err(CodegenError::MissingMain);
+    }
+
+    // constants may refer to the constants defined before them, and statics to any constant
+    let mut variables: HashMap<&str, (Variable, Type)> = HashMap::new();
+    let mut globals = Vec::new();
+    let definitions = program
+        .constants
+        .iter()
+        .map(|constant| {
+            (
+                &constant.name,
+                &constant.constant_type,
+                &constant.value,
+                false,
+            )
+        })
+        .chain(program.statics.iter().map(|definition| {
+            (
+                &definition.name,
+                &definition.static_type,
+                &definition.value,
+                true,
+            )
+        }));
+    for (name, ast_type, value, is_static) in definitions {
+        let ty = lower_type(ast_type, &names)?;
+        let value = FunctionLowerer::new(&functions, &names, &types, &variables)
+            .lower_expression_as(value, &ty)?;
+        let value = value.evaluate().ok_or_else(|| CodegenError::NotConstant {
+            name: name.name.clone(),
+            location: value.location.clone(),
+        })?;
+        let variable = if is_static {
+            globals.push(ir::Global {
+                name: name.name.clone(),
+                value,
+            });
+            Variable::Static(ir::GlobalId(globals.len() - 1))
+        } else {
+            Variable::Constant(value)
+        };
+        if variables
+            .insert(name.name.as_str(), (variable, ty))
+            .is_some()
+        {
+            return Err(CodegenError::DuplicateDefinition {
+                name: name.name.clone(),
+                location: name.location.clone(),
+            });
+        }
+    }
+
+    let mut inferred_types = Vec::new();
+    let functions = program
+        .functions
+        .iter()
+        .map(|function| {
+            let mut lowerer = FunctionLowerer::new(&functions, &names, &types, &variables);
+            let function = lowerer.lower(function)?;
+            inferred_types.append(&mut lowerer.inferred_types);
+            Ok(function)
+        })
+        .collect::<Result<_>>()?;
+    Ok((ir::Program { globals, functions }, inferred_types))
+}
+
+/// Lay out the fields of a struct.
+///
+/// `names` are the types defined by the program, which the types of the fields may refer to.
+fn lower_struct(
+    definition: &ast::StructDefinition,
+    names: &HashMap<&str, Type>,
+) -> Result<StructLayout> {
+    let mut layout = StructLayout {
+        fields: Vec::new(),
+        size: 0,
+    };
+    for field in &definition.fields {
+        if layout.field(&field.name.name).is_some() {
+            return Err(CodegenError::DuplicateField {
+                field: field.name.name.clone(),
+                location: field.name.location.clone(),
+            });
+        }
+        layout.push(
+            field.name.name.clone(),
+            lower_type(&field.field_type, names)?,
+        );
+    }
+    Ok(layout)
+}
+
+/// Lay out the values of the variants of an enum, after their `i32` discriminant.
+fn lower_enum(definition: &ast::EnumDefinition, names: &HashMap<&str, Type>) -> Result<EnumLayout> {
+    let mut layout = EnumLayout {
+        variants: Vec::new(),
+    };
+    for variant in &definition.variants {
+        if layout.variant(&variant.name.name).is_some() {
+            return Err(CodegenError::DuplicateDefinition {
+                name: variant.name.name.clone(),
+                location: variant.name.location.clone(),
+            });
+        }
+        let mut fields = StructLayout {
+            fields: Vec::new(),
+            size: value_size(ir::Type::I32),
+        };
+        for (index, field) in variant.fields.iter().enumerate() {
+            fields.push(index.to_string(), lower_type(field, names)?);
+        }
+        layout.variants.push(VariantLayout {
+            name: variant.name.name.clone(),
+            fields,
+        });
+    }
+    Ok(layout)
+}
+
+/// Lower a type, whose names refer to the types defined by the program.
+fn lower_type(ast_type: &ast::Type, names: &HashMap<&str, Type>) -> Result<Type> {
+    Ok(match &ast_type.name {
+        ast::TypeKind::I32 => Type::I32,
+        ast::TypeKind::I64 => Type::I64,
+        ast::TypeKind::U32 => Type::U32,
+        ast::TypeKind::U64 => Type::U64,
+        ast::TypeKind::F32 => Type::F32,
+        ast::TypeKind::F64 => Type::F64,
+        ast::TypeKind::Bool => Type::Bool,
+        ast::TypeKind::Unit => {
+            return Err(CodegenError::MisplacedUnitType {
+                location: ast_type.location.clone(),
+            })
+        }
+        ast::TypeKind::Tuple(_) => {
+            return Err(CodegenError::MisplacedTupleType {
+                location: ast_type.location.clone(),
+            })
+        }
+        ast::TypeKind::Named(name) => {
+            names
+                .get(name.as_str())
+                .cloned()
+                .ok_or_else(|| CodegenError::UnknownType {
+                    name: name.clone(),
+                    location: ast_type.location.clone(),
+                })?
+        }
+        ast::TypeKind::Array { element, length } => {
+            let element = lower_type(element, names)?;
+            check_element_type(&element, &ast_type.location)?;
+            let length = length
+                .as_ref()
+                .map(|length| {
+                    array_length(length).ok_or_else(|| CodegenError::LiteralOutOfRange {
+                        literal: length.value.clone(),
+                        ty: Type::I32,
+                        location: length.location.clone(),
+                    })
+                })
+                .transpose()?;
+            Type::Array {
+                element: Box::new(element),
+                length,
+            }
+        }
+    })
+}
+
+/// Lower the return type of a function, which unlike other types may be `()` or a tuple.
+fn lower_return_type(ast_type: &ast::Type, names: &HashMap<&str, Type>) -> Result<Type> {
+    match &ast_type.name {
+        ast::TypeKind::Unit => Ok(Type::Unit),
+        ast::TypeKind::Tuple(elements) => elements
+            .iter()
+            .map(|element| lower_type(element, names))
+            .collect::<Result<_>>()
+            .map(Type::Tuple),
+        _ => lower_type(ast_type, names),
+    }
+}
+
+/// Size in bytes of a value of the type in memory.
+fn value_size(ty: ir::Type) -> u32 {
+    match ty {
+        ir::Type::I32 | ir::Type::F32 => 4,
+        ir::Type::I64 | ir::Type::F64 => 8,
+    }
+}
+
+/// Length of an array given by an integer literal, which must fit in an `i32`.
+fn array_length(literal: &ast::IntegerLiteral) -> Option<u32> {
+    literal
+        .value
+        .parse::<i32>()
+        .ok()
+        .map(|length| length as u32)
+}
+
+/// Arrays can only hold numbers, whose IR type decides the runtime functions handling them.
+fn check_element_type(element: &Type, location: &ast::Location) -> Result<()> {
+    if element.is_numeric() {
+        Ok(())
+    } else {
+        Err(CodegenError::UnsupportedElementType {
+            ty: element.clone(),
+            location: location.clone(),
+        })
+    }
+}
+
+/// Lower the index and the table of a switch on an integer value.
+///
+/// `value` is of the integer type `ty`, `ranges` are the inclusive ranges of values matched by the
+/// patterns of each arm, in order, `catch_all` is the first arm matching any value and `arms` is
+/// the number of arms.
+fn lower_integer_dispatch(
+    value: ir::Expression,
+    ty: &Type,
+    ranges: &[(i128, i128, usize)],
+    catch_all: Option<usize>,
+    arms: usize,
+) -> (ir::Expression, Vec<usize>, usize) {
+    let location = value.location.clone();
+    let (Some(min), Some(max)) = (
+        ranges.iter().map(|(start, _, _)| *start).min(),
+        ranges.iter().map(|(_, end, _)| *end).max(),
+    ) else {
+        return (
+            constant(0, ir::Type::I32, &location),
+            Vec::new(),
+            catch_all.unwrap_or(0),
+        );
+    };
+
+    // dispatch on the offset from the smallest value, which is out of the bounds of the table
+    // for values outside of the patterns
+    if let Some(catch_all) = catch_all.filter(|_| max.abs_diff(min) < MAX_TABLE_LENGTH) {
+        let table: Vec<usize> = (min..=max)
+            .map(|value| {
+                ranges
+                    .iter()
+                    .find(|(start, end, _)| (*start..=*end).contains(&value))
+                    .map_or(catch_all, |(_, _, arm)| (*arm).min(catch_all))
+            })
+            .collect();
+        let offset = match min {
+            0 => value.clone(),
+            _ => binary(
+                ir::BinaryOperator::Subtract,
+                value.clone(),
+                constant(min as i64, value.ty, &location),
+            ),
+        };
+        let index = match value.ty {
+            // values below the table wrap around to large unsigned indices
+            ir::Type::I32 => offset,
+            _ => if_expression(
+                range_condition(&value, ty, min, max),
+                ir::Expression {
+                    kind: ir::ExpressionKind::Convert {
+                        operand: Box::new(offset),
+                        signed: true,
+                    },
+                    ty: ir::Type::I32,
+                    location: location.clone(),
+                },
+                constant(table.len() as i64, ir::Type::I32, &location),
+            ),
+        };
+        return (index, table, catch_all);
+    }
+
+    // otherwise find the index of the arm by comparing the value with the ranges in order:
+    //   if value in range_0 { 0 } else if value in range_1 { 1 } else { catch_all }
+    let mut ranges = ranges
+        .iter()
+        .filter(|(_, _, arm)| catch_all.is_none_or(|catch_all| *arm < catch_all))
+        .rev();
+    let mut index = match catch_all {
+        Some(catch_all) => constant(catch_all as i64, ir::Type::I32, &location),
+        // the ranges cover every value, so the last one needs no comparison
+        None => {
+            let (_, _, arm) = ranges.next().expect("expected exhaustive ranges");
+            constant(*arm as i64, ir::Type::I32, &location)
+        }
+    };
+    for (start, end, arm) in ranges {
+        index = if_expression(
+            range_condition(&value, ty, *start, *end),
+            constant(*arm as i64, ir::Type::I32, &location),
+            index,
+        );
+    }
+    (index, (0..arms).collect(), catch_all.unwrap_or(0))
+}
+
+/// Type of an expression whose type is not decided by its context: float literals and arithmetic
+/// on them are `f64` and everything else is `i32`.
+fn default_type(expression: &ast::Expression) -> Type {
+    match expression {
+        ast::Expression::FloatLiteral(_) => Type::F64,
+        ast::Expression::BinaryExpression(expr)
+            if matches!(
+                expr.operator.operator,
+                ast::OperatorKind::Add
+                    | ast::OperatorKind::Subtract
+                    | ast::OperatorKind::Multiply
+                    | ast::OperatorKind::Divide
+            ) =>
+        {
+            if default_type(&expr.left).is_float() {
+                Type::F64
+            } else {
+                default_type(&expr.right)
+            }
+        }
+        _ => Type::I32,
+    }
+}
+
+/// Whether the constant is a float too large for its type.
+fn is_infinite(constant: &ir::Constant) -> bool {
+    match constant {
+        ir::Constant::F32(value) => value.is_infinite(),
+        ir::Constant::F64(value) => value.is_infinite(),
+        _ => false,
+    }
+}
+
+/// Smallest and largest values of an integer type, which an `i128` holds for every type.
+fn integer_bounds(ty: &Type) -> (i128, i128) {
+    match ty {
+        Type::I32 => (i32::MIN.into(), i32::MAX.into()),
+        Type::I64 => (i64::MIN.into(), i64::MAX.into()),
+        Type::U32 => (0, u32::MAX.into()),
+        Type::U64 => (0, u64::MAX.into()),
+        _ => unreachable!("expected an integer type, found `{}`", ty),
+    }
+}
+
+/// Value of an integer pattern matching a value of the type.
+fn pattern_value(literal: &ast::IntegerLiteral, ty: &Type) -> Result<i128> {
+    let value = match ty {
+        Type::I32 => literal.value.parse::<i32>().map(i128::from),
+        Type::I64 => literal.value.parse::<i64>().map(i128::from),
+        Type::U32 => literal.value.parse::<u32>().map(i128::from),
+        Type::U64 => literal.value.parse::<u64>().map(i128::from),
+        _ => {
+            return Err(CodegenError::MismatchedTypes {
+                expected: ty.clone(),
+                found: Type::I32,
+                location: literal.location.clone(),
+            })
+        }
+    };
+    value.map_err(|_| CodegenError::LiteralOutOfRange {
+        literal: literal.value.clone(),
+        ty: ty.clone(),
+        location: literal.location.clone(),
+    })
+}
+
+/// Patterns of the ranges of values of an integer type that none of the `ranges` cover.
+fn missing_ranges(ranges: &[(i128, i128, usize)], ty: &Type) -> Vec<String> {
+    let (min, max) = integer_bounds(ty);
+    let bound = |value: i128| match value {
+        value if value == min => format!("{}::MIN", ty),
+        value if value == max => format!("{}::MAX", ty),
+        value => value.to_string(),
+    };
+    let pattern = |first: i128, last: i128| {
+        if first == last {
+            format!("`{}`", bound(first))
+        } else {
+            format!("`{}..={}`", bound(first), bound(last))
+        }
+    };
+    let mut ranges: Vec<(i128, i128)> = ranges
+        .iter()
+        .map(|(start, end, _)| (*start, *end))
+        .collect();
+    ranges.sort();
+    // the first value not covered by the ranges seen so far
+    let mut next = min;
+    let mut missing = Vec::new();
+    for (start, end) in ranges {
+        if start > next {
+            missing.push(pattern(next, start - 1));
+        }
+        next = next.max(end + 1);
+    }
+    if next <= max {
+        missing.push(pattern(next, max));
+    }
+    missing
+}
+
+/// Pattern matching any value of the variant.
+fn variant_pattern(ty: &Type, variant: &VariantLayout) -> String {
+    if variant.fields.fields.is_empty() {
+        format!("`{}::{}`", ty, variant.name)
+    } else {
+        let fields = vec!["_"; variant.fields.fields.len()];
+        format!("`{}::{}({})`", ty, variant.name, fields.join(", "))
+    }
+}
+
+/// Whether the pattern binds any name.
+fn has_bindings(pattern: &ast::Pattern) -> bool {
+    match pattern {
+        ast::Pattern::Binding(_) => true,
+        ast::Pattern::Variant(pattern) => pattern.fields.iter().any(has_bindings),
+        _ => false,
+    }
+}
+
+/// Check the number of values given to a variant of an enum, in an expression or a pattern.
+fn check_field_count(
+    enum_name: &ast::Identifier,
+    variant: &VariantLayout,
+    found: usize,
+    location: &ast::Location,
+) -> Result<()> {
+    let expected = variant.fields.fields.len();
+    if found == expected {
+        return Ok(());
+    }
+    Err(CodegenError::FieldCount {
+        variant: format!("{}::{}", enum_name.name, variant.name),
+        expected,
+        found,
+        location: location.clone(),
+    })
+}
+
+/// A constant of the type with the value, whose bits are truncated to the size of integer types.
+fn constant(value: i64, ty: ir::Type, location: &ast::Location) -> ir::Expression {
+    ir::Expression {
+        kind: ir::ExpressionKind::Constant(match ty {
+            ir::Type::I32 => ir::Constant::I32(value as i32),
+            ir::Type::I64 => ir::Constant::I64(value),
+            ir::Type::F32 => ir::Constant::F32(value as f32),
+            ir::Type::F64 => ir::Constant::F64(value as f64),
+        }),
+        ty,
+        location: location.clone(),
+    }
+}
+
+fn binary(
+    operator: ir::BinaryOperator,
+    left: ir::Expression,
+    right: ir::Expression,
+) -> ir::Expression {
+    ir::Expression {
+        ty: if operator.is_comparison() {
+            ir::Type::I32
+        } else {
+            left.ty
+        },
+        location: left.location.clone(),
+        kind: ir::ExpressionKind::Binary {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        },
+    }
+}
+
+fn if_expression(
+    condition: ir::Expression,
+    then_result: ir::Expression,
+    else_result: ir::Expression,
+) -> ir::Expression {
+    ir::Expression {
+        ty: then_result.ty,
+        location: condition.location.clone(),
+        kind: ir::ExpressionKind::If {
+            condition: Box::new(condition),
+            then_block: ir::Block {
+                statements: Vec::new(),
+                result: Some(Box::new(then_result)),
+            },
+            else_block: ir::Block {
+                statements: Vec::new(),
+                result: Some(Box::new(else_result)),
+            },
+        },
+    }
+}
+
+/// Condition that the value of the integer type `ty` is between `start` and `end`, inclusive.
+fn range_condition(value: &ir::Expression, ty: &Type, start: i128, end: i128) -> ir::Expression {
+    let (min, max) = integer_bounds(ty);
+    let compare = |operator: ir::BinaryOperator, bound: i128| {
+        binary(
+            if ty.is_unsigned() {
+                operator.unsigned()
+            } else {
+                operator
+            },
+            value.clone(),
+            constant(bound as i64, value.ty, &value.location),
+        )
+    };
+    match (start, end) {
+        _ if start == end => compare(ir::BinaryOperator::Equal, start),
+        _ if start == min => compare(ir::BinaryOperator::LessThanOrEqual, end),
+        _ if end == max => compare(ir::BinaryOperator::GreaterThanOrEqual, start),
+        _ => if_expression(
+            compare(ir::BinaryOperator::GreaterThanOrEqual, start),
+            compare(ir::BinaryOperator::LessThanOrEqual, end),
+            constant(0, ir::Type::I32, &value.location),
+        ),
+    }
+}
+
+/// Value assigned by a compound assignment, which applies the operator to the current value like
+/// a binary expression:
+///   x += y => x = x + y
+fn compound_assignment_value(expr: &ast::CompoundAssignmentExpression) -> ast::Expression {
+    ast::Expression::BinaryExpression(ast::BinaryExpression {
+        left: Box::new(ast::Expression::Identifier(expr.name.clone())),
+        operator: expr.operator.clone(),
+        right: expr.value.clone(),
+        location: expr.location.clone(),
+    })
+}
+
+/// Store a value into a local or a static, evaluating to the value. Statics are read back after
+/// the store:
+///   g = value => { @g = value; @g }
+fn assignment_expression(variable: Variable, value: ir::Expression) -> ir::ExpressionKind {
+    match variable {
+        Variable::Local(local) => ir::ExpressionKind::LocalTee {
+            local,
+            value: Box::new(value),
+        },
+        Variable::Static(global) => ir::ExpressionKind::Block(ir::Block {
+            result: Some(Box::new(ir::Expression {
+                kind: ir::ExpressionKind::GlobalGet(global),
+                ty: value.ty,
+                location: value.location.clone(),
+            })),
+            statements: vec![ir::Statement::GlobalSet { global, value }],
+        }),
+        Variable::Constant(_) => unreachable!("constants cannot be assigned to"),
+    }
+}
+
+/// Store a value into a local or a static, discarding the value.
+fn assignment_statement(
+    variable: Variable,
+    value: ir::Expression,
+    location: &ast::Location,
+) -> ir::Statement {
+    match variable {
+        Variable::Static(global) => ir::Statement::GlobalSet { global, value },
+        _ => ir::Statement::Expression(ir::Expression {
+            ty: value.ty,
+            location: location.clone(),
+            kind: assignment_expression(variable, value),
+        }),
+    }
+}
+
+/// Type of the elements of an array type.
+fn element_type(ty: Type) -> Option<Type> {
+    match ty {
+        Type::Array { element, .. } => Some(*element),
+        _ => None,
+    }
+}
+
+/// Map an operator to the IR, unless it is lowered to control flow or is not a binary operator.
+fn lower_binary_operator(operator: &ast::OperatorKind) -> Option<ir::BinaryOperator> {
+    match operator {
+        ast::OperatorKind::Add => Some(ir::BinaryOperator::Add),
+        ast::OperatorKind::Subtract => Some(ir::BinaryOperator::Subtract),
+        ast::OperatorKind::Multiply => Some(ir::BinaryOperator::Multiply),
+        ast::OperatorKind::Divide => Some(ir::BinaryOperator::Divide),
+        ast::OperatorKind::Remainder => Some(ir::BinaryOperator::Remainder),
+        ast::OperatorKind::BitAnd => Some(ir::BinaryOperator::And),
+        ast::OperatorKind::BitOr => Some(ir::BinaryOperator::Or),
+        ast::OperatorKind::BitXor => Some(ir::BinaryOperator::Xor),
+        ast::OperatorKind::ShiftLeft => Some(ir::BinaryOperator::ShiftLeft),
+        ast::OperatorKind::ShiftRight => Some(ir::BinaryOperator::ShiftRight),
+        ast::OperatorKind::Equal => Some(ir::BinaryOperator::Equal),
+        ast::OperatorKind::NotEqual => Some(ir::BinaryOperator::NotEqual),
+        ast::OperatorKind::LessThan => Some(ir::BinaryOperator::LessThan),
+        ast::OperatorKind::LessThanOrEqual => Some(ir::BinaryOperator::LessThanOrEqual),
+        ast::OperatorKind::GreaterThan => Some(ir::BinaryOperator::GreaterThan),
+        ast::OperatorKind::GreaterThanOrEqual => Some(ir::BinaryOperator::GreaterThanOrEqual),
+        ast::OperatorKind::LogicalAnd
+        | ast::OperatorKind::LogicalOr
+        | ast::OperatorKind::LogicalNot => None,
+    }
+}
+
+struct FunctionLowerer<'a> {
+    functions: &'a HashMap<&'a str, Signature>,
+    names: &'a HashMap<&'a str, Type>,
+    types: &'a Types<'a>,
+
+    /// Constants and statics defined by the program, by name, which locals shadow.
+    globals: &'a HashMap<&'a str, (Variable, Type)>,
+    locals: Vec<ir::Local>,
+
+    /// Types of the locals in the source, indexed like `locals`.
+    local_types: Vec<Type>,
+
+    /// Whether the locals can be assigned to, indexed like `locals`.
+    mutable_locals: Vec<bool>,
+
+    /// Locals visible by name, from the outermost to the innermost block.
+    scopes: Vec<HashMap<String, ir::LocalId>>,
+
+    /// Types inferred for the variables defined without one, by the index of their names.
+    inferred: HashMap<usize, Type>,
+
+    /// Types of the variables defined without one, once they are declared.
+    inferred_types: Vec<InferredType>,
+}
+
+impl<'a> FunctionLowerer<'a> {
+    fn new(
+        functions: &'a HashMap<&'a str, Signature>,
+        names: &'a HashMap<&'a str, Type>,
+        types: &'a Types<'a>,
+        globals: &'a HashMap<&'a str, (Variable, Type)>,
+    ) -> Self {
+        Self {
+            functions,
+            names,
+            types,
+            globals,
+            locals: Vec::new(),
+            local_types: Vec::new(),
+            mutable_locals: Vec::new(),
+            scopes: vec![HashMap::new()],
+            inferred: HashMap::new(),
+            inferred_types: Vec::new(),
+        }
+    }
+
+    fn lower(&mut self, function: &ast::FunctionDefinition) -> Result<ir::Function> {
+        for parameter in &function.parameters.parameters {
+            let ty = self.lower_type(&parameter.parameter_type)?;
+            self.declare(&parameter.name, ty, true);
+        }
+        self.inferred = infer::infer_function(self, function);
+        let return_type = lower_return_type(&function.return_type, self.names)?;
+        let mut body = self.lower_block(&function.body, Some(&return_type))?;
+        let results = match return_type {
+            // `main` returns the exit code of the program, which is 0 unless it returns one
+            Type::Unit if function.name.name == "main" => {
+                body.result = Some(Box::new(constant(0, ir::Type::I32, &function.location)));
+                vec![ir::Type::I32]
+            }
+            Type::Unit => Vec::new(),
+            Type::Tuple(types) => types.iter().map(Type::ir_type).collect(),
+            ty => vec![ty.ir_type()],
+        };
+        Ok(ir::Function {
+            name: function.name.name.clone(),
+            parameter_count: function.parameters.parameters.len(),
+            locals: std::mem::take(&mut self.locals),
+            results,
+            body,
+            location: function.location.clone(),
+        })
+    }
+
+    /// Declare a new local in the innermost scope, shadowing any local with the same name.
+    ///
+    /// Only `let` definitions declare locals that cannot be assigned to.
+    fn declare(&mut self, name: &ast::Identifier, ty: Type, mutable: bool) -> ir::LocalId {
+        let id = self.temporary(&name.name, ty);
+        self.mutable_locals[id.0] = mutable;
+        self.scopes
+            .last_mut()
+            .expect("expected at least one scope")
+            .insert(name.name.clone(), id);
+        id
+    }
+
+    /// Add a local that cannot be referred to by name.
+    fn temporary(&mut self, name: &str, ty: Type) -> ir::LocalId {
+        let id = ir::LocalId(self.locals.len());
+        self.locals.push(ir::Local {
+            name: name.to_string(),
+            ty: ty.ir_type(),
+        });
+        self.local_types.push(ty);
+        self.mutable_locals.push(true);
+        id
+    }
+
+    fn lower_type(&self, ast_type: &ast::Type) -> Result<Type> {
+        lower_type(ast_type, self.names)
+    }
+
+    /// Field with the name of the struct the type refers to.
+    fn field(&self, ty: &Type, name: &ast::Identifier) -> Result<&'a FieldLayout> {
+        let layout = match ty {
+            Type::Struct(struct_name) => self.types.structs.get(struct_name.as_str()),
+            _ => None,
+        };
+        layout
+            .and_then(|layout| layout.field(&name.name))
+            .ok_or_else(|| CodegenError::UnknownField {
+                ty: ty.clone(),
+                field: name.name.clone(),
+                location: name.location.clone(),
+            })
+    }
+
+    /// Layout of the enum, its discriminant and the layout of the variant with the names.
+    fn variant(
+        &self,
+        enum_name: &ast::Identifier,
+        variant_name: &ast::Identifier,
+    ) -> Result<(&'a EnumLayout, usize, &'a VariantLayout)> {
+        let layout = self
+            .types
+            .enums
+            .get(enum_name.name.as_str())
+            .ok_or_else(|| CodegenError::UnknownType {
+                name: enum_name.name.clone(),
+                location: enum_name.location.clone(),
+            })?;
+        let (discriminant, variant) =
+            layout
+                .variant(&variant_name.name)
+                .ok_or_else(|| CodegenError::UnknownVariant {
+                    ty: Type::Enum(enum_name.name.clone()),
+                    variant: variant_name.name.clone(),
+                    location: variant_name.location.clone(),
+                })?;
+        Ok((layout, discriminant, variant))
+    }
+
+    /// Whether values of the type are the discriminants of an enum.
+    fn is_discriminant(&self, ty: &Type) -> bool {
+        match ty {
+            Type::Enum(name) => self
+                .types
+                .enums
+                .get(name.as_str())
+                .is_some_and(|layout| !layout.is_boxed()),
+            _ => false,
+        }
+    }
+
+    fn resolve(&self, name: &ast::Identifier) -> Result<ir::LocalId> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name.name).copied())
+            .ok_or_else(|| CodegenError::UnknownVariable {
+                name: name.name.clone(),
+                location: name.location.clone(),
+            })
+    }
+
+    /// Variable with the name and its type: a local, or else a constant or a static.
+    fn variable(&self, name: &ast::Identifier) -> Result<(Variable, Type)> {
+        match self.resolve(name) {
+            Ok(local) => Ok((Variable::Local(local), self.local_types[local.0].clone())),
+            Err(error) => self.globals.get(name.name.as_str()).cloned().ok_or(error),
+        }
+    }
+
+    fn signature(&self, name: &ast::Identifier) -> Result<&'a Signature> {
+        self.functions
+            .get(name.name.as_str())
+            .ok_or_else(|| CodegenError::UnknownFunction {
+                name: name.name.clone(),
+                location: name.location.clone(),
+            })
+    }
+
+    /// Lower the arguments of a call to a function defined by the program or the standard
+    /// library, along with its return type.
+    fn lower_call(&mut self, call: &ast::FunctionCall) -> Result<(Vec<ir::Expression>, Type)> {
+        let signature = self.signature(&call.name)?;
+        if call.arguments.len() != signature.parameters.len() {
+            return Err(CodegenError::ArgumentCount {
+                function: call.name.name.clone(),
+                expected: signature.parameters.len(),
+                found: call.arguments.len(),
+                location: call.location.clone(),
+            });
+        }
+        let arguments = call
+            .arguments
+            .iter()
+            .zip(&signature.parameters)
+            .map(|(argument, ty)| self.lower_expression_as(argument, ty))
+            .collect::<Result<_>>()?;
+        Ok((arguments, signature.return_type.clone()))
+    }
+
+    /// Whether the call is to the builtin `len`.
+    fn is_len(&self, call: &ast::FunctionCall) -> bool {
+        call.name.name == LEN && !self.functions.contains_key(LEN)
+    }
+
+    /// Lower a block in a new scope.
+    ///
+    /// `expected_type` is the type of the value the block evaluates to, if it ends with an expression.
+    fn lower_block(
+        &mut self,
+        block: &ast::Block,
+        expected_type: Option<&Type>,
+    ) -> Result<ir::Block> {
+        self.scopes.push(HashMap::new());
+        let mut statements = Vec::new();
+        let mut result = None;
+        for statement in &block.statements.statements {
+            match statement {
+                // a block of a tuple type returns its values from the function
+                ast::Statement::Expression(expression)
+                    if matches!(expected_type, Some(Type::Tuple(_))) =>
+                {
+                    let expected_type = expected_type.expect("expected a tuple type");
+                    statements.append(&mut self.lower_tuple_return(expression, expected_type)?);
+                }
+                // a block of type `()` has no value, so its last expression is only evaluated
+                ast::Statement::Expression(expression)
+                    if expected_type == Some(&Type::Unit)
+                        || (expected_type.is_none() && self.is_valueless_call(expression)) =>
+                {
+                    statements.push(self.lower_expression_statement(expression)?);
+                }
+                ast::Statement::Expression(expression) => {
+                    result = Some(Box::new(match expected_type {
+                        Some(ty) => self.lower_expression_as(expression, ty)?,
+                        None => self.lower_expression(expression, None)?.0,
+                    }));
+                }
+                statement => self.lower_statement(statement, &mut statements)?,
+            }
+        }
+        self.scopes.pop();
+        Ok(ir::Block { statements, result })
+    }
+
+    /// Lower a block evaluating to its last expression in a new scope, along with its type.
+    ///
+    /// `expected_type` is the type the surrounding context expects, like for
+    /// [`Self::lower_expression`].
+    fn lower_block_expression(
+        &mut self,
+        block: &ast::Block,
+        expected_type: Option<&Type>,
+    ) -> Result<(ir::Block, Type)> {
+        self.scopes.push(HashMap::new());
+        let mut statements = Vec::new();
+        let mut result = None;
+        for statement in &block.statements.statements {
+            match statement {
+                ast::Statement::Expression(expression) => {
+                    result = Some(self.lower_expression(expression, expected_type)?);
+                }
+                statement => self.lower_statement(statement, &mut statements)?,
+            }
+        }
+        self.scopes.pop();
+        let (result, ty) = result.expect("expected a block ending with an expression");
+        let block = ir::Block {
+            statements,
+            result: Some(Box::new(result)),
+        };
+        Ok((block, ty))
+    }
+
+    /// Lower a statement other than the last expression of a block into `statements`.
+    fn lower_statement(
+        &mut self,
+        statement: &ast::Statement,
+        statements: &mut Vec<ir::Statement>,
+    ) -> Result<()> {
+        match statement {
+            ast::Statement::VariableDefinition(variable) => {
+                let ty = match &variable.variable_type {
+                    Some(ty) => Some(self.lower_type(ty)?),
+                    None => self
+                        .inferred
+                        .get(&variable.name.location.start.index)
+                        .cloned(),
+                };
+                // the initial value is lowered before the declaration so that it refers to
+                // any shadowed variable with the same name
+                let (value, ty) = match (&variable.value, ty) {
+                    (Some(value), Some(ty)) => (Some(self.lower_expression_as(value, &ty)?), ty),
+                    (None, Some(ty)) => (None, ty),
+                    // inference could not decide the type, so it is the type of the value
+                    (Some(value), None) => {
+                        let (value, ty) = self.lower_expression(value, None)?;
+                        (Some(value), ty)
+                    }
+                    (None, None) => {
+                        return Err(CodegenError::TypeAnnotationNeeded {
+                            name: variable.name.name.clone(),
+                            location: variable.name.location.clone(),
+                        })
+                    }
+                };
+                if variable.variable_type.is_none() {
+                    self.inferred_types.push(InferredType {
+                        name: variable.name.clone(),
+                        ty: ty.clone(),
+                    });
+                }
+                let local = self.declare(&variable.name, ty, variable.mutable);
+                if let Some(value) = value {
+                    statements.push(ir::Statement::LocalSet { local, value });
+                }
+            }
+            ast::Statement::DestructuringDefinition(definition) => {
+                statements.append(&mut self.lower_destructuring(definition)?);
+            }
+            ast::Statement::IfStatement(if_statement) => {
+                statements.push(ir::Statement::If {
+                    condition: self.lower_condition(&if_statement.condition)?,
+                    then_block: self.lower_block(&if_statement.then_block, None)?,
+                    else_block: if_statement
+                        .else_block
+                        .as_ref()
+                        .map(|else_block| self.lower_block(else_block, None))
+                        .transpose()?,
+                });
+            }
+            // the value of the assignment is discarded, so it is stored directly
+            ast::Statement::ExpressionStatement(ast::ExpressionStatement {
+                expression: ast::Expression::IndexAssignmentExpression(expr),
+                ..
+            }) => {
+                let store = self.lower_index_assignment(expr)?;
+                statements.push(store.into_statement());
+            }
+            ast::Statement::ExpressionStatement(ast::ExpressionStatement {
+                expression: ast::Expression::FieldAssignmentExpression(expr),
+                ..
+            }) => {
+                let store = self.lower_field_assignment(expr)?;
+                statements.push(store.into_statement());
+            }
+            ast::Statement::ExpressionStatement(statement) => {
+                statements.push(self.lower_expression_statement(&statement.expression)?);
+            }
+            ast::Statement::Expression(_) => {
+                unreachable!("the last expression of a block is lowered with the block")
+            }
+        }
+        Ok(())
+    }
+
+    /// Lower an expression whose value is discarded.
+    fn lower_expression_statement(
+        &mut self,
+        expression: &ast::Expression,
+    ) -> Result<ir::Statement> {
+        match expression {
+            ast::Expression::FunctionCall(call) if self.is_valueless_call(expression) => {
+                let (arguments, _) = self.lower_call(call)?;
+                Ok(ir::Statement::Call {
+                    function: call.name.name.clone(),
+                    arguments,
+                    results: Vec::new(),
+                    location: call.location.clone(),
+                })
+            }
+            // the value of the assignment is discarded, so a static is stored directly
+            ast::Expression::AssignmentExpression(expr) => {
+                let (variable, value, _) = self.lower_assignment(&expr.name, &expr.value)?;
+                Ok(assignment_statement(variable, value, &expr.location))
+            }
+            ast::Expression::CompoundAssignmentExpression(expr) => {
+                let value = compound_assignment_value(expr);
+                let (variable, value, _) = self.lower_assignment(&expr.name, &value)?;
+                Ok(assignment_statement(variable, value, &expr.location))
+            }
+            _ => Ok(ir::Statement::Expression(
+                self.lower_expression(expression, None)?.0,
+            )),
+        }
+    }
+
+    /// Lower the value assigned to a variable, along with the variable and its type.
+    fn lower_assignment(
+        &mut self,
+        name: &ast::Identifier,
+        value: &ast::Expression,
+    ) -> Result<(Variable, ir::Expression, Type)> {
+        let (variable, ty) = self.variable(name)?;
+        match variable {
+            Variable::Constant(_) => {
+                return Err(CodegenError::AssignmentToConstant {
+                    name: name.name.clone(),
+                    location: name.location.clone(),
+                })
+            }
+            Variable::Local(local) if !self.mutable_locals[local.0] => {
+                return Err(CodegenError::AssignmentToImmutable {
+                    name: name.name.clone(),
+                    location: name.location.clone(),
+                })
+            }
+            _ => {}
+        }
+        let value = self.lower_expression_as(value, &ty)?;
+        Ok((variable, value, ty))
+    }
+
+    /// Whether the expression is a call to a function without a single return value, which
+    /// returns no value or a tuple.
+    fn is_valueless_call(&self, expression: &ast::Expression) -> bool {
+        matches!(expression, ast::Expression::FunctionCall(call) if !self.is_len(call))
+            && matches!(
+                self.expression_type(expression),
+                Some(Type::Unit | Type::Tuple(_))
+            )
+    }
+
+    /// Lower an expression that must be a tuple, a tuple literal or a call to a function
+    /// returning one, along with the types of its values.
+    ///
+    /// `expected_types` are the types the surrounding context expects for the values, if known.
+    fn lower_tuple(
+        &mut self,
+        expression: &ast::Expression,
+        expected_types: &[Option<Type>],
+    ) -> Result<(Tuple, Vec<Type>)> {
+        match expression {
+            ast::Expression::TupleLiteral(literal) => {
+                let mut elements = Vec::new();
+                let mut types = Vec::new();
+                for (index, element) in literal.elements.iter().enumerate() {
+                    let (element, ty) = match expected_types.get(index).cloned().flatten() {
+                        Some(ty) => (self.lower_expression_as(element, &ty)?, ty),
+                        None => self.lower_expression(element, None)?,
+                    };
+                    elements.push(element);
+                    types.push(ty);
+                }
+                Ok((Tuple::Elements(elements), types))
+            }
+            ast::Expression::FunctionCall(call) if !self.is_len(call) => {
+                let (arguments, return_type) = self.lower_call(call)?;
+                let Type::Tuple(types) = return_type else {
+                    return Err(CodegenError::NotATuple {
+                        ty: return_type,
+                        location: call.location.clone(),
+                    });
+                };
+                let tuple = Tuple::Call {
+                    function: call.name.name.clone(),
+                    arguments,
+                    location: call.location.clone(),
+                };
+                Ok((tuple, types))
+            }
+            _ => {
+                let (expression, ty) = self.lower_expression(expression, None)?;
+                Err(CodegenError::NotATuple {
+                    ty,
+                    location: expression.location,
+                })
+            }
+        }
+    }
+
+    /// Lower the last expression of the body of a function returning a tuple to the statements
+    /// returning its values. A call returns its values directly, as a tail call:
+    ///   f(x) => return f(x);
+    fn lower_tuple_return(
+        &mut self,
+        expression: &ast::Expression,
+        expected_type: &Type,
+    ) -> Result<Vec<ir::Statement>> {
+        let Type::Tuple(expected_types) = expected_type else {
+            unreachable!("expected a tuple type, found `{}`", expected_type)
+        };
+        let expected: Vec<Option<Type>> = expected_types.iter().cloned().map(Some).collect();
+        let (tuple, types) = self.lower_tuple(expression, &expected)?;
+        if types != *expected_types {
+            return Err(CodegenError::MismatchedTypes {
+                expected: expected_type.clone(),
+                found: Type::Tuple(types),
+                location: expression.location().clone(),
+            });
+        }
+        Ok(vec![match tuple {
+            Tuple::Elements(values) => ir::Statement::Return {
+                values,
+                location: expression.location().clone(),
+            },
+            Tuple::Call {
+                function,
+                arguments,
+                location,
+            } => ir::Statement::ReturnCall {
+                function,
+                arguments,
+                location,
+            },
+        }])
+    }
+
+    /// Lower the definition of a variable for each value of a tuple. The values of a call are
+    /// stored into the variables directly:
+    ///   let (a, b) = f(x) => %a, %b = f(x);
+    ///
+    /// The elements of a literal are lowered before the variables are declared, so that they refer
+    /// to any shadowed variables with the same names.
+    fn lower_destructuring(
+        &mut self,
+        definition: &ast::DestructuringDefinition,
+    ) -> Result<Vec<ir::Statement>> {
+        let expected: Vec<Option<Type>> = definition
+            .names
+            .iter()
+            .map(|name| self.inferred.get(&name.location.start.index).cloned())
+            .collect();
+        let (tuple, types) = self.lower_tuple(&definition.value, &expected)?;
+        if types.len() != definition.names.len() {
+            return Err(CodegenError::DestructuringCount {
+                expected: definition.names.len(),
+                found: types.len(),
+                location: definition.value.location().clone(),
+            });
+        }
+        let mut locals = Vec::new();
+        for (name, ty) in definition.names.iter().zip(types) {
+            self.inferred_types.push(InferredType {
+                name: name.clone(),
+                ty: ty.clone(),
+            });
+            locals.push(self.declare(name, ty, definition.mutable));
+        }
+        Ok(match tuple {
+            Tuple::Elements(elements) => locals
+                .into_iter()
+                .zip(elements)
+                .map(|(local, value)| ir::Statement::LocalSet { local, value })
+                .collect(),
+            Tuple::Call {
+                function,
+                arguments,
+                location,
+            } => vec![ir::Statement::Call {
+                function,
+                arguments,
+                results: locals.into_iter().map(Some).collect(),
+                location,
+            }],
+        })
+    }
+
+    /// Infer the type of an expression without lowering it.
+    ///
+    /// Returns `None` for expressions whose type is decided by their context, such as integer
+    /// literals.
+    fn expression_type(&self, expression: &ast::Expression) -> Option<Type> {
+        match expression {
+            ast::Expression::BinaryExpression(expr) => match expr.operator.operator {
+                ast::OperatorKind::Add
+                | ast::OperatorKind::Subtract
+                | ast::OperatorKind::Multiply
+                | ast::OperatorKind::Divide
+                | ast::OperatorKind::Remainder
+                | ast::OperatorKind::BitAnd
+                | ast::OperatorKind::BitOr
+                | ast::OperatorKind::BitXor
+                | ast::OperatorKind::ShiftLeft
+                | ast::OperatorKind::ShiftRight => self
+                    .expression_type(&expr.left)
+                    .or_else(|| self.expression_type(&expr.right)),
+                _ => Some(Type::Bool),
+            },
+            ast::Expression::UnaryExpression(_) => Some(Type::Bool),
+            ast::Expression::AssignmentExpression(ast::AssignmentExpression { name, .. })
+            | ast::Expression::CompoundAssignmentExpression(ast::CompoundAssignmentExpression {
+                name,
+                ..
+            }) => self.variable(name).ok().map(|(_, ty)| ty),
+            ast::Expression::IndexAssignmentExpression(expr) => {
+                self.expression_type(&expr.array).and_then(element_type)
+            }
+            ast::Expression::FieldAssignmentExpression(expr) => self
+                .expression_type(&expr.object)
+                .and_then(|ty| self.field(&ty, &expr.field).ok())
+                .map(|field| field.ty.clone()),
+            ast::Expression::IfElseExpression(expr) => self.lower_type(&expr.return_type).ok(),
+            ast::Expression::CastExpression(expr) => self.lower_type(&expr.target_type).ok(),
+            ast::Expression::Identifier(identifier) => {
+                self.variable(identifier).ok().map(|(_, ty)| ty)
+            }
+            ast::Expression::IntegerLiteral(_) | ast::Expression::FloatLiteral(_) => None,
+            ast::Expression::BooleanLiteral(_) => Some(Type::Bool),
+            ast::Expression::FunctionCall(call) if self.is_len(call) => Some(Type::I32),
+            ast::Expression::FunctionCall(call) => self
+                .signature(&call.name)
+                .ok()
+                .map(|signature| signature.return_type.clone()),
+            ast::Expression::ArrayLiteral(literal) => literal
+                .elements
+                .iter()
+                .find_map(|element| self.expression_type(element))
+                .map(|element| Type::Array {
+                    element: Box::new(element),
+                    length: Some(literal.elements.len() as u32),
+                }),
+            ast::Expression::ArrayRepeat(repeat) => {
+                self.expression_type(&repeat.value)
+                    .map(|element| Type::Array {
+                        element: Box::new(element),
+                        length: match repeat.length.as_ref() {
+                            ast::Expression::IntegerLiteral(length) => array_length(length),
+                            _ => None,
+                        },
+                    })
+            }
+            ast::Expression::IndexExpression(expr) => {
+                self.expression_type(&expr.array).and_then(element_type)
+            }
+            ast::Expression::StructLiteral(literal) => {
+                Some(Type::Struct(literal.name.name.clone()))
+            }
+            ast::Expression::TupleLiteral(literal) => literal
+                .elements
+                .iter()
+                .map(|element| self.expression_type(element))
+                .collect::<Option<_>>()
+                .map(Type::Tuple),
+            ast::Expression::FieldAccess(expr) => self
+                .expression_type(&expr.object)
+                .and_then(|ty| self.field(&ty, &expr.field).ok())
+                .map(|field| field.ty.clone()),
+            ast::Expression::EnumVariant(expr) => self
+                .types
+                .enums
+                .contains_key(expr.enum_name.name.as_str())
+                .then(|| Type::Enum(expr.enum_name.name.clone())),
+            // names bound by a pattern are not declared yet, so arms using them are skipped
+            ast::Expression::MatchExpression(expr) => expr
+                .arms
+                .iter()
+                .filter(|arm| !has_bindings(&arm.pattern))
+                .find_map(|arm| self.expression_type(&arm.expression)),
+            // likewise the variables defined by a block are not declared yet
+            ast::Expression::Block(block) => {
+                let statements = &block.statements.statements;
+                let defines_variables = statements.iter().any(|statement| {
+                    matches!(
+                        statement,
+                        ast::Statement::VariableDefinition(_)
+                            | ast::Statement::DestructuringDefinition(_)
+                    )
+                });
+                match statements.last() {
+                    Some(ast::Statement::Expression(expression)) if !defines_variables => {
+                        self.expression_type(expression)
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Lower an expression that must have the given type.
+    fn lower_expression_as(
+        &mut self,
+        expression: &ast::Expression,
+        ty: &Type,
+    ) -> Result<ir::Expression> {
+        let (expression, found) = self.lower_expression(expression, Some(ty))?;
+        if !found.is_assignable_to(ty) {
+            return Err(CodegenError::MismatchedTypes {
+                expected: ty.clone(),
+                found,
+                location: expression.location,
+            });
+        }
+        Ok(expression)
+    }
+
+    /// Lower the condition of an `if` or the operand of a logical operator, which must be a
+    /// `bool`.
+    fn lower_condition(&mut self, expression: &ast::Expression) -> Result<ir::Expression> {
+        self.lower_expression_as(expression, &Type::Bool)
+    }
+
+    /// Lower the address of an element of an array, checked against the bounds of the array.
+    ///
+    /// Returns the address and the type of the element. `expected_element` is the type of the
+    /// element the surrounding context expects.
+    fn lower_element_address(
+        &mut self,
+        array: &ast::Expression,
+        index: &ast::Expression,
+        expected_element: Option<&Type>,
+        location: &ast::Location,
+    ) -> Result<(ir::Expression, Type)> {
+        let expected_array = expected_element.map(|element| Type::Array {
+            element: Box::new(element.clone()),
+            length: None,
+        });
+        let (array, array_type) = self.lower_expression(array, expected_array.as_ref())?;
+        let Some(element) = element_type(array_type.clone()) else {
+            return Err(CodegenError::NotAnArray {
+                ty: array_type,
+                location: array.location,
+            });
+        };
+        let index = self.lower_expression_as(index, &Type::I32)?;
+        let address = ir::Expression {
+            kind: ir::ExpressionKind::Call {
+                function: format!("array_address_{}", element.ir_type()),
+                arguments: vec![array, index],
+            },
+            ty: ir::Type::I32,
+            location: location.clone(),
+        };
+        Ok((address, element))
+    }
+
+    /// Lower an assignment to an element of an array.
+    fn lower_index_assignment(&mut self, expr: &ast::IndexAssignmentExpression) -> Result<Store> {
+        let (address, element) =
+            self.lower_element_address(&expr.array, &expr.index, None, &expr.location)?;
+        let value = self.lower_expression_as(&expr.value, &element)?;
+        Ok(Store {
+            address,
+            value,
+            offset: 0,
+            ty: element,
+        })
+    }
+
+    /// Lower an assignment to a field of a struct.
+    fn lower_field_assignment(&mut self, expr: &ast::FieldAssignmentExpression) -> Result<Store> {
+        let (address, ty) = self.lower_expression(&expr.object, None)?;
+        let field = self.field(&ty, &expr.field)?;
+        let value = self.lower_expression_as(&expr.value, &field.ty)?;
+        Ok(Store {
+            address,
+            value,
+            offset: field.offset,
+            ty: field.ty.clone(),
+        })
+    }
+
+    /// Lower an assignment to memory as an expression evaluating to the stored value, which is
+    /// kept in a local:
+    ///   address[offset] = value => { store(address + offset, (%tmp = value)); %tmp }
+    fn lower_store_expression(&mut self, store: Store) -> (ir::ExpressionKind, Type) {
+        let local = self.temporary("tmp", store.ty.clone());
+        let value_type = store.value.ty;
+        let value_location = store.value.location.clone();
+        (
+            ir::ExpressionKind::Block(ir::Block {
+                statements: vec![ir::Statement::Store {
+                    address: store.address,
+                    value: ir::Expression {
+                        kind: ir::ExpressionKind::LocalTee {
+                            local,
+                            value: Box::new(store.value),
+                        },
+                        ty: value_type,
+                        location: value_location.clone(),
+                    },
+                    offset: store.offset,
+                }],
+                result: Some(Box::new(ir::Expression {
+                    kind: ir::ExpressionKind::LocalGet(local),
+                    ty: value_type,
+                    location: value_location,
+                })),
+            }),
+            store.ty,
+        )
+    }
+
+    /// Allocate memory for a value of the type and store the values at their offsets into it.
+    fn lower_allocation(
+        &mut self,
+        ty: &Type,
+        size: u32,
+        values: Vec<(ir::Expression, u32)>,
+        location: &ast::Location,
+    ) -> ir::ExpressionKind {
+        let local = self.temporary("tmp", ty.clone());
+        let get = |location: &ast::Location| ir::Expression {
+            kind: ir::ExpressionKind::LocalGet(local),
+            ty: ir::Type::I32,
+            location: location.clone(),
+        };
+        let size = ir::Expression {
+            kind: ir::ExpressionKind::Constant(ir::Constant::I32(size as i32)),
+            ty: ir::Type::I32,
+            location: location.clone(),
+        };
+        let mut statements = vec![ir::Statement::LocalSet {
+            local,
+            value: ir::Expression {
+                kind: ir::ExpressionKind::Call {
+                    function: "alloc".to_string(),
+                    arguments: vec![size],
+                },
+                ty: ir::Type::I32,
+                location: location.clone(),
+            },
+        }];
+        for (value, offset) in values {
+            statements.push(ir::Statement::Store {
+                address: get(&value.location),
+                value,
+                offset,
+            });
+        }
+        ir::ExpressionKind::Block(ir::Block {
+            statements,
+            result: Some(Box::new(get(location))),
+        })
+    }
+
+    /// Lower a match expression to a switch on the arm to evaluate.
+    ///
+    /// The switch dispatches on the discriminant of an enum, or on the offset of an integer from
+    /// the smallest value of the patterns if they span few values. Otherwise the integer is
+    /// compared with the patterns of every arm in order, and the switch dispatches on the index
+    /// of the first arm that matches.
+    fn lower_match(
+        &mut self,
+        expr: &ast::MatchExpression,
+        expected_type: Option<&Type>,
+    ) -> Result<(ir::ExpressionKind, Type)> {
+        let (value, value_type) = self.lower_expression(&expr.value, None)?;
+        let value_ir_type = value.ty;
+        let location = &expr.location;
+        let mut statements = Vec::new();
+        // the value is read by the patterns, so it is kept in a local
+        let local = match value.kind {
+            ir::ExpressionKind::LocalGet(local) => local,
+            _ => {
+                let local = self.temporary("match", value_type.clone());
+                statements.push(ir::Statement::LocalSet { local, value });
+                local
+            }
+        };
+        let get = |location: &ast::Location| ir::Expression {
+            kind: ir::ExpressionKind::LocalGet(local),
+            ty: value_ir_type,
+            location: location.clone(),
+        };
+        let layout = match &value_type {
+            Type::Enum(name) => self.types.enums.get(name.as_str()),
+            _ => None,
+        };
+
+        // the first arm matching every value, each variant, and each range of integers
+        let mut catch_all = None;
+        let mut variants = vec![None; layout.map_or(0, |layout| layout.variants.len())];
+        let mut ranges = Vec::new();
+        // the scope and the statements binding the names of the pattern of each arm, and the
+        // type of the expression of the arm if it can be inferred
+        let mut arms = Vec::with_capacity(expr.arms.len());
+        for (arm, ast_arm) in expr.arms.iter().enumerate() {
+            self.scopes.push(HashMap::new());
+            let mut bindings = Vec::new();
+            match &ast_arm.pattern {
+                ast::Pattern::Wildcard(_) => {
+                    catch_all.get_or_insert(arm);
+                }
+                ast::Pattern::Binding(name) => {
+                    catch_all.get_or_insert(arm);
+                    let binding = self.declare(name, value_type.clone(), true);
+                    bindings.push(ir::Statement::LocalSet {
+                        local: binding,
+                        value: get(&name.location),
+                    });
+                }
+                ast::Pattern::IntegerLiteral(literal) => {
+                    let value = pattern_value(literal, &value_type)?;
+                    ranges.push((value, value, arm));
+                }
+                ast::Pattern::Range(range) => {
+                    let start = pattern_value(&range.start, &value_type)?;
+                    let end = pattern_value(&range.end, &value_type)?;
+                    let end = if range.inclusive {
+                        Some(end)
+                    } else {
+                        end.checked_sub(1)
+                    };
+                    match end {
+                        Some(end) if start <= end => ranges.push((start, end, arm)),
+                        _ => {
+                            return Err(CodegenError::EmptyRange {
+                                location: range.location.clone(),
+                            })
+                        }
+                    }
+                }
+                ast::Pattern::Variant(pattern) => {
+                    let (_, discriminant, variant) =
+                        self.variant(&pattern.enum_name, &pattern.variant)?;
+                    let pattern_type = Type::Enum(pattern.enum_name.name.clone());
+                    if pattern_type != value_type {
+                        return Err(CodegenError::MismatchedTypes {
+                            expected: value_type,
+                            found: pattern_type,
+                            location: pattern.location.clone(),
+                        });
+                    }
+                    check_field_count(
+                        &pattern.enum_name,
+                        variant,
+                        pattern.fields.len(),
+                        &pattern.location,
+                    )?;
+                    for (field_pattern, field) in pattern.fields.iter().zip(&variant.fields.fields)
+                    {
+                        match field_pattern {
+                            ast::Pattern::Wildcard(_) => {}
+                            ast::Pattern::Binding(name) => {
+                                let binding = self.declare(name, field.ty.clone(), true);
+                                bindings.push(ir::Statement::LocalSet {
+                                    local: binding,
+                                    value: ir::Expression {
+                                        kind: ir::ExpressionKind::Load {
+                                            address: Box::new(get(&name.location)),
+                                            offset: field.offset,
+                                        },
+                                        ty: field.ty.ir_type(),
+                                        location: name.location.clone(),
+                                    },
+                                });
+                            }
+                            _ => {
+                                return Err(CodegenError::UnsupportedPattern {
+                                    location: field_pattern.location().clone(),
+                                })
+                            }
+                        }
+                    }
+                    variants[discriminant].get_or_insert(arm);
+                }
+            }
+            let ty = self.expression_type(&ast_arm.expression);
+            let scope = self.scopes.pop().expect("expected the scope of the arm");
+            arms.push((scope, bindings, ty));
+        }
+
+        // the first arm matching each value, with the missing patterns if no arm does
+        let first = |arm: Option<usize>| match (arm, catch_all) {
+            (Some(arm), Some(catch_all)) => Some(arm.min(catch_all)),
+            (arm, catch_all) => arm.or(catch_all),
+        };
+        let mut missing = Vec::new();
+        let (index, table, default) = if let Some(layout) = layout {
+            let mut table = Vec::with_capacity(variants.len());
+            for (variant, arm) in layout.variants.iter().zip(&variants) {
+                match first(*arm) {
+                    Some(arm) => table.push(arm),
+                    None => missing.push(variant_pattern(&value_type, variant)),
+                }
+            }
+            let index = if layout.is_boxed() {
+                ir::Expression {
+                    kind: ir::ExpressionKind::Load {
+                        address: Box::new(get(location)),
+                        offset: 0,
+                    },
+                    ty: ir::Type::I32,
+                    location: location.clone(),
+                }
+            } else {
+                get(location)
+            };
+            let default = catch_all.or(table.last().copied()).unwrap_or(0);
+            (index, table, default)
+        } else if value_type.is_integer() {
+            if catch_all.is_none() {
+                missing = missing_ranges(&ranges, &value_type);
+            }
+            lower_integer_dispatch(
+                get(location),
+                &value_type,
+                &ranges,
+                catch_all,
+                expr.arms.len(),
+            )
+        } else {
+            if catch_all.is_none() {
+                missing.push("`_`".to_string());
+            }
+            (
+                constant(0, ir::Type::I32, location),
+                Vec::new(),
+                catch_all.unwrap_or(0),
+            )
+        };
+        if !missing.is_empty() {
+            return Err(CodegenError::NonExhaustiveMatch {
+                missing: missing.join(", "),
+                location: expr.value.location().clone(),
+            });
+        }
+
+        let ty = arms
+            .iter()
+            .find_map(|(_, _, ty)| ty.clone())
+            .or_else(|| expected_type.cloned())
+            .unwrap_or_else(|| {
+                expr.arms
+                    .first()
+                    .map_or(Type::I32, |arm| default_type(&arm.expression))
+            });
+        let mut cases = Vec::with_capacity(arms.len());
+        for ((scope, bindings, _), arm) in arms.into_iter().zip(&expr.arms) {
+            self.scopes.push(scope);
+            let result = self.lower_expression_as(&arm.expression, &ty)?;
+            self.scopes.pop();
+            cases.push(ir::Block {
+                statements: bindings,
+                result: Some(Box::new(result)),
+            });
+        }
+        let switch = ir::ExpressionKind::Switch {
+            index: Box::new(index),
+            table,
+            default,
+            cases,
+        };
+        if statements.is_empty() {
+            return Ok((switch, ty));
+        }
+        Ok((
+            ir::ExpressionKind::Block(ir::Block {
+                statements,
+                result: Some(Box::new(ir::Expression {
+                    kind: switch,
+                    ty: ty.ir_type(),
+                    location: location.clone(),
+                })),
+            }),
+            ty,
+        ))
+    }
+
+    /// Type of the elements of an array expression, from the surrounding context or else from
+    /// the elements themselves.
+    fn array_element_type<'e>(
+        &self,
+        elements: impl IntoIterator<Item = &'e ast::Expression>,
+        expected_type: Option<&Type>,
+        location: &ast::Location,
+    ) -> Result<Type> {
+        let element = expected_type
+            .cloned()
+            .and_then(element_type)
+            .or_else(|| {
+                elements
+                    .into_iter()
+                    .find_map(|element| self.expression_type(element))
+            })
+            .unwrap_or(Type::I32);
+        check_element_type(&element, location)?;
+        Ok(element)
+    }
+
+    /// Lower an expression.
+    ///
+    /// `expected_type` is the type the surrounding context expects, which decides the type of
+    /// expressions such as integer literals that cannot infer their own type.
+    ///
+    /// Returns the expression with its type in the source.
+    fn lower_expression(
+        &mut self,
+        expression: &ast::Expression,
+        expected_type: Option<&Type>,
+    ) -> Result<(ir::Expression, Type)> {
+        let location = expression.location().clone();
+        let (kind, ty) = match expression {
+            ast::Expression::BinaryExpression(expr) => match expr.operator.operator {
+                // evaluate rhs only when lhs does not decide the result:
+                //   lhs && rhs => if lhs { rhs } else { false }
+                //   lhs || rhs => if lhs { true } else { rhs }
+                ast::OperatorKind::LogicalAnd | ast::OperatorKind::LogicalOr => {
+                    let condition = self.lower_condition(&expr.left)?;
+                    let rhs = self.lower_condition(&expr.right)?;
+                    let constant = ir::Expression {
+                        kind: ir::ExpressionKind::Constant(ir::Constant::I32(
+                            if expr.operator.operator == ast::OperatorKind::LogicalAnd {
+                                0
+                            } else {
+                                1
+                            },
+                        )),
+                        ty: ir::Type::I32,
+                        location: expr.operator.location.clone(),
+                    };
+                    let (then_result, else_result) =
+                        if expr.operator.operator == ast::OperatorKind::LogicalAnd {
+                            (rhs, constant)
+                        } else {
+                            (constant, rhs)
+                        };
+                    (
+                        ir::ExpressionKind::If {
+                            condition: Box::new(condition),
+                            then_block: ir::Block {
+                                statements: Vec::new(),
+                                result: Some(Box::new(then_result)),
+                            },
+                            else_block: ir::Block {
+                                statements: Vec::new(),
+                                result: Some(Box::new(else_result)),
+                            },
+                        },
+                        Type::Bool,
+                    )
+                }
+                ref operator => {
+                    let Some(operator) = lower_binary_operator(operator) else {
+                        return Err(CodegenError::UnsupportedOperator {
+                            operator: expr.operator.operator.clone(),
+                            location: expr.operator.location.clone(),
+                        });
+                    };
+                    // arithmetic operators produce the type of their operands, while comparison
+                    // operators always produce a bool
+                    let operand_type = self
+                        .expression_type(&expr.left)
+                        .or_else(|| self.expression_type(&expr.right))
+                        .or(if operator.is_comparison() {
+                            None
+                        } else {
+                            expected_type.filter(|ty| ty.is_numeric()).cloned()
+                        })
+                        .unwrap_or_else(|| {
+                            if default_type(&expr.left).is_float() {
+                                Type::F64
+                            } else {
+                                default_type(&expr.right)
+                            }
+                        });
+                    // booleans can only be compared for equality or combined bitwise, and the
+                    // remainder and shifts are only defined on integers
+                    let is_valid = match operator {
+                        ir::BinaryOperator::Equal | ir::BinaryOperator::NotEqual => {
+                            operand_type.is_numeric() || operand_type == Type::Bool
+                        }
+                        ir::BinaryOperator::And
+                        | ir::BinaryOperator::Or
+                        | ir::BinaryOperator::Xor => {
+                            operand_type.is_integer() || operand_type == Type::Bool
+                        }
+                        ir::BinaryOperator::Remainder
+                        | ir::BinaryOperator::ShiftLeft
+                        | ir::BinaryOperator::ShiftRight => operand_type.is_integer(),
+                        _ => operand_type.is_numeric(),
+                    };
+                    if !is_valid {
+                        return Err(CodegenError::InvalidOperand {
+                            operator: expr.operator.operator.clone(),
+                            ty: operand_type,
+                            location: expr.operator.location.clone(),
+                        });
+                    }
+                    let operator = if operand_type.is_unsigned() {
+                        operator.unsigned()
+                    } else {
+                        operator
+                    };
+                    let left = self.lower_expression_as(&expr.left, &operand_type)?;
+                    let right = self.lower_expression_as(&expr.right, &operand_type)?;
+                    (
+                        ir::ExpressionKind::Binary {
+                            operator,
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        },
+                        if operator.is_comparison() {
+                            Type::Bool
+                        } else {
+                            operand_type
+                        },
+                    )
+                }
+            },
+            ast::Expression::UnaryExpression(expr) => {
+                let (operand, operand_type) = self.lower_expression(&expr.operand, None)?;
+                if operand_type != Type::Bool {
+                    return Err(CodegenError::InvalidOperand {
+                        operator: expr.operator.operator.clone(),
+                        ty: operand_type,
+                        location: expr.operator.location.clone(),
+                    });
+                }
+                (
+                    ir::ExpressionKind::Unary {
+                        operator: ir::UnaryOperator::Eqz,
+                        operand: Box::new(operand),
+                    },
+                    Type::Bool,
+                )
+            }
+            ast::Expression::IfElseExpression(expr) => {
+                let ty = self.lower_type(&expr.return_type)?;
+                (
+                    ir::ExpressionKind::If {
+                        condition: Box::new(self.lower_condition(&expr.condition)?),
+                        then_block: self.lower_block(&expr.then_block, Some(&ty))?,
+                        else_block: self.lower_block(&expr.else_block, Some(&ty))?,
+                    },
+                    ty,
+                )
+            }
+            ast::Expression::CastExpression(expr) => {
+                let target_type = self.lower_type(&expr.target_type)?;
+                let source_type = self.expression_type(&expr.expression).unwrap_or_else(|| {
+                    let default = default_type(&expr.expression);
+                    if !default.is_float() && target_type.is_numeric() {
+                        target_type.clone()
+                    } else {
+                        default
+                    }
+                });
+                // numbers can be cast to each other, booleans to 0 or 1 and enums without values
+                // to their discriminant
+                let is_valid = if source_type.is_numeric() {
+                    target_type.is_numeric()
+                } else {
+                    (source_type == Type::Bool || self.is_discriminant(&source_type))
+                        && target_type.is_integer()
+                };
+                if !is_valid {
+                    return Err(CodegenError::InvalidCast {
+                        from: source_type,
+                        to: target_type,
+                        location,
+                    });
+                }
+                let operand = self.lower_expression_as(&expr.expression, &source_type)?;
+                if source_type.ir_type() == target_type.ir_type() {
+                    return Ok((
+                        ir::Expression {
+                            location,
+                            ..operand
+                        },
+                        target_type,
+                    ));
+                }
+                // a float is truncated to the signedness of the integer it is converted to
+                let signed = if source_type.is_float() {
+                    !target_type.is_unsigned()
+                } else {
+                    !source_type.is_unsigned()
+                };
+                (
+                    ir::ExpressionKind::Convert {
+                        operand: Box::new(operand),
+                        signed,
+                    },
+                    target_type,
+                )
+            }
+            ast::Expression::AssignmentExpression(expr) => {
+                let (variable, value, ty) = self.lower_assignment(&expr.name, &expr.value)?;
+                (assignment_expression(variable, value), ty)
+            }
+            ast::Expression::CompoundAssignmentExpression(expr) => {
+                let value = compound_assignment_value(expr);
+                let (variable, value, ty) = self.lower_assignment(&expr.name, &value)?;
+                (assignment_expression(variable, value), ty)
+            }
+            ast::Expression::IndexAssignmentExpression(expr) => {
+                let store = self.lower_index_assignment(expr)?;
+                self.lower_store_expression(store)
+            }
+            ast::Expression::FieldAssignmentExpression(expr) => {
+                let store = self.lower_field_assignment(expr)?;
+                self.lower_store_expression(store)
+            }
+            ast::Expression::FunctionCall(call) if self.is_len(call) => {
+                let [array] = call.arguments.as_slice() else {
+                    return Err(CodegenError::ArgumentCount {
+                        function: LEN.to_string(),
+                        expected: 1,
+                        found: call.arguments.len(),
+                        location,
+                    });
+                };
+                let (array, array_type) = self.lower_expression(array, None)?;
+                if element_type(array_type.clone()).is_none() {
+                    return Err(CodegenError::NotAnArray {
+                        ty: array_type,
+                        location: array.location,
+                    });
+                }
+                (
+                    ir::ExpressionKind::Load {
+                        address: Box::new(array),
+                        offset: 0,
+                    },
+                    Type::I32,
+                )
+            }
+            ast::Expression::FunctionCall(call) => {
+                let (arguments, return_type) = self.lower_call(call)?;
+                match return_type {
+                    Type::Unit => {
+                        return Err(CodegenError::NoValue {
+                            name: call.name.name.clone(),
+                            location,
+                        })
+                    }
+                    Type::Tuple(_) => return Err(CodegenError::MisplacedTuple { location }),
+                    _ => {}
+                }
+                (
+                    ir::ExpressionKind::Call {
+                        function: call.name.name.clone(),
+                        arguments,
+                    },
+                    return_type,
+                )
+            }
+            ast::Expression::Identifier(identifier) => {
+                let (variable, ty) = self.variable(identifier)?;
+                let kind = match variable {
+                    Variable::Local(local) => ir::ExpressionKind::LocalGet(local),
+                    Variable::Constant(value) => ir::ExpressionKind::Constant(value),
+                    Variable::Static(global) => ir::ExpressionKind::GlobalGet(global),
+                };
+                (kind, ty)
+            }
+            ast::Expression::IntegerLiteral(literal) => {
+                let ty = expected_type
+                    .filter(|ty| ty.is_numeric())
+                    .cloned()
+                    .unwrap_or(Type::I32);
+                let value = &literal.value;
+                // unsigned values are stored with the bits of their signed counterpart
+                let constant = match ty {
+                    Type::U32 => value
+                        .parse()
+                        .map(|value: u32| ir::Constant::I32(value as i32)),
+                    Type::U64 => value
+                        .parse()
+                        .map(|value: u64| ir::Constant::I64(value as i64)),
+                    Type::F32 => Ok(ir::Constant::F32(value.parse().unwrap_or(f32::INFINITY))),
+                    Type::F64 => Ok(ir::Constant::F64(value.parse().unwrap_or(f64::INFINITY))),
+                    Type::I64 => value.parse().map(ir::Constant::I64),
+                    _ => value.parse().map(ir::Constant::I32),
+                }
+                .ok()
+                .filter(|constant| !is_infinite(constant))
+                .ok_or_else(|| CodegenError::LiteralOutOfRange {
+                    literal: value.clone(),
+                    ty: ty.clone(),
+                    location: location.clone(),
+                })?;
+                (ir::ExpressionKind::Constant(constant), ty)
+            }
+            ast::Expression::FloatLiteral(literal) => {
+                let ty = expected_type
+                    .filter(|ty| ty.is_float())
+                    .cloned()
+                    .unwrap_or(Type::F64);
+                let constant = match ty {
+                    Type::F32 => literal.value.parse().map(ir::Constant::F32),
+                    _ => literal.value.parse().map(ir::Constant::F64),
+                }
+                .ok()
+                .filter(|constant| !is_infinite(constant))
+                .ok_or_else(|| CodegenError::LiteralOutOfRange {
+                    literal: literal.value.clone(),
+                    ty: ty.clone(),
+                    location: location.clone(),
+                })?;
+                (ir::ExpressionKind::Constant(constant), ty)
+            }
+            ast::Expression::BooleanLiteral(literal) => (
+                ir::ExpressionKind::Constant(ir::Constant::I32(literal.value as i32)),
+                Type::Bool,
+            ),
+            // a zeroed array is created, then every element is stored:
+            //   [a, b] => { %tmp = array_new_i32(0, 2); store(%tmp + 8, a); store(%tmp + 12, b); %tmp }
+            ast::Expression::ArrayLiteral(literal) => {
+                let element =
+                    self.array_element_type(&literal.elements, expected_type, &location)?;
+                let element_ir_type = element.ir_type();
+                let element_size = value_size(element_ir_type);
+                let ty = Type::Array {
+                    element: Box::new(element.clone()),
+                    length: Some(literal.elements.len() as u32),
+                };
+                let local = self.temporary("tmp", ty.clone());
+                let get = |location: &ast::Location| ir::Expression {
+                    kind: ir::ExpressionKind::LocalGet(local),
+                    ty: ir::Type::I32,
+                    location: location.clone(),
+                };
+                let zero = constant(0, element_ir_type, &location);
+                let length = ir::Expression {
+                    kind: ir::ExpressionKind::Constant(ir::Constant::I32(
+                        literal.elements.len() as i32
+                    )),
+                    ty: ir::Type::I32,
+                    location: location.clone(),
+                };
+                let mut statements = vec![ir::Statement::LocalSet {
+                    local,
+                    value: ir::Expression {
+                        kind: ir::ExpressionKind::Call {
+                            function: format!("array_new_{}", element_ir_type),
+                            arguments: vec![zero, length],
+                        },
+                        ty: ir::Type::I32,
+                        location: location.clone(),
+                    },
+                }];
+                for (index, value) in literal.elements.iter().enumerate() {
+                    let value = self.lower_expression_as(value, &element)?;
+                    statements.push(ir::Statement::Store {
+                        address: get(&value.location),
+                        value,
+                        offset: ARRAY_ELEMENTS_OFFSET + index as u32 * element_size,
+                    });
+                }
+                (
+                    ir::ExpressionKind::Block(ir::Block {
+                        statements,
+                        result: Some(Box::new(get(&location))),
+                    }),
+                    ty,
+                )
+            }
+            ast::Expression::ArrayRepeat(repeat) => {
+                let element =
+                    self.array_element_type([repeat.value.as_ref()], expected_type, &location)?;
+                let value = self.lower_expression_as(&repeat.value, &element)?;
+                let length = self.lower_expression_as(&repeat.length, &Type::I32)?;
+                (
+                    ir::ExpressionKind::Call {
+                        function: format!("array_new_{}", element.ir_type()),
+                        arguments: vec![value, length],
+                    },
+                    Type::Array {
+                        element: Box::new(element),
+                        length: match repeat.length.as_ref() {
+                            ast::Expression::IntegerLiteral(length) => array_length(length),
+                            _ => None,
+                        },
+                    },
+                )
+            }
+            ast::Expression::IndexExpression(expr) => {
+                let (address, element) =
+                    self.lower_element_address(&expr.array, &expr.index, expected_type, &location)?;
+                (
+                    ir::ExpressionKind::Load {
+                        address: Box::new(address),
+                        offset: 0,
+                    },
+                    element,
+                )
+            }
+            // the struct is allocated, then every field is stored in the order of the initializers:
+            //   Point { x: a, y: b } => { %tmp = alloc(8); store(%tmp + 0, a); store(%tmp + 4, b); %tmp }
+            ast::Expression::StructLiteral(literal) => {
+                let layout = self
+                    .types
+                    .structs
+                    .get(literal.name.name.as_str())
+                    .ok_or_else(|| CodegenError::UnknownType {
+                        name: literal.name.name.clone(),
+                        location: literal.name.location.clone(),
+                    })?;
+                let ty = Type::Struct(literal.name.name.clone());
+                for (index, initializer) in literal.fields.iter().enumerate() {
+                    if literal.fields[..index]
+                        .iter()
+                        .any(|previous| previous.name.name == initializer.name.name)
+                    {
+                        return Err(CodegenError::DuplicateField {
+                            field: initializer.name.name.clone(),
+                            location: initializer.name.location.clone(),
+                        });
+                    }
+                }
+                if let Some(missing) = layout.fields.iter().find(|field| {
+                    !literal
+                        .fields
+                        .iter()
+                        .any(|initializer| initializer.name.name == field.name)
+                }) {
+                    return Err(CodegenError::MissingField {
+                        name: literal.name.name.clone(),
+                        field: missing.name.clone(),
+                        location,
+                    });
+                }
+                let values = literal
+                    .fields
+                    .iter()
+                    .map(|initializer| {
+                        let field = self.field(&ty, &initializer.name)?;
+                        let value = self.lower_expression_as(&initializer.value, &field.ty)?;
+                        Ok((value, field.offset))
+                    })
+                    .collect::<Result<_>>()?;
+                (
+                    self.lower_allocation(&ty, layout.size, values, &location),
+                    ty,
+                )
+            }
+            ast::Expression::FieldAccess(expr) => {
+                let (object, ty) = self.lower_expression(&expr.object, None)?;
+                let field = self.field(&ty, &expr.field)?;
+                (
+                    ir::ExpressionKind::Load {
+                        address: Box::new(object),
+                        offset: field.offset,
+                    },
+                    field.ty.clone(),
+                )
+            }
+            // a variant with values is allocated like a struct, after its discriminant:
+            //   Shape::Rectangle(a, b) => { %tmp = alloc(12); store(%tmp, 1); store(%tmp + 4, a); store(%tmp + 8, b); %tmp }
+            ast::Expression::EnumVariant(expr) => {
+                let (layout, discriminant, variant) =
+                    self.variant(&expr.enum_name, &expr.variant)?;
+                check_field_count(&expr.enum_name, variant, expr.arguments.len(), &location)?;
+                let ty = Type::Enum(expr.enum_name.name.clone());
+                let discriminant = ir::Expression {
+                    kind: ir::ExpressionKind::Constant(ir::Constant::I32(discriminant as i32)),
+                    ty: ir::Type::I32,
+                    location: location.clone(),
+                };
+                if !layout.is_boxed() {
+                    return Ok((discriminant, ty));
+                }
+                let mut values = vec![(discriminant, 0)];
+                for (argument, field) in expr.arguments.iter().zip(&variant.fields.fields) {
+                    values.push((self.lower_expression_as(argument, &field.ty)?, field.offset));
+                }
+                (
+                    self.lower_allocation(&ty, variant.fields.size, values, &location),
+                    ty,
+                )
+            }
+            ast::Expression::MatchExpression(expr) => self.lower_match(expr, expected_type)?,
+            ast::Expression::TupleLiteral(_) => {
+                return Err(CodegenError::MisplacedTuple { location });
+            }
+            ast::Expression::Block(block) => {
+                let (block, ty) = self.lower_block_expression(block, expected_type)?;
+                (ir::ExpressionKind::Block(block), ty)
+            }
+        };
+        Ok((
+            ir::Expression {
+                kind,
+                ty: ty.ir_type(),
+                location,
+            },
+            ty,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn lower_source(source: &str) -> Result<ir::Program> {
+        let tokens = tokenizer::tokenize(source.to_string());
+        let ast = parser::parse(tokens);
+        lower(&ast)
+    }
+
+    #[test]
+    fn lower_dumps_typed_ir() {
+        let source = indoc! {"
+            fn add(a: i64, b: i32) -> i64 {
+                let c: i64 = a + b as i64;
+                if c > 0 && !(b == 0) {
+                    print_i64(c);
+                }
+                c
+            }
+
+            fn main() -> i32 { 0 }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn add(%0 a: i64, %1 b: i32) -> i64 {
+                    local %2 c: i64
+                    %2 = i64.add(%0, i64.convert_i32(%1));
+                    if if i64.gt(%2, 0i64) -> i32 {
+                        i32.eqz(i32.eq(%1, 0i32))
+                    } else {
+                        0i32
+                    } {
+                        print_i64(%2);
+                    }
+                    %2
+                }
+
+                fn main() -> i32 {
+                    0i32
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_resolves_shadowed_variables() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let x: i32 = 1;
+                if x == 1 {
+                    let x: i64 = 2;
+                    print_i64(x);
+                }
+                let x: i32 = x + 1;
+                x
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn main() -> i32 {
+                    local %0 x: i32
+                    local %1 x: i64
+                    local %2 x: i32
+                    %0 = 1i32;
+                    if i32.eq(%0, 1i32) {
+                        %1 = 2i64;
+                        print_i64(%1);
+                    }
+                    %2 = i32.add(%0, 1i32);
+                    %2
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_dumps_compound_assignment() {
+        let source = indoc! {"
+            fn halve(a: u32) -> u32 {
+                var b: u32 = a;
+                b /= 2;
+                b += 1
+            }
+
+            fn main() -> i32 { 0 }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn halve(%0 a: i32) -> i32 {
+                    local %1 b: i32
+                    %1 = %0;
+                    (%1 = i32.div_u(%1, 2i32));
+                    (%1 = i32.add(%1, 1i32))
+                }
+
+                fn main() -> i32 {
+                    0i32
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_dumps_constants_and_statics() {
+        let source = indoc! {"
+            const LIMIT: u32 = 1 << 4;
+            static var count: u32 = LIMIT - 1;
+            fn next() -> u32 {
+                count += 1;
+                count = LIMIT
+            }
+
+            fn main() -> i32 { 0 }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                global @0 count: i32 = 15i32
+
+                fn next() -> i32 {
+                    @0 = i32.add(@0, 1i32);
+                    block -> i32 {
+                        @0 = 16i32;
+                        @0
+                    }
+                }
+
+                fn main() -> i32 {
+                    0i32
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_functions() {
+        let cases = [
+            (
+                "fn f() -> i32 { 1 } fn f() -> i32 { 2 } fn main() -> i32 { f() }",
+                "the name `f` is defined multiple times at 1:24",
+            ),
+            (
+                "fn print_i64(n: i64) -> i32 { 0 } fn main() -> i32 { print_i64(1) }",
+                "the name `print_i64` is defined multiple times at 1:4",
+            ),
+            (
+                "fn array_new_i32(n: i32, m: i32) -> i32 { n } fn main() -> i32 { 0 }",
+                "the name `array_new_i32` is defined multiple times at 1:4",
+            ),
+            ("fn f() -> i32 { 0 }", "`main` function not found"),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_globals() {
+        let cases = [
+            (
+                "fn f() -> i32 { 1 } const N: i32 = f(); fn main() -> i32 { N }",
+                "initial value of `N` cannot be evaluated at compile time at 1:36",
+            ),
+            (
+                "static var a: i32 = 1; static var b: i32 = a; fn main() -> i32 { b }",
+                "initial value of `b` cannot be evaluated at compile time at 1:44",
+            ),
+            (
+                "const N: i32 = 1; fn main() -> i32 { N = 2; 0 }",
+                "cannot assign to constant `N` at 1:38",
+            ),
+            (
+                "const N: i32 = 1 / 0; fn main() -> i32 { N }",
+                "initial value of `N` cannot be evaluated at compile time at 1:16",
+            ),
+            (
+                "const N: i32 = 1; static var N: i32 = 2; fn main() -> i32 { N }",
+                "the name `N` is defined multiple times at 1:30",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_returns_errors_for_assignments_to_immutable_variables() {
+        let cases = [
+            (
+                "fn main() -> i32 { let x: i32 = 1; x = 5; x }",
+                "cannot assign to immutable variable `x` at 1:36",
+            ),
+            (
+                "fn main() -> i32 { let x: i32 = 1; x += 2; x }",
+                "cannot assign to immutable variable `x` at 1:36",
+            ),
+            (
+                "fn main() -> i32 { let (a, b) = (1, 2); b = 3; a + b }",
+                "cannot assign to immutable variable `b` at 1:41",
+            ),
+            (
+                "fn main() -> i32 { var x: i32 = 1; if x > 0 { let x: i32 = 2; x = 3; } x }",
+                "cannot assign to immutable variable `x` at 1:63",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+        assert!(lower_source(
+            "fn main() -> i32 { var (a, b) = (1, 2); var x: i32 = 1; let y: i32 = 2; \
+             if y > 0 { var y: i32 = 3; y += 1; } a = 3; x += b; x }"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn lower_dumps_tuples() {
+        let source = indoc! {"
+            fn divmod(a: i32, b: i32) -> (i32, i64) {
+                (a / b, (a % b) as i64)
+            }
+
+            fn forward(a: i32) -> (i32, i64) {
+                divmod(a, 2)
+            }
+
+            fn main() -> i32 {
+                let (q, r) = forward(7);
+                q
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn divmod(%0 a: i32, %1 b: i32) -> (i32, i64) {
+                    return i32.div(%0, %1), i64.convert_i32(i32.rem(%0, %1));
+                }
+
+                fn forward(%0 a: i32) -> (i32, i64) {
+                    return divmod(%0, 2i32);
+                }
+
+                fn main() -> i32 {
+                    local %0 q: i32
+                    local %1 r: i64
+                    %0, %1 = forward(7i32);
+                    %0
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_dumps_block_expressions() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let x: i64 = { let y = 2; y * 3 };
+                { let z = x; z as i32 }
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn main() -> i32 {
+                    local %0 y: i64
+                    local %1 x: i64
+                    local %2 z: i64
+                    %1 = block -> i64 {
+                        %0 = 2i64;
+                        i64.mul(%0, 3i64)
+                    };
+                    block -> i32 {
+                        %2 = %1;
+                        i32.convert_i64(%2)
+                    }
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_misused_tuples() {
+        let cases = [
+            (
+                "fn main() -> i32 { let x: (i32, i32) = 1; 0 }",
+                "tuple types can only be the return type of a function at 1:27",
+            ),
+            (
+                "fn f() -> (i32, i32) { (1, 2) } fn main() -> i32 { let (a, b, c) = f(); a }",
+                "cannot destructure a tuple of 2 values into 3 names at 1:68",
+            ),
+            (
+                "fn f() -> (i32, i32) { (1, 2) } fn main() -> i32 { print_int(f()); 0 }",
+                "tuples can only be returned from a function or destructured at 1:62",
+            ),
+            (
+                "fn main() -> i32 { let x = (1, 2); 0 }",
+                "tuples can only be returned from a function or destructured at 1:28",
+            ),
+            (
+                "fn main() -> i32 { let (a, b) = 1; a }",
+                "expected a tuple, found `i32` at 1:33",
+            ),
+            (
+                "fn f() -> (i32, i32) { (1, true) } fn main() -> i32 { 0 }",
+                "mismatched types: expected `i32`, found `bool` at 1:28",
+            ),
+            (
+                "fn f() -> (i32, i32) { (1, 2, 3) } fn main() -> i32 { 0 }",
+                "mismatched types: expected `(i32, i32)`, found `(i32, i32, i32)` at 1:24",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_dumps_functions_without_return_values() {
+        let source = indoc! {"
+            fn greet(n: i32) {
+                print_int(n);
+            }
+
+            fn main() {
+                greet(1)
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn greet(%0 n: i32) {
+                    print_int(%0);
+                }
+
+                fn main() -> i32 {
+                    greet(1i32);
+                    0i32
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_unit_values() {
+        let cases = [
+            (
+                "fn f() {} fn main() -> i32 { let a: i32 = f(); a }",
+                "function `f` does not return a value at 1:43",
+            ),
+            (
+                "fn f() {} fn main() -> i32 { f() }",
+                "function `f` does not return a value at 1:30",
+            ),
+            (
+                "fn f() {} fn main() -> i32 { f() + 1 }",
+                "cannot apply `+` to a value of type `()` at 1:34",
+            ),
+            (
+                "fn f(a: ()) {} fn main() -> i32 { 0 }",
+                "the unit type `()` can only be the return type of a function at 1:9",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_infers_variable_types() {
+        let source = indoc! {"
+            struct Point { x: f32, y: f32 }
+
+            fn scale(n: u64) -> u64 {
+                let factor = 3;
+                var total = 0;
+                total = n * factor;
+                total
+            }
+
+            fn main() {
+                let a = 5;
+                let b: i64 = a * 2;
+                let half = 0.5;
+                let p = Point { x: half, y: 1.0 };
+                let ratio = p.x / 2.0;
+                let zeros = [0; 4];
+                let first = zeros[0] + 1;
+                let wide = [0, b];
+                let positive = first > 0;
+            }
+        "};
+        let tokens = tokenizer::tokenize(source.to_string());
+        let ast = parser::parse(tokens);
+        let inferred_types = check(&ast)
+            .unwrap()
+            .iter()
+            .map(|inferred| format!("{}: {}", inferred.name.name, inferred.ty))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            inferred_types,
+            [
+                "factor: u64",
+                "total: u64",
+                "a: i64",
+                "half: f32",
+                "p: Point",
+                "ratio: f32",
+                "zeros: [i32; 4]",
+                "first: i32",
+                "wide: [i64; 2]",
+                "positive: bool",
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_uninferred_types() {
+        let cases = [
+            (
+                "fn main() -> i32 { var x; 0 }",
+                "type annotations needed for `x` at 1:24",
+            ),
+            (
+                "fn f() {} fn main() -> i32 { let x = f(); 0 }",
+                "function `f` does not return a value at 1:38",
+            ),
+            (
+                "fn main() -> i32 { let x = 5; let y: bool = x; 0 }",
+                "mismatched types: expected `bool`, found `i32` at 1:45",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_dumps_array_accesses() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: [i64; 2] = [1, 2];
+                a[1] = a[0];
+                len(a)
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn main() -> i32 {
+                    local %0 tmp: i32
+                    local %1 a: i32
+                    %1 = block -> i32 {
+                        %0 = array_new_i64(0i64, 2i32);
+                        i64.store(%0 + 8, 1i64);
+                        i64.store(%0 + 16, 2i64);
+                        %0
+                    };
+                    i64.store(array_address_i64(%1, 1i32), i64.load(array_address_i64(%1, 0i32)));
+                    i32.load(%1)
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_array_types() {
+        let cases = [
+            (
+                "fn main() -> i32 { let a: [i32; 2] = [1, 2, 3]; 0 }",
+                "mismatched types: expected `[i32; 2]`, found `[i32; 3]` at 1:38",
+            ),
+            (
+                "fn main() -> i32 { let a: i32 = 1; a[0] }",
+                "expected an array, found `i32` at 1:36",
+            ),
+            (
+                "fn main() -> i32 { let a: [[i32]] = [[1]]; 0 }",
+                "arrays of `[i32]` are not supported at 1:27",
+            ),
+            (
+                "fn main() -> i32 { let a: [bool] = [true]; 0 }",
+                "arrays of `bool` are not supported at 1:27",
+            ),
+            (
+                "fn main() -> i32 { let a: [i32] = [0; 3]; a + 1 }",
+                "cannot apply `+` to a value of type `[i32]` at 1:45",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_dumps_struct_accesses() {
+        let source = indoc! {"
+            struct Pair {
+                a: i32,
+                b: i64,
+            }
+
+            fn main() -> i64 {
+                let p: Pair = Pair { b: 2, a: 1 };
+                p.a = 3;
+                p.b
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn main() -> i64 {
+                    local %0 tmp: i32
+                    local %1 p: i32
+                    %1 = block -> i32 {
+                        %0 = alloc(16i32);
+                        i64.store(%0 + 8, 2i64);
+                        i32.store(%0, 1i32);
+                        %0
+                    };
+                    i32.store(%1, 3i32);
+                    i64.load(%1 + 8)
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_structs() {
+        let cases = [
+            (
+                "fn main() -> i32 { let p: Point = 0; 0 }",
+                "cannot find type `Point` in this scope at 1:27",
+            ),
+            (
+                "struct P { a: i32, a: i32 } fn main() -> i32 { 0 }",
+                "field `a` is specified more than once at 1:20",
+            ),
+            (
+                "struct P { a: i32 } fn main() -> i32 { let p: P = P { a: 1 }; p.b }",
+                "no field `b` on type `P` at 1:65",
+            ),
+            (
+                "struct P { a: i32, b: i32 } fn main() -> i32 { let p: P = P { a: 1 }; 0 }",
+                "missing field `b` in initializer of `P` at 1:59",
+            ),
+            (
+                "struct P { a: i32 } fn main() -> i32 { let p: P = P { a: 1, a: 2 }; 0 }",
+                "field `a` is specified more than once at 1:61",
+            ),
+            (
+                "struct P { a: i32 } fn main() -> i32 { let p: P = P { a: 1 }; p + 1 }",
+                "cannot apply `+` to a value of type `P` at 1:65",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_dumps_match_expressions() {
+        let source = indoc! {"
+            enum Option { None, Some(i64) }
+
+            fn f(option: Option, n: i32) -> i64 {
+                match n {
+                    1 => 10,
+                    3..=4 => match option { Option::Some(value) => value, Option::None => 0 },
+                    _ => 0,
+                }
+            }
+
+            fn main() -> i32 { 0 }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn f(%0 option: i32, %1 n: i32) -> i64 {
+                    local %2 value: i64
+                    switch i32.sub(%1, 1i32) [0, 2, 1, 1, default 2] -> i64 {
+                        case 0 {
+                            10i64
+                        }
+                        case 1 {
+                            switch i32.load(%0) [1, 0, default 0] -> i64 {
+                                case 0 {
+                                    %2 = i64.load(%0 + 8);
+                                    %2
+                                }
+                                case 1 {
+                                    0i64
+                                }
+                            }
+                        }
+                        case 2 {
+                            0i64
+                        }
+                    }
+                }
+
+                fn main() -> i32 {
+                    0i32
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_matches() {
+        let cases = [
+            (
+                "enum E { A, B } fn main() -> i32 { match E::A { E::A => 0 } }",
+                "non-exhaustive patterns: `E::B` not covered at 1:42",
+            ),
+            (
+                "fn main() -> i32 { match 1 { 0 => 0, 2..=9 => 1, 2147483647 => 2 } }",
+                "non-exhaustive patterns: `i32::MIN..=-1`, `1`, `10..=2147483646` not covered at 1:26",
+            ),
+            (
+                "enum E { A(i32) } fn main() -> i32 { match E::A(1) { E::A(1) => 0, _ => 1 } }",
+                "values of variants can only be matched by names or `_` at 1:59",
+            ),
+            (
+                "enum E { A(i32) } fn main() -> i32 { match E::A(1) { E::A(x, y) => 0 } }",
+                "variant `E::A` has 1 values but 2 were supplied at 1:54",
+            ),
+            (
+                "enum E { A } fn main() -> i32 { match E::A { E::C => 0 } }",
+                "no variant `C` in enum `E` at 1:49",
+            ),
+            (
+                "enum E { A } fn main() -> i32 { match 0 { E::A => 0 } }",
+                "mismatched types: expected `i32`, found `E` at 1:43",
+            ),
+            (
+                "enum E { A } fn main() -> i32 { match E::A { 0 => 0 } }",
+                "mismatched types: expected `E`, found `i32` at 1:46",
+            ),
+            (
+                "fn main() -> i32 { match 0 { 5..5 => 0, _ => 1 } }",
+                "range pattern matches no values at 1:30",
+            ),
+            (
+                "enum E { A } struct E { a: i32 } fn main() -> i32 { 0 }",
+                "the name `E` is defined multiple times at 1:6",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_booleans() {
+        let cases = [
+            (
+                "fn main() -> i32 { if 1 { print_int(1); } 0 }",
+                "mismatched types: expected `bool`, found `i32` at 1:23",
+            ),
+            (
+                "fn main() -> bool { true && 1 }",
+                "mismatched types: expected `bool`, found `i32` at 1:29",
+            ),
+            (
+                "fn main() -> bool { !0 }",
+                "cannot apply `!` to a value of type `i32` at 1:21",
+            ),
+            (
+                "fn main() -> bool { true < false }",
+                "cannot apply `<` to a value of type `bool` at 1:26",
+            ),
+            (
+                "fn main() -> i32 { let b: bool = true; b + 1 }",
+                "cannot apply `+` to a value of type `bool` at 1:42",
+            ),
+            (
+                "fn main() -> bool { 1 as bool }",
+                "cannot cast `i32` as `bool` at 1:21",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_dumps_unsigned_and_float_operations() {
+        let source = indoc! {"
+            fn main(a: u32, b: f32) -> f64 {
+                if a / 2 < 1 { print_int(1); }
+                b as f64 + a as f64 + b as u64 as f64
+            }
+        "};
+        let ir = lower_source(source).unwrap();
+        assert_eq!(
+            ir.to_string(),
+            indoc! {"
+                fn main(%0 a: i32, %1 b: f32) -> f64 {
+                    if i32.lt_u(i32.div_u(%0, 2i32), 1i32) {
+                        print_int(1i32);
+                    }
+                    f64.add(f64.add(f64.convert_f32(%1), f64.convert_i32_u(%0)), f64.convert_i64_u(i64.convert_f32_u(%1)))
+                }
+            "}
+        );
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_numbers() {
+        let cases = [
+            (
+                "fn main() -> f64 { let a: f64 = 1.5; let b: f32 = 2.5; a + b }",
+                "mismatched types: expected `f64`, found `f32` at 1:60",
+            ),
+            (
+                "fn main() -> u32 { let a: u32 = 1; let b: i32 = 2; a * b }",
+                "mismatched types: expected `u32`, found `i32` at 1:56",
+            ),
+            (
+                "fn main() -> u32 { 4294967296 }",
+                "literal `4294967296` is out of range for `u32` at 1:20",
+            ),
+            (
+                "fn main() -> i32 { 1.5 }",
+                "mismatched types: expected `i32`, found `f64` at 1:20",
+            ),
+            (
+                "fn main() -> f64 { 1.5 % 2.0 }",
+                "cannot apply `%` to a value of type `f64` at 1:24",
+            ),
+            (
+                "fn main() -> bool { true << false }",
+                "cannot apply `<<` to a value of type `bool` at 1:26",
+            ),
+            (
+                "fn main() -> f64 { true as f64 }",
+                "cannot cast `bool` as `f64` at 1:20",
+            ),
+            (
+                "fn main() -> bool { 1.5 as bool }",
+                "cannot cast `f64` as `bool` at 1:21",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_returns_errors_for_invalid_casts() {
+        let cases = [
+            (
+                "fn main() -> i32 { let a: [i32; 1] = [1]; a as i32 }",
+                "cannot cast `[i32; 1]` as `i32` at 1:43",
+            ),
+            (
+                "struct P { x: i32 } fn main() -> i64 { P { x: 1 } as i64 }",
+                "cannot cast `P` as `i64` at 1:40",
+            ),
+            (
+                "enum E { A(i32) } fn main() -> i32 { E::A(1) as i32 }",
+                "cannot cast `E` as `i32` at 1:38",
+            ),
+            (
+                "enum E { A } fn main() -> f64 { E::A as f64 }",
+                "cannot cast `E` as `f64` at 1:33",
+            ),
+            (
+                "fn main() -> i32 { 1 as [i32; 1]; 0 }",
+                "cannot cast `i32` as `[i32; 1]` at 1:20",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(lower_source(source).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn lower_returns_error_for_unknown_variable() {
+        let source = "fn main() -> i32 { y }";
+        assert_eq!(
+            lower_source(source).unwrap_err().to_string(),
+            "cannot find variable `y` in this scope at 1:20"
+        );
+    }
+
+    #[test]
+    fn lower_returns_error_for_mismatched_types() {
+        let source = "fn main() -> i32 { let a: i64 = 1; print_int(a); 0 }";
+        assert_eq!(
+            lower_source(source).unwrap_err().to_string(),
+            "mismatched types: expected `i32`, found `i64` at 1:46"
+        );
+    }
+
+    #[test]
+    fn lower_returns_located_error_for_literal_out_of_range() {
+        let source = "fn main() -> i32 {\n    print_int(2147483648)\n}";
+        let error = lower_source(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "literal `2147483648` is out of range for `i32` at 2:15"
+        );
+        assert_eq!(
+            error.location().map(|location| location.start.line),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,538 @@
+//! Inference of the types of variables defined without one.
+//!
+//! Before a function is lowered, each variable defined without a type gets a type variable, which
+//! is unified with the types of the expressions the variable meets: its initial value, the values
+//! assigned to it, the other operands of its operators and the parameters it is passed to. Integer
+//! and float literals get type variables too, restricted to numeric and float types, which default
+//! to `i32` and `f64` when nothing else decides them.
+//!
+//! Inference reports no errors. Constraints that cannot be satisfied are ignored, and lowering
+//! then checks every expression against the inferred types.
+
+use std::collections::HashMap;
+
+use super::{element_type, FunctionLowerer};
+use crate::Type;
+
+/// Type of an expression, which may still contain type variables.
+#[derive(Clone, Debug)]
+enum Term {
+    Type(Type),
+    Variable(usize),
+    Array {
+        element: Box<Term>,
+        length: Option<u32>,
+    },
+}
+
+/// Types a type variable may be resolved to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Any,
+
+    /// Numeric types, which integer literals may have.
+    Numeric,
+
+    /// Float types, which float literals may have.
+    Float,
+}
+
+impl Kind {
+    /// Kind of the types of both kinds.
+    fn intersect(self, other: Kind) -> Kind {
+        match (self, other) {
+            (Kind::Any, kind) | (kind, Kind::Any) => kind,
+            (Kind::Float, _) | (_, Kind::Float) => Kind::Float,
+            (Kind::Numeric, Kind::Numeric) => Kind::Numeric,
+        }
+    }
+
+    fn admits(self, ty: &Type) -> bool {
+        match self {
+            Kind::Any => true,
+            Kind::Numeric => ty.is_numeric(),
+            Kind::Float => ty.is_float(),
+        }
+    }
+
+    /// Type of a variable of the kind that nothing decides.
+    fn default_type(self) -> Option<Type> {
+        match self {
+            Kind::Any => None,
+            Kind::Numeric => Some(Type::I32),
+            Kind::Float => Some(Type::F64),
+        }
+    }
+}
+
+/// Infer the types of the variables the function defines without one, by the index of their
+/// names in the source.
+///
+/// Variables whose type is not decided, or would be `()`, are left out.
+pub(super) fn infer_function(
+    lowerer: &FunctionLowerer,
+    function: &ast::FunctionDefinition,
+) -> HashMap<usize, Type> {
+    let mut inference = Inference {
+        lowerer,
+        bindings: Vec::new(),
+        kinds: Vec::new(),
+        scopes: vec![HashMap::new()],
+        variables: Vec::new(),
+    };
+    for parameter in &function.parameters.parameters {
+        let term = inference.known(lowerer.lower_type(&parameter.parameter_type).ok());
+        inference.declare(&parameter.name, term);
+    }
+    let return_type = match super::lower_return_type(&function.return_type, lowerer.names) {
+        Ok(Type::Unit) => None,
+        ty => Some(inference.known(ty.ok())),
+    };
+    inference.infer_block(&function.body, return_type.as_ref());
+    inference
+        .variables
+        .iter()
+        .filter_map(|(index, term)| Some((*index, inference.resolve(term)?)))
+        .filter(|(_, ty)| !matches!(ty, Type::Unit | Type::Tuple(_)))
+        .collect()
+}
+
+struct Inference<'l, 'a> {
+    lowerer: &'l FunctionLowerer<'a>,
+
+    /// Term each type variable is bound to, if any.
+    bindings: Vec<Option<Term>>,
+
+    /// Kind of each type variable.
+    kinds: Vec<Kind>,
+
+    /// Types of the variables visible by name, from the outermost to the innermost block.
+    scopes: Vec<HashMap<String, Term>>,
+
+    /// Index of the name and type of each variable defined without a type.
+    variables: Vec<(usize, Term)>,
+}
+
+impl Inference<'_, '_> {
+    fn fresh(&mut self, kind: Kind) -> Term {
+        self.bindings.push(None);
+        self.kinds.push(kind);
+        Term::Variable(self.bindings.len() - 1)
+    }
+
+    /// Term of a type, or a new type variable if the type is unknown.
+    fn known(&mut self, ty: Option<Type>) -> Term {
+        match ty {
+            Some(ty) => Term::Type(ty),
+            None => self.fresh(Kind::Any),
+        }
+    }
+
+    fn declare(&mut self, name: &ast::Identifier, term: Term) {
+        self.scopes
+            .last_mut()
+            .expect("expected at least one scope")
+            .insert(name.name.clone(), term);
+    }
+
+    fn lookup(&mut self, name: &ast::Identifier) -> Term {
+        let term = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&name.name).cloned());
+        let global = || {
+            let (_, ty) = self.lowerer.globals.get(name.name.as_str())?;
+            Some(Term::Type(ty.clone()))
+        };
+        match term.or_else(global) {
+            Some(term) => term,
+            None => self.fresh(Kind::Any),
+        }
+    }
+
+    /// Follow the bindings of a type variable to the term it stands for.
+    fn find(&self, term: &Term) -> Term {
+        let mut term = term;
+        while let Term::Variable(variable) = term {
+            match &self.bindings[*variable] {
+                Some(bound) => term = bound,
+                None => break,
+            }
+        }
+        term.clone()
+    }
+
+    /// Type a term stands for, with the type variables nothing decides set to their default.
+    fn resolve(&self, term: &Term) -> Option<Type> {
+        match self.find(term) {
+            Term::Type(ty) => Some(ty),
+            Term::Variable(variable) => self.kinds[variable].default_type(),
+            Term::Array { element, length } => self.resolve(&element).map(|element| Type::Array {
+                element: Box::new(element),
+                length,
+            }),
+        }
+    }
+
+    /// Whether the type variable appears in the term.
+    fn occurs(&self, variable: usize, term: &Term) -> bool {
+        match self.find(term) {
+            Term::Type(_) => false,
+            Term::Variable(other) => other == variable,
+            Term::Array { element, .. } => self.occurs(variable, &element),
+        }
+    }
+
+    /// Make both terms stand for the same type, if they can.
+    ///
+    /// Array lengths are not unified, since arrays of a known length can be used as arrays of any
+    /// length.
+    fn unify(&mut self, a: &Term, b: &Term) {
+        match (self.find(a), self.find(b)) {
+            (Term::Variable(a), Term::Variable(b)) => {
+                if a != b {
+                    self.kinds[b] = self.kinds[a].intersect(self.kinds[b]);
+                    self.bindings[a] = Some(Term::Variable(b));
+                }
+            }
+            (Term::Variable(variable), term) | (term, Term::Variable(variable)) => {
+                let admitted = match &term {
+                    Term::Type(ty) => self.kinds[variable].admits(ty),
+                    _ => self.kinds[variable] == Kind::Any,
+                };
+                if admitted && !self.occurs(variable, &term) {
+                    self.bindings[variable] = Some(term);
+                }
+            }
+            (Term::Array { element: a, .. }, Term::Array { element: b, .. }) => {
+                self.unify(&a, &b);
+            }
+            (Term::Array { element, .. }, Term::Type(Type::Array { element: ty, .. }))
+            | (Term::Type(Type::Array { element: ty, .. }), Term::Array { element, .. }) => {
+                self.unify(&element, &Term::Type(*ty));
+            }
+            (Term::Type(_), _) | (Term::Array { .. }, _) => {}
+        }
+    }
+
+    /// Term of the elements of an array.
+    fn element(&mut self, array: &Term) -> Term {
+        match self.find(array) {
+            Term::Array { element, .. } => *element,
+            Term::Type(ty) => self.known(element_type(ty)),
+            Term::Variable(_) => self.fresh(Kind::Any),
+        }
+    }
+
+    /// Term of a field of a struct.
+    fn field(&mut self, object: &Term, name: &ast::Identifier) -> Term {
+        let ty = match self.find(object) {
+            Term::Type(ty) => self
+                .lowerer
+                .field(&ty, name)
+                .ok()
+                .map(|field| field.ty.clone()),
+            _ => None,
+        };
+        self.known(ty)
+    }
+
+    fn infer_block(&mut self, block: &ast::Block, expected: Option<&Term>) {
+        self.scopes.push(HashMap::new());
+        for statement in &block.statements.statements {
+            match statement {
+                ast::Statement::VariableDefinition(variable) => {
+                    let value = variable
+                        .value
+                        .as_ref()
+                        .map(|value| self.infer_expression(value));
+                    let term = match &variable.variable_type {
+                        Some(ty) => {
+                            let ty = self.lowerer.lower_type(ty).ok();
+                            self.known(ty)
+                        }
+                        None => {
+                            let term = match &value {
+                                Some(value) => value.clone(),
+                                None => self.fresh(Kind::Any),
+                            };
+                            self.variables
+                                .push((variable.name.location.start.index, term.clone()));
+                            term
+                        }
+                    };
+                    if let Some(value) = &value {
+                        self.unify(&term, value);
+                    }
+                    self.declare(&variable.name, term);
+                }
+                ast::Statement::DestructuringDefinition(definition) => {
+                    let values = self.infer_tuple(&definition.value);
+                    for (index, name) in definition.names.iter().enumerate() {
+                        let term = match values.get(index) {
+                            Some(value) => value.clone(),
+                            None => self.fresh(Kind::Any),
+                        };
+                        self.variables
+                            .push((name.location.start.index, term.clone()));
+                        self.declare(name, term);
+                    }
+                }
+                ast::Statement::IfStatement(if_statement) => {
+                    self.infer_condition(&if_statement.condition);
+                    self.infer_block(&if_statement.then_block, None);
+                    if let Some(else_block) = &if_statement.else_block {
+                        self.infer_block(else_block, None);
+                    }
+                }
+                ast::Statement::ExpressionStatement(statement) => {
+                    self.infer_expression(&statement.expression);
+                }
+                // the values of a tuple returned by the function have the types of its return type
+                ast::Statement::Expression(expression @ ast::Expression::TupleLiteral(_)) => {
+                    let values = self.infer_tuple(expression);
+                    let types = match expected.map(|expected| self.find(expected)) {
+                        Some(Term::Type(Type::Tuple(types))) => types,
+                        _ => Vec::new(),
+                    };
+                    for (value, ty) in values.iter().zip(types) {
+                        self.unify(value, &Term::Type(ty));
+                    }
+                }
+                ast::Statement::Expression(expression) => {
+                    let term = self.infer_expression(expression);
+                    if let Some(expected) = expected {
+                        self.unify(expected, &term);
+                    }
+                }
+            }
+        }
+        self.scopes.pop();
+    }
+
+    /// Terms of the values of a tuple, which are unknown unless the expression is a tuple literal
+    /// or a call to a function returning a tuple.
+    fn infer_tuple(&mut self, expression: &ast::Expression) -> Vec<Term> {
+        match expression {
+            ast::Expression::TupleLiteral(literal) => literal
+                .elements
+                .iter()
+                .map(|element| self.infer_expression(element))
+                .collect(),
+            _ => match self.infer_expression(expression) {
+                Term::Type(Type::Tuple(types)) => types.into_iter().map(Term::Type).collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    fn infer_condition(&mut self, expression: &ast::Expression) {
+        let term = self.infer_expression(expression);
+        self.unify(&term, &Term::Type(Type::Bool));
+    }
+
+    /// Infer the term of an expression, unifying the terms of its parts along the way.
+    fn infer_expression(&mut self, expression: &ast::Expression) -> Term {
+        match expression {
+            ast::Expression::BinaryExpression(expr) => match expr.operator.operator {
+                ast::OperatorKind::LogicalAnd | ast::OperatorKind::LogicalOr => {
+                    self.infer_condition(&expr.left);
+                    self.infer_condition(&expr.right);
+                    Term::Type(Type::Bool)
+                }
+                ast::OperatorKind::LessThan
+                | ast::OperatorKind::LessThanOrEqual
+                | ast::OperatorKind::GreaterThan
+                | ast::OperatorKind::GreaterThanOrEqual
+                | ast::OperatorKind::Equal
+                | ast::OperatorKind::NotEqual => {
+                    let left = self.infer_expression(&expr.left);
+                    let right = self.infer_expression(&expr.right);
+                    self.unify(&left, &right);
+                    Term::Type(Type::Bool)
+                }
+                _ => {
+                    let left = self.infer_expression(&expr.left);
+                    let right = self.infer_expression(&expr.right);
+                    self.unify(&left, &right);
+                    left
+                }
+            },
+            ast::Expression::UnaryExpression(expr) => {
+                self.infer_condition(&expr.operand);
+                Term::Type(Type::Bool)
+            }
+            ast::Expression::AssignmentExpression(ast::AssignmentExpression {
+                name,
+                value,
+                ..
+            })
+            | ast::Expression::CompoundAssignmentExpression(ast::CompoundAssignmentExpression {
+                name,
+                value,
+                ..
+            }) => {
+                let variable = self.lookup(name);
+                let value = self.infer_expression(value);
+                self.unify(&variable, &value);
+                variable
+            }
+            ast::Expression::IndexAssignmentExpression(expr) => {
+                let array = self.infer_expression(&expr.array);
+                let element = self.element(&array);
+                let index = self.infer_expression(&expr.index);
+                self.unify(&index, &Term::Type(Type::I32));
+                let value = self.infer_expression(&expr.value);
+                self.unify(&element, &value);
+                element
+            }
+            ast::Expression::FieldAssignmentExpression(expr) => {
+                let object = self.infer_expression(&expr.object);
+                let field = self.field(&object, &expr.field);
+                let value = self.infer_expression(&expr.value);
+                self.unify(&field, &value);
+                field
+            }
+            ast::Expression::Block(block) => {
+                let term = self.fresh(Kind::Any);
+                self.infer_block(block, Some(&term));
+                term
+            }
+            ast::Expression::IfElseExpression(expr) => {
+                self.infer_condition(&expr.condition);
+                let ty = self.lowerer.lower_type(&expr.return_type).ok();
+                let term = self.known(ty);
+                self.infer_block(&expr.then_block, Some(&term));
+                self.infer_block(&expr.else_block, Some(&term));
+                term
+            }
+            ast::Expression::CastExpression(expr) => {
+                self.infer_expression(&expr.expression);
+                let ty = self.lowerer.lower_type(&expr.target_type).ok();
+                self.known(ty)
+            }
+            ast::Expression::Identifier(identifier) => self.lookup(identifier),
+            ast::Expression::IntegerLiteral(_) => self.fresh(Kind::Numeric),
+            ast::Expression::FloatLiteral(_) => self.fresh(Kind::Float),
+            ast::Expression::BooleanLiteral(_) => Term::Type(Type::Bool),
+            ast::Expression::FunctionCall(call) => {
+                let arguments = call
+                    .arguments
+                    .iter()
+                    .map(|argument| self.infer_expression(argument))
+                    .collect::<Vec<_>>();
+                if self.lowerer.is_len(call) {
+                    return Term::Type(Type::I32);
+                }
+                let signature = self
+                    .lowerer
+                    .signature(&call.name)
+                    .ok()
+                    .filter(|signature| signature.parameters.len() == arguments.len());
+                let Some(signature) = signature else {
+                    return self.fresh(Kind::Any);
+                };
+                for (argument, parameter) in arguments.iter().zip(&signature.parameters) {
+                    self.unify(argument, &Term::Type(parameter.clone()));
+                }
+                Term::Type(signature.return_type.clone())
+            }
+            ast::Expression::ArrayLiteral(literal) => {
+                let element = self.fresh(Kind::Any);
+                for value in &literal.elements {
+                    let value = self.infer_expression(value);
+                    self.unify(&element, &value);
+                }
+                Term::Array {
+                    element: Box::new(element),
+                    length: Some(literal.elements.len() as u32),
+                }
+            }
+            ast::Expression::ArrayRepeat(repeat) => {
+                let element = self.infer_expression(&repeat.value);
+                let length = self.infer_expression(&repeat.length);
+                self.unify(&length, &Term::Type(Type::I32));
+                Term::Array {
+                    element: Box::new(element),
+                    length: match repeat.length.as_ref() {
+                        ast::Expression::IntegerLiteral(length) => super::array_length(length),
+                        _ => None,
+                    },
+                }
+            }
+            ast::Expression::IndexExpression(expr) => {
+                let array = self.infer_expression(&expr.array);
+                let index = self.infer_expression(&expr.index);
+                self.unify(&index, &Term::Type(Type::I32));
+                self.element(&array)
+            }
+            ast::Expression::StructLiteral(literal) => {
+                let ty = Type::Struct(literal.name.name.clone());
+                for initializer in &literal.fields {
+                    let value = self.infer_expression(&initializer.value);
+                    let field = self.field(&Term::Type(ty.clone()), &initializer.name);
+                    self.unify(&field, &value);
+                }
+                Term::Type(ty)
+            }
+            // tuples are only returned or destructured, which infer the terms of their values
+            ast::Expression::TupleLiteral(literal) => {
+                for element in &literal.elements {
+                    self.infer_expression(element);
+                }
+                self.fresh(Kind::Any)
+            }
+            ast::Expression::FieldAccess(access) => {
+                let object = self.infer_expression(&access.object);
+                self.field(&object, &access.field)
+            }
+            ast::Expression::EnumVariant(variant) => {
+                let arguments = variant
+                    .arguments
+                    .iter()
+                    .map(|argument| self.infer_expression(argument))
+                    .collect::<Vec<_>>();
+                let Ok((_, _, layout)) = self.lowerer.variant(&variant.enum_name, &variant.variant)
+                else {
+                    return self.fresh(Kind::Any);
+                };
+                for (argument, field) in arguments.iter().zip(&layout.fields.fields) {
+                    self.unify(argument, &Term::Type(field.ty.clone()));
+                }
+                Term::Type(Type::Enum(variant.enum_name.name.clone()))
+            }
+            ast::Expression::MatchExpression(expr) => {
+                let value = self.infer_expression(&expr.value);
+                let result = self.fresh(Kind::Any);
+                for arm in &expr.arms {
+                    self.scopes.push(HashMap::new());
+                    match &arm.pattern {
+                        ast::Pattern::Binding(name) => self.declare(name, value.clone()),
+                        ast::Pattern::Variant(pattern) => {
+                            let fields = self
+                                .lowerer
+                                .variant(&pattern.enum_name, &pattern.variant)
+                                .map(|(_, _, layout)| &layout.fields.fields[..])
+                                .unwrap_or_default();
+                            for (index, field_pattern) in pattern.fields.iter().enumerate() {
+                                if let ast::Pattern::Binding(name) = field_pattern {
+                                    let ty = fields.get(index).map(|field| field.ty.clone());
+                                    let term = self.known(ty);
+                                    self.declare(name, term);
+                                }
+                            }
+                        }
+                        ast::Pattern::Wildcard(_)
+                        | ast::Pattern::IntegerLiteral(_)
+                        | ast::Pattern::Range(_) => {}
+                    }
+                    let term = self.infer_expression(&arm.expression);
+                    self.unify(&result, &term);
+                    self.scopes.pop();
+                }
+                result
+            }
+        }
+    }
+}
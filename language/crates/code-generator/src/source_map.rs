@@ -0,0 +1,183 @@
+//! Mapping from code offsets of the generated functions back to positions in the Shuiro source.
+//!
+//! The map is stored in the [`SECTION_NAME`] custom section of the main module. It is encoded as
+//! unsigned LEB128 integers: the number of functions, then for each function its index in the
+//! module, the number of entries and the entries themselves as `offset line column`. Offsets are
+//! relative to the start of the function body, and an entry applies until the next one. The first
+//! entry of a function is at offset 0, at the definition of the function.
+
+use anyhow::{bail, Result};
+
+pub const SECTION_NAME: &str = "shuiro:source-map";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    pub functions: Vec<FunctionSourceMap>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionSourceMap {
+    /// Index of the function in the function index space of the module, including imports.
+    pub function_index: u32,
+
+    /// Entries sorted by offset.
+    pub entries: Vec<SourceMapEntry>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceMapEntry {
+    pub offset: u32,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl SourceMap {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        write_u32(&mut data, self.functions.len() as u32);
+        for function in &self.functions {
+            write_u32(&mut data, function.function_index);
+            write_u32(&mut data, function.entries.len() as u32);
+            for entry in &function.entries {
+                write_u32(&mut data, entry.offset);
+                write_u32(&mut data, entry.line);
+                write_u32(&mut data, entry.column);
+            }
+        }
+        data
+    }
+
+    pub fn decode(mut data: &[u8]) -> Result<Self> {
+        let data = &mut data;
+        let function_count = read_u32(data)?;
+        let mut functions = Vec::with_capacity(function_count as usize);
+        for _ in 0..function_count {
+            let function_index = read_u32(data)?;
+            let entry_count = read_u32(data)?;
+            let mut entries = Vec::with_capacity(entry_count as usize);
+            for _ in 0..entry_count {
+                entries.push(SourceMapEntry {
+                    offset: read_u32(data)?,
+                    line: read_u32(data)?,
+                    column: read_u32(data)?,
+                });
+            }
+            functions.push(FunctionSourceMap {
+                function_index,
+                entries,
+            });
+        }
+        if !data.is_empty() {
+            bail!("unexpected trailing bytes in the source map");
+        }
+        Ok(Self { functions })
+    }
+
+    /// Read the source map from the custom section of a module, or of a module nested in a
+    /// component.
+    pub fn find(wasm: &[u8]) -> Result<Option<Self>> {
+        for payload in wasmparser::Parser::new(0).parse_all(wasm) {
+            if let wasmparser::Payload::CustomSection(reader) = payload? {
+                if reader.name() == SECTION_NAME {
+                    return Self::decode(reader.data()).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find the source position of the instruction at `offset` in the body of the function.
+    pub fn lookup(&self, function_index: u32, offset: u32) -> Option<SourceMapEntry> {
+        let function = self
+            .functions
+            .iter()
+            .find(|function| function.function_index == function_index)?;
+        function
+            .entries
+            .iter()
+            .take_while(|entry| entry.offset <= offset)
+            .last()
+            .copied()
+    }
+}
+
+fn write_u32(data: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            data.push(byte);
+            return;
+        }
+        data.push(byte | 0x80);
+    }
+}
+
+fn read_u32(data: &mut &[u8]) -> Result<u32> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let Some((&byte, rest)) = data.split_first() else {
+            bail!("unexpected end of the source map");
+        };
+        *data = rest;
+        value |= ((byte & 0x7f) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    bail!("integer too large in the source map")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn decode_returns_encoded_source_map() {
+        let source_map = SourceMap {
+            functions: vec![FunctionSourceMap {
+                function_index: 3,
+                entries: vec![
+                    SourceMapEntry {
+                        offset: 1,
+                        line: 2,
+                        column: 5,
+                    },
+                    SourceMapEntry {
+                        offset: 200,
+                        line: 300,
+                        column: 13,
+                    },
+                ],
+            }],
+        };
+        assert_eq!(SourceMap::decode(&source_map.encode()).unwrap(), source_map);
+    }
+
+    #[test]
+    fn lookup_returns_last_entry_before_offset() {
+        let source_map = SourceMap {
+            functions: vec![FunctionSourceMap {
+                function_index: 3,
+                entries: vec![
+                    SourceMapEntry {
+                        offset: 1,
+                        line: 2,
+                        column: 5,
+                    },
+                    SourceMapEntry {
+                        offset: 4,
+                        line: 3,
+                        column: 5,
+                    },
+                ],
+            }],
+        };
+        assert_eq!(source_map.lookup(3, 0), None);
+        assert_eq!(source_map.lookup(3, 3).map(|entry| entry.line), Some(2));
+        assert_eq!(source_map.lookup(3, 4).map(|entry| entry.line), Some(3));
+        assert_eq!(source_map.lookup(3, 10).map(|entry| entry.line), Some(3));
+        assert_eq!(source_map.lookup(4, 10), None);
+    }
+}
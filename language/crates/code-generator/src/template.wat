@@ -1,11 +1,22 @@
 (component
   ;; environment
   (core module $Env
-    (memory $memory 17)
+    ;; the reserved memory, the input buffer and the first segment of the shadow stack
+    (memory $memory 2)
     (export "memory" (memory $memory))
+
+    ;;; Allocates the lists returned by the host.
+    ;;;
+    ;;; The only list is the input read by `$Std`, which is always placed in its input buffer at
+    ;;; 1024..5120 and consumed before the next read.
+    (func $realloc (export "realloc")
+      (param $ptr i32) (param $old_size i32) (param $align i32) (param $new_size i32) (result i32)
+      (i32.const 1024)
+    )
   )
   (core instance $core|env (instantiate $Env))
   (alias core export $core|env "memory" (core memory $memory))
+  (alias core export $core|env "realloc" (core func $realloc))
 
 
   ;; dependencies
@@ -23,6 +34,15 @@
     ))
     (export $stream-error "stream-error" (type (eq $stream-error-type)))
 
+    (export $input-stream "input-stream" (type (sub resource)))
+    (export $input-stream.blocking-read "[method]input-stream.blocking-read"
+      (func
+        (param "self" (borrow $input-stream))
+        (param "len" u64)
+        (result (result (list u8) (error $stream-error)))
+      )
+    )
+
     (export $output-stream "output-stream" (type (sub resource)))
     (export $output-stream.blocking-write-and-flush "[method]output-stream.blocking-write-and-flush"
       (func
@@ -32,15 +52,35 @@
       )
     )
   ))
+  (alias export $wasi:io/streams@0.2.2 "input-stream" (type $input-stream))
   (alias export $wasi:io/streams@0.2.2 "output-stream" (type $output-stream))
+  (core func $core|input-stream.blocking-read (canon lower
+    (func $wasi:io/streams@0.2.2 "[method]input-stream.blocking-read")
+    (memory $memory)
+    (realloc $realloc)
+  ))
   (core func $core|output-stream.blocking-write-and-flush (canon lower
     (func $wasi:io/streams@0.2.2 "[method]output-stream.blocking-write-and-flush")
     (memory $memory)
   ))
   (core instance $core|wasi:io/streams@0.2.2
+    (export "[method]input-stream.blocking-read" (func $core|input-stream.blocking-read))
     (export "[method]output-stream.blocking-write-and-flush" (func $core|output-stream.blocking-write-and-flush))
   )
 
+  (import "wasi:cli/stdin@0.2.2" (instance $wasi:cli/stdin@0.2.2
+    (alias outer 1 $input-stream (type $input-stream))
+    (export $get-stdin "get-stdin"
+      (func (result (own $input-stream)))
+    )
+  ))
+  (core func $core|get-stdin (canon lower
+    (func $wasi:cli/stdin@0.2.2 "get-stdin")
+  ))
+  (core instance $core|wasi:cli/stdin@0.2.2
+    (export "get-stdin" (func $core|get-stdin))
+  )
+
   (import "wasi:cli/stdout@0.2.2" (instance $wasi:cli/stdout@0.2.2
     (alias outer 1 $output-stream (type $output-stream))
     (export $get-stdout "get-stdout"
@@ -59,14 +99,476 @@
   (core module $Std
     (import "env" "memory" (memory $memory 0))
 
+    (import "wasi:cli/stdin@0.2.2" "get-stdin"
+      (func $get-stdin (result i32))
+    )
     (import "wasi:cli/stdout@0.2.2" "get-stdout"
       (func $get-stdout (result i32))
     )
+    (import "wasi:io/streams@0.2.2" "[method]input-stream.blocking-read"
+      (func $input-stream.blocking-read (param i32 i64 i32))
+    )
     (import "wasi:io/streams@0.2.2" "[method]output-stream.blocking-write-and-flush"
       (func $output-stream.blocking-write-and-flush (param i32 i32 i32 i32))
     )
 
-    (global $stack_pointer (mut i32) (i32.const 0))
+    ;; memory layout
+    ;;
+    ;; - 0..1024:          reserved, so that no allocation is at the null pointer
+    ;; - 1024..5120:       input buffer, see `$realloc` in `$Env`
+    ;; - 65536..131072:    first segment of the shadow stack
+    ;; - 131072..:         heap, growing with `memory.grow` from $heap_end
+
+    ;; the shadow stack grows upwards from $stack_pointer in the segment $stack_start..$stack_end;
+    ;; when it is full, the stack continues in a new segment allocated on the heap, whose first 8
+    ;; bytes hold the start and the end of the previous segment
+    (global $stack_pointer (mut i32) (i32.const 65536))
+    (global $stack_start (mut i32) (i32.const 65536))
+    (global $stack_end (mut i32) (i32.const 131072))
+
+    ;; blocks of the heap have an 8 bytes header holding the size of the block; free blocks hold
+    ;; the next free block in their first 4 bytes
+    (global $heap_end (mut i32) (i32.const 131072))
+    (global $free_list (mut i32) (i32.const 0))
+
+    ;; input is read from stdin into the input buffer; $input_position..$input_end are the bytes
+    ;; not consumed yet
+    (global $stdin (mut i32) (i32.const -1))
+    (global $input_position (mut i32) (i32.const 0))
+    (global $input_end (mut i32) (i32.const 0))
+    (global $input_closed (mut i32) (i32.const 0))
+
+    ;;; Allocates memory on the shadow stack, continuing the stack in a new segment of at least
+    ;;; 64 KiB when the current one is full.
+    ;;;
+    ;;; # Parameters
+    ;;; - $size: i32 - The number of bytes to allocate, rounded up to a multiple of 8.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the memory, to be passed to `$stack_restore` to free it and
+    ;;;   everything allocated after it. Traps when the memory cannot grow.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (local.set $ptr (call $stack_alloc (i32.const 24)))
+    ;;; ;; use $memory[$ptr..($ptr + 24)]
+    ;;; (call $stack_restore (local.get $ptr))
+    ;;; ```
+    (func $stack_alloc (export "stack_alloc") (param $size i32) (result i32)
+      (local $ptr i32)
+      (local $segment i32)
+      (local $segment_size i32)
+
+      ;; if $size > $stack_end - $stack_pointer {
+      ;;   $segment_size = max(align($size + 8, 8), 65536);
+      ;;   $segment = alloc($segment_size);
+      ;;   $memory[$segment] = $stack_start;
+      ;;   $memory[$segment + 4] = $stack_end;
+      ;;   $stack_start = $segment;
+      ;;   $stack_pointer = $segment + 8;
+      ;;   $stack_end = $segment + $segment_size;
+      ;; }
+      (if (i32.gt_u
+            (local.get $size)
+            (i32.sub (global.get $stack_end) (global.get $stack_pointer)))
+        (then
+          (if (i32.gt_u (local.get $size) (i32.const 0x7fff0000))
+            (then (call $out_of_memory))
+          )
+          (local.set $segment_size
+            (i32.and (i32.add (local.get $size) (i32.const 15)) (i32.const -8))
+          )
+          (if (i32.lt_u (local.get $segment_size) (i32.const 65536))
+            (then (local.set $segment_size (i32.const 65536)))
+          )
+          (local.set $segment (call $alloc (local.get $segment_size)))
+          (i32.store (local.get $segment) (global.get $stack_start))
+          (i32.store offset=4 (local.get $segment) (global.get $stack_end))
+          (global.set $stack_start (local.get $segment))
+          (global.set $stack_pointer (i32.add (local.get $segment) (i32.const 8)))
+          (global.set $stack_end (i32.add (local.get $segment) (local.get $segment_size)))
+        )
+      )
+      (local.set $ptr (global.get $stack_pointer))
+      (global.set $stack_pointer
+        (i32.and
+          (i32.add (i32.add (local.get $ptr) (local.get $size)) (i32.const 7))
+          (i32.const -8)
+        )
+      )
+      (local.get $ptr)
+    )
+
+    ;;; Frees the memory allocated on the shadow stack since `$ptr` was allocated, including the
+    ;;; segments added after the one holding `$ptr`.
+    ;;;
+    ;;; # Parameters
+    ;;; - $ptr: i32 - The pointer returned by `$stack_alloc`.
+    (func $stack_restore (export "stack_restore") (param $ptr i32)
+      (local $segment i32)
+
+      ;; while $ptr < $stack_start || $ptr > $stack_end {
+      ;;   $segment = $stack_start;
+      ;;   $stack_start = $memory[$segment];
+      ;;   $stack_end = $memory[$segment + 4];
+      ;;   free($segment);
+      ;; }
+      (block $done
+        (loop $pop_segment
+          (br_if $done
+            (i32.and
+              (i32.ge_u (local.get $ptr) (global.get $stack_start))
+              (i32.le_u (local.get $ptr) (global.get $stack_end))))
+          (local.set $segment (global.get $stack_start))
+          (global.set $stack_start (i32.load (local.get $segment)))
+          (global.set $stack_end (i32.load offset=4 (local.get $segment)))
+          (drop (call $free (local.get $segment)))
+          (br $pop_segment)
+        )
+      )
+      (global.set $stack_pointer (local.get $ptr))
+    )
+
+    ;;; Allocates memory on the heap, growing the memory if needed.
+    ;;;
+    ;;; The first free block large enough is reused, otherwise a new block is added at the end of
+    ;;; the heap. Blocks are not split nor merged.
+    ;;;
+    ;;; # Parameters
+    ;;; - $size: i32 - The number of bytes to allocate.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the memory, aligned to 8 bytes. Traps when the memory cannot grow.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (local.set $ptr (call $alloc (i32.const 100)))
+    ;;; ```
+    (func $alloc (export "alloc") (param $size i32) (result i32)
+      (local $block i32)
+      (local $previous i32)
+      (local $end i32)
+      (local $pages i32)
+
+      ;; $size = max(align($size, 8), 8);
+      (if (i32.gt_u (local.get $size) (i32.const 0x7fff0000))
+        (then (call $out_of_memory))
+      )
+      (local.set $size
+        (i32.and (i32.add (local.get $size) (i32.const 7)) (i32.const -8))
+      )
+      (if (i32.eqz (local.get $size))
+        (then (local.set $size (i32.const 8)))
+      )
+
+      ;; // reuse the first free block large enough
+      ;; $block = $free_list;
+      ;; while $block != 0 {
+      ;;   if $memory[$block - 8] >= $size { unlink $block; return $block; }
+      ;;   $previous = $block;
+      ;;   $block = $memory[$block];
+      ;; }
+      (local.set $block (global.get $free_list))
+      (block $not_found
+        (loop $find_free_block
+          (br_if $not_found (i32.eqz (local.get $block)))
+          (if (i32.ge_u
+                (i32.load (i32.sub (local.get $block) (i32.const 8)))
+                (local.get $size))
+            (then
+              (if (i32.eqz (local.get $previous))
+                (then (global.set $free_list (i32.load (local.get $block))))
+                (else (i32.store (local.get $previous) (i32.load (local.get $block))))
+              )
+              (return (local.get $block))
+            )
+          )
+          (local.set $previous (local.get $block))
+          (local.set $block (i32.load (local.get $block)))
+          (br $find_free_block)
+        )
+      )
+
+      ;; // add a block at the end of the heap
+      ;; $block = $heap_end + 8;
+      ;; $end = $block + $size;
+      ;; $pages = ceil($end / 65536) - memory.size;
+      ;; if $pages > 0 && memory.grow($pages) == -1 { trap; }
+      (local.set $block (i32.add (global.get $heap_end) (i32.const 8)))
+      (local.set $end (i32.add (local.get $block) (local.get $size)))
+      (if (i32.lt_u (local.get $end) (local.get $block))
+        (then (call $out_of_memory))
+      )
+      (local.set $pages
+        (i32.sub
+          (i32.add
+            (i32.shr_u (i32.sub (local.get $end) (i32.const 1)) (i32.const 16))
+            (i32.const 1)
+          )
+          (memory.size)
+        )
+      )
+      (if (i32.gt_s (local.get $pages) (i32.const 0))
+        (then
+          (if (i32.eq (memory.grow (local.get $pages)) (i32.const -1))
+            (then (call $out_of_memory))
+          )
+        )
+      )
+      (i32.store (global.get $heap_end) (local.get $size))
+      (global.set $heap_end (local.get $end))
+      (local.get $block)
+    )
+
+    ;;; Frees memory allocated by `$alloc`, so that it can be allocated again.
+    ;;;
+    ;;; # Parameters
+    ;;; - $ptr: i32 - The pointer returned by `$alloc`, or 0 to do nothing.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $free (local.get $ptr))
+    ;;; ```
+    (func $free (export "free") (param $ptr i32) (result i32)
+      (if (local.get $ptr)
+        (then
+          (i32.store (local.get $ptr) (global.get $free_list))
+          (global.set $free_list (local.get $ptr))
+        )
+      )
+      (i32.const 0)
+    )
+
+    ;;; Traps because the memory cannot grow to hold an allocation.
+    (func $out_of_memory (export "out_of_memory")
+      (unreachable)
+    )
+
+    ;;; Traps because an array was indexed out of its bounds.
+    ;;;
+    ;;; This is a separate function so that the runner can tell this trap from others by the
+    ;;; function it happened in.
+    (func $index_out_of_bounds (export "index_out_of_bounds")
+      (unreachable)
+    )
+
+    ;;; Traps because an array was created with a negative or too large length.
+    (func $invalid_array_length (export "invalid_array_length")
+      (unreachable)
+    )
+
+    ;;; Traps because the input ended before the value being read.
+    (func $end_of_input (export "end_of_input")
+      (unreachable)
+    )
+
+    ;;; Traps because the input does not continue with an integer where one is read.
+    (func $invalid_integer (export "invalid_integer")
+      (unreachable)
+    )
+
+    ;;; Traps because an integer read from the input does not fit in its type.
+    (func $integer_out_of_range (export "integer_out_of_range")
+      (unreachable)
+    )
+
+    ;;; Allocates an array on the heap.
+    ;;;
+    ;;; Arrays hold their length in their first 4 bytes, followed by their elements from offset 8.
+    ;;;
+    ;;; # Parameters
+    ;;; - $length: i32 - The number of elements. Traps when it is negative or too large.
+    ;;; - $element_size: i32 - The size of an element in bytes, 4 or 8.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the array, whose elements are not initialized.
+    (func $array_alloc (param $length i32) (param $element_size i32) (result i32)
+      (local $array i32)
+
+      (if (i32.gt_u (local.get $length) (i32.const 0x0fff0000))
+        (then (call $invalid_array_length))
+      )
+      (local.set $array
+        (call $alloc
+          (i32.add (i32.const 8) (i32.mul (local.get $length) (local.get $element_size))))
+      )
+      (i32.store (local.get $array) (local.get $length))
+      (local.get $array)
+    )
+
+    ;;; Creates an array of `i32`s with every element set to `$value`.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: i32 - The value of the elements.
+    ;;; - $length: i32 - The number of elements.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the array.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; ;; [0; 10]
+    ;;; (local.set $array (call $array_new_i32 (i32.const 0) (i32.const 10)))
+    ;;; ```
+    (func $array_new_i32 (export "array_new_i32")
+      (param $value i32) (param $length i32) (result i32)
+      (local $array i32)
+      (local $ptr i32)
+      (local $end i32)
+
+      (local.set $array (call $array_alloc (local.get $length) (i32.const 4)))
+      (local.set $ptr (i32.add (local.get $array) (i32.const 8)))
+      (local.set $end (i32.add (local.get $ptr) (i32.shl (local.get $length) (i32.const 2))))
+      (block $done
+        (loop $fill
+          (br_if $done (i32.ge_u (local.get $ptr) (local.get $end)))
+          (i32.store (local.get $ptr) (local.get $value))
+          (local.set $ptr (i32.add (local.get $ptr) (i32.const 4)))
+          (br $fill)
+        )
+      )
+      (local.get $array)
+    )
+
+    ;;; Creates an array of `i64`s with every element set to `$value`.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: i64 - The value of the elements.
+    ;;; - $length: i32 - The number of elements.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the array.
+    (func $array_new_i64 (export "array_new_i64")
+      (param $value i64) (param $length i32) (result i32)
+      (local $array i32)
+      (local $ptr i32)
+      (local $end i32)
+
+      (local.set $array (call $array_alloc (local.get $length) (i32.const 8)))
+      (local.set $ptr (i32.add (local.get $array) (i32.const 8)))
+      (local.set $end (i32.add (local.get $ptr) (i32.shl (local.get $length) (i32.const 3))))
+      (block $done
+        (loop $fill
+          (br_if $done (i32.ge_u (local.get $ptr) (local.get $end)))
+          (i64.store (local.get $ptr) (local.get $value))
+          (local.set $ptr (i32.add (local.get $ptr) (i32.const 8)))
+          (br $fill)
+        )
+      )
+      (local.get $array)
+    )
+
+    ;;; Computes the address of an element of an array of `i32`s, checking the bounds.
+    ;;;
+    ;;; # Parameters
+    ;;; - $array: i32 - The pointer to the array.
+    ;;; - $index: i32 - The index of the element. Traps when it is negative or not less than the
+    ;;;   length of the array.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The address of the element.
+    (func $array_address_i32 (export "array_address_i32")
+      (param $array i32) (param $index i32) (result i32)
+      (if (i32.ge_u (local.get $index) (i32.load (local.get $array)))
+        (then (call $index_out_of_bounds))
+      )
+      (i32.add
+        (i32.add (local.get $array) (i32.const 8))
+        (i32.shl (local.get $index) (i32.const 2))
+      )
+    )
+
+    ;;; Computes the address of an element of an array of `i64`s, checking the bounds.
+    ;;;
+    ;;; # Parameters
+    ;;; - $array: i32 - The pointer to the array.
+    ;;; - $index: i32 - The index of the element. Traps when it is negative or not less than the
+    ;;;   length of the array.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The address of the element.
+    (func $array_address_i64 (export "array_address_i64")
+      (param $array i32) (param $index i32) (result i32)
+      (if (i32.ge_u (local.get $index) (i32.load (local.get $array)))
+        (then (call $index_out_of_bounds))
+      )
+      (i32.add
+        (i32.add (local.get $array) (i32.const 8))
+        (i32.shl (local.get $index) (i32.const 3))
+      )
+    )
+
+    ;;; Creates an array of `f32`s with every element set to `$value`.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: f32 - The value of the elements.
+    ;;; - $length: i32 - The number of elements.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the array.
+    (func $array_new_f32 (export "array_new_f32")
+      (param $value f32) (param $length i32) (result i32)
+      (call $array_new_i32 (i32.reinterpret_f32 (local.get $value)) (local.get $length))
+    )
+
+    ;;; Creates an array of `f64`s with every element set to `$value`.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: f64 - The value of the elements.
+    ;;; - $length: i32 - The number of elements.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to the array.
+    (func $array_new_f64 (export "array_new_f64")
+      (param $value f64) (param $length i32) (result i32)
+      (call $array_new_i64 (i64.reinterpret_f64 (local.get $value)) (local.get $length))
+    )
+
+    ;;; Computes the address of an element of an array of `f32`s, checking the bounds.
+    ;;;
+    ;;; # Parameters
+    ;;; - $array: i32 - The pointer to the array.
+    ;;; - $index: i32 - The index of the element. Traps when it is negative or not less than the
+    ;;;   length of the array.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The address of the element.
+    (func $array_address_f32 (export "array_address_f32")
+      (param $array i32) (param $index i32) (result i32)
+      (call $array_address_i32 (local.get $array) (local.get $index))
+    )
+
+    ;;; Computes the address of an element of an array of `f64`s, checking the bounds.
+    ;;;
+    ;;; # Parameters
+    ;;; - $array: i32 - The pointer to the array.
+    ;;; - $index: i32 - The index of the element. Traps when it is negative or not less than the
+    ;;;   length of the array.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The address of the element.
+    (func $array_address_f64 (export "array_address_f64")
+      (param $array i32) (param $index i32) (result i32)
+      (call $array_address_i64 (local.get $array) (local.get $index))
+    )
+
+    ;;; Writes bytes to stdout.
+    ;;;
+    ;;; # Parameters
+    ;;; - $ptr: i32 - The pointer to the bytes.
+    ;;; - $length: i32 - The number of bytes.
+    (func $write_stdout (param $ptr i32) (param $length i32)
+      (local $result_ptr i32)
+
+      (local.set $result_ptr (call $stack_alloc (i32.const 16)))
+      (call $output-stream.blocking-write-and-flush
+        (call $get-stdout)
+        (local.get $ptr)
+        (local.get $length)
+        (local.get $result_ptr)
+      )
+      (call $stack_restore (local.get $result_ptr))
+    )
 
     ;;; Prints an integer to stdout.
     ;;;
@@ -142,60 +644,97 @@
       (local.get $length)
     )
 
-    ;;; Prints a character (ASCII code) to stdout.
+    ;;; Converts a 64-bit integer to a string.
     ;;;
     ;;; # Parameters
-    ;;; - $value: i32 - The ASCII code of the character to print.
+    ;;; - $value: i64 - The integer to convert to a string.
+    ;;; - $ptr: i32 - The pointer to the memory location to write the string to.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The length of the string (bytes).
     ;;;
     ;;; # Example
     ;;; ```wat
-    ;;; (call $print_char (i32.const 33)) ;; print_char('!');
+    ;;; ;; $memory[0..10] = "4294967296";
+    ;;; (local.set $length (call $i64_to_string (i64.const 4294967296) (i32.const 0)))
+    ;;; (local.get $length) ;; 10
     ;;; ```
-    (func $print_char (export "print_char") (param $value i32) (result i32)
-      (local $stdout i32)
-      (local $offset i32)
-      (local $result_ptr i32)
+    (func $i64_to_string (export "i64_to_string") (param $value i64) (param $ptr i32) (result i32)
+      (local $tmp i64)         ;; i64    - temporary variable
+      (local $i i32)           ;; i32    - loop variable
+      (local $length i32)      ;; i32    - length of the string
+      (local $is_negative i32) ;; 0 or 1 - 0: positive, 1: negative
 
-      (local.set $stdout (call $get-stdout))
-      (local.set $offset (global.get $stack_pointer))
-
-      ;; $memory[$offset] = $value;
-      (i32.store8 (local.get $offset) (local.get $value))
-
-      ;; // print the string to stdout
-      ;; $result_ptr = $offset + 1 + (4 - (($offset + 1) % 4));
-      ;; $output-stream.blocking-write-and-flush(
-      ;;   $stdout,
-      ;;   $offset,
-      ;;   1,
-      ;;   $result_ptr
-      ;; );
-      (local.set $result_ptr
-        (i32.add
-          (i32.add (local.get $offset) (i32.const 1))
-          (i32.sub
-            (i32.const 4)
-            (i32.rem_u
-              (i32.add (local.get $offset) (i32.const 1))
-              (i32.const 4)
-            )
-          )
+      ;; $is_negative = if $value < 0 { 1 } else { 0 };
+      (local.set $is_negative (i32.const 0))
+      (if (i64.lt_s (local.get $value) (i64.const 0))
+        (then
+          (local.set $is_negative (i32.const 1))
+          (local.set $value (i64.mul (local.get $value) (i64.const -1)))
         )
       )
-      (call $output-stream.blocking-write-and-flush
-        (local.get $stdout)
-        (local.get $offset)
-        (i32.const 1)
-        (local.get $result_ptr)
+
+      ;; $length = $is_negative;
+      ;; $tmp = $value;
+      ;; loop {
+      ;;   $tmp /= 10;
+      ;;   $length += 1;
+      ;;   if $tmp != 0 { continue; } else { break; }
+      ;; }
+      (local.set $length (local.get $is_negative))
+      (local.set $tmp (local.get $value))
+      (loop $count_digits
+        (local.set $tmp (i64.div_u (local.get $tmp) (i64.const 10)))
+        (local.set $length (i32.add (local.get $length) (i32.const 1)))
+        (br_if $count_digits (i64.ne (local.get $tmp) (i64.const 0)))
       )
 
-      ;; // cleanup
-      ;; $memory[$offset..($result_ptr + 3)] = null;
-      (memory.fill
-        (local.get $offset)
-        (i32.const 0)
-        (i32.sub (i32.add (local.get $result_ptr) (i32.const 3)) (local.get $offset))
+      ;; $tmp = $value
+      ;; $i = $length - 1
+      ;; loop {
+      ;;   $memory[$ptr + $i] = ($tmp % 10) + '0';
+      ;;   $tmp /= 10;
+      ;;   $i -= 1;
+      ;;   if $i >= $is_negative { continue; } else { break; }
+      ;; }
+      ;; if $is_negative { $memory[$ptr] = '-'; }
+      (local.set $tmp (local.get $value))
+      (local.set $i (i32.sub (local.get $length) (i32.const 1)))
+      (loop $write_digits
+        (i64.store8
+          (i32.add (local.get $ptr) (local.get $i))
+          (i64.add (i64.rem_u (local.get $tmp) (i64.const 10)) (i64.const 48))
+        )
+        (local.set $tmp (i64.div_u (local.get $tmp) (i64.const 10)))
+        (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+        (br_if $write_digits (i32.ge_s (local.get $i) (local.get $is_negative)))
       )
+      (if (i32.eq (local.get $is_negative) (i32.const 1))
+        (then
+          (i32.store8 (local.get $ptr) (i32.const 45))
+        )
+      )
+
+      (local.get $length)
+    )
+
+    ;;; Prints a character (ASCII code) to stdout.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: i32 - The ASCII code of the character to print.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $print_char (i32.const 33)) ;; print_char('!');
+    ;;; ```
+    (func $print_char (export "print_char") (param $value i32) (result i32)
+      (local $ptr i32)
+
+      ;; $memory[$ptr] = $value;
+      (local.set $ptr (call $stack_alloc (i32.const 1)))
+      (i32.store8 (local.get $ptr) (local.get $value))
+      (call $write_stdout (local.get $ptr) (i32.const 1))
+      (call $stack_restore (local.get $ptr))
       (i32.const 0)
     )
 
@@ -209,68 +748,571 @@
     ;;; (call $print_int (i32.const 123)) ;; print_int(123);
     ;;; ```
     (func $print_int (export "print_int") (param $value i32) (result i32)
-      (local $stdout i32)
-      (local $offset i32)
+      (local $ptr i32)
+
+      ;; an i32 has at most 11 characters
+      (local.set $ptr (call $stack_alloc (i32.const 11)))
+      (call $write_stdout
+        (local.get $ptr)
+        (call $int_to_string (local.get $value) (local.get $ptr))
+      )
+      (call $stack_restore (local.get $ptr))
+      (i32.const 0)
+    )
+
+    ;;; Prints a 64-bit integer to stdout.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: i64 - The integer to print.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $print_i64 (i64.const 4294967296)) ;; print_i64(4294967296);
+    ;;; ```
+    (func $print_i64 (export "print_i64") (param $value i64) (result i32)
+      (local $ptr i32)
+
+      ;; an i64 has at most 20 characters
+      (local.set $ptr (call $stack_alloc (i32.const 20)))
+      (call $write_stdout
+        (local.get $ptr)
+        (call $i64_to_string (local.get $value) (local.get $ptr))
+      )
+      (call $stack_restore (local.get $ptr))
+      (i32.const 0)
+    )
+
+    ;;; Prints a 64-bit float to stdout, with 6 decimals.
+    ;;;
+    ;;; The integer part is printed exactly, with all its digits, like `%f` in C.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: f64 - The float to print.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $print_f64 (f64.const -2.5)) ;; print_f64(-2.5); prints -2.500000
+    ;;; ```
+    (func $print_f64 (export "print_f64") (param $value f64) (result i32)
+      (local $ptr i32)
+      (local $integer f64)
+      (local $fraction i32)
+
+      ;; an i64 has at most 20 characters
+      (local.set $ptr (call $stack_alloc (i32.const 20)))
+      (block $done
+        ;; only NaN is not equal to itself
+        (if (f64.ne (local.get $value) (local.get $value))
+          (then
+            ;; "NaN"
+            (i32.store (local.get $ptr) (i32.const 0x4e614e))
+            (call $write_stdout (local.get $ptr) (i32.const 3))
+            (br $done)
+          )
+        )
+        ;; the sign bit is checked to also print -0
+        (if (i64.lt_s (i64.reinterpret_f64 (local.get $value)) (i64.const 0))
+          (then
+            (i32.store8 (local.get $ptr) (i32.const 0x2d))
+            (call $write_stdout (local.get $ptr) (i32.const 1))
+            (local.set $value (f64.neg (local.get $value)))
+          )
+        )
+        (if (f64.eq (local.get $value) (f64.const inf))
+          (then
+            ;; "inf"
+            (i32.store (local.get $ptr) (i32.const 0x666e69))
+            (call $write_stdout (local.get $ptr) (i32.const 3))
+            (br $done)
+          )
+        )
+
+        (local.set $integer (f64.floor (local.get $value)))
+        (local.set $fraction
+          (i32.trunc_f64_u
+            (f64.nearest
+              (f64.mul
+                (f64.sub (local.get $value) (local.get $integer))
+                (f64.const 1000000)
+              )
+            )
+          )
+        )
+        ;; the fraction can round up to the next integer
+        (if (i32.ge_u (local.get $fraction) (i32.const 1000000))
+          (then
+            (local.set $fraction (i32.sub (local.get $fraction) (i32.const 1000000)))
+            (local.set $integer (f64.add (local.get $integer) (f64.const 1)))
+          )
+        )
+
+        ;; the integer part is an integer exactly, which fits in an i64 below 2^63
+        (if (f64.lt (local.get $integer) (f64.const 0x1p63))
+          (then
+            (call $write_stdout
+              (local.get $ptr)
+              (call $i64_to_string (i64.trunc_f64_s (local.get $integer)) (local.get $ptr))
+            )
+          )
+          (else (call $write_large_integer (local.get $integer)))
+        )
+
+        ;; the leading 1 keeps the leading zeros of the fraction and is replaced by the point
+        (drop
+          (call $int_to_string
+            (i32.add (local.get $fraction) (i32.const 1000000))
+            (local.get $ptr)
+          )
+        )
+        (i32.store8 (local.get $ptr) (i32.const 0x2e))
+        (call $write_stdout (local.get $ptr) (i32.const 7))
+      )
+      (call $stack_restore (local.get $ptr))
+      (i32.const 0)
+    )
+
+    ;;; Writes an integer held by a float of at least 2^63 to stdout, with all its digits.
+    ;;;
+    ;;; The float is its mantissa times 2 to the power of its exponent: the decimal digits of the
+    ;;; mantissa are doubled once for each power of 2, one digit per byte from the least
+    ;;; significant.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: f64 - The integer, at least 2^63 and finite.
+    (func $write_large_integer (param $value f64)
+      (local $bits i64)
+      (local $mantissa i64)
+      (local $exponent i32)
+      (local $digits i32)
+      (local $text i32)
       (local $length i32)
-      (local $result_ptr i32)
+      (local $i i32)
+      (local $digit i32)
+      (local $carry i32)
+
+      ;; a float has at most 309 integer digits
+      (local.set $digits (call $stack_alloc (i32.const 320)))
+      (local.set $text (call $stack_alloc (i32.const 320)))
+
+      ;; $value = $mantissa * 2^$exponent, where $exponent > 0
+      (local.set $bits (i64.reinterpret_f64 (local.get $value)))
+      (local.set $mantissa
+        (i64.or
+          (i64.and (local.get $bits) (i64.const 0xfffffffffffff))
+          (i64.const 0x10000000000000)
+        )
+      )
+      (local.set $exponent
+        (i32.sub
+          (i32.wrap_i64 (i64.shr_u (local.get $bits) (i64.const 52)))
+          (i32.const 1075)
+        )
+      )
 
-      (local.set $stdout (call $get-stdout))
-      (local.set $offset (global.get $stack_pointer))
-      (local.set $length (call $int_to_string (local.get $value) (local.get $offset)))
-
-      ;; // print the string to stdout
-      ;; $result_ptr = $offset + $length + (4 - (($offset + $length) % 4));
-      ;; $output-stream.blocking-write-and-flush(
-      ;;   $stdout,
-      ;;   $offset,
-      ;;   $length,
-      ;;   $result_ptr
-      ;; );
-      (local.set $result_ptr
-        (i32.add
-          (i32.add (local.get $offset) (local.get $length))
-          (i32.sub
-            (i32.const 4)
-            (i32.rem_u
-              (i32.add (local.get $offset) (local.get $length))
-              (i32.const 4)
+      ;; do {
+      ;;   $memory[$digits + $length] = $mantissa % 10;
+      ;;   $length += 1;
+      ;;   $mantissa /= 10;
+      ;; } while $mantissa != 0;
+      (loop $mantissa_digits
+        (i32.store8
+          (i32.add (local.get $digits) (local.get $length))
+          (i32.wrap_i64 (i64.rem_u (local.get $mantissa) (i64.const 10)))
+        )
+        (local.set $length (i32.add (local.get $length) (i32.const 1)))
+        (local.set $mantissa (i64.div_u (local.get $mantissa) (i64.const 10)))
+        (br_if $mantissa_digits (i64.ne (local.get $mantissa) (i64.const 0)))
+      )
+
+      ;; for _ in 0..$exponent {
+      ;;   $carry = 0;
+      ;;   for $i in 0..$length {
+      ;;     $digit = $memory[$digits + $i] * 2 + $carry;
+      ;;     $carry = $digit >= 10;
+      ;;     $memory[$digits + $i] = $digit - $carry * 10;
+      ;;   }
+      ;;   if $carry { $memory[$digits + $length] = 1; $length += 1; }
+      ;; }
+      (block $doubled
+        (loop $double
+          (br_if $doubled (i32.eqz (local.get $exponent)))
+          (local.set $carry (i32.const 0))
+          (local.set $i (i32.const 0))
+          (block $digits_doubled
+            (loop $double_digit
+              (br_if $digits_doubled (i32.ge_u (local.get $i) (local.get $length)))
+              (local.set $digit
+                (i32.add
+                  (i32.shl
+                    (i32.load8_u (i32.add (local.get $digits) (local.get $i)))
+                    (i32.const 1)
+                  )
+                  (local.get $carry)
+                )
+              )
+              (local.set $carry (i32.ge_u (local.get $digit) (i32.const 10)))
+              (i32.store8
+                (i32.add (local.get $digits) (local.get $i))
+                (i32.sub (local.get $digit) (i32.mul (local.get $carry) (i32.const 10)))
+              )
+              (local.set $i (i32.add (local.get $i) (i32.const 1)))
+              (br $double_digit)
             )
           )
+          (if (local.get $carry)
+            (then
+              (i32.store8 (i32.add (local.get $digits) (local.get $length)) (i32.const 1))
+              (local.set $length (i32.add (local.get $length) (i32.const 1)))
+            )
+          )
+          (local.set $exponent (i32.sub (local.get $exponent) (i32.const 1)))
+          (br $double)
         )
       )
-      (call $output-stream.blocking-write-and-flush
-        (local.get $stdout)
-        (local.get $offset)
-        (local.get $length)
-        (local.get $result_ptr)
+
+      ;; for $i in 0..$length { $memory[$text + $i] = '0' + $memory[$digits + $length - 1 - $i]; }
+      (local.set $i (i32.const 0))
+      (block $converted
+        (loop $convert_digit
+          (br_if $converted (i32.ge_u (local.get $i) (local.get $length)))
+          (i32.store8
+            (i32.add (local.get $text) (local.get $i))
+            (i32.add
+              (i32.load8_u
+                (i32.sub
+                  (i32.add (local.get $digits) (local.get $length))
+                  (i32.add (local.get $i) (i32.const 1))
+                )
+              )
+              (i32.const 48)
+            )
+          )
+          (local.set $i (i32.add (local.get $i) (i32.const 1)))
+          (br $convert_digit)
+        )
       )
+      (call $write_stdout (local.get $text) (local.get $length))
+      (call $stack_restore (local.get $digits))
+    )
+
+    ;;; Prints a boolean to stdout, as `true` or `false`.
+    ;;;
+    ;;; # Parameters
+    ;;; - $value: i32 - The boolean to print, 1 for `true` and 0 for `false`.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $print_bool (i32.const 1)) ;; print_bool(true);
+    ;;; ```
+    (func $print_bool (export "print_bool") (param $value i32) (result i32)
+      (local $ptr i32)
 
-      ;; // cleanup
-      ;; $memory[$offset..($result_ptr + 3)] = null;
-      (memory.fill
-        (local.get $offset)
-        (i32.const 0)
-        (i32.sub (i32.add (local.get $result_ptr) (i32.const 3)) (local.get $offset))
+      ;; the characters are stored as little-endian integers
+      (local.set $ptr (call $stack_alloc (i32.const 5)))
+      (if (local.get $value)
+        (then
+          ;; "true"
+          (i32.store (local.get $ptr) (i32.const 0x65757274))
+          (call $write_stdout (local.get $ptr) (i32.const 4))
+        )
+        (else
+          ;; "false"
+          (i32.store (local.get $ptr) (i32.const 0x736c6166))
+          (i32.store8 offset=4 (local.get $ptr) (i32.const 0x65))
+          (call $write_stdout (local.get $ptr) (i32.const 5))
+        )
       )
+      (call $stack_restore (local.get $ptr))
       (i32.const 0)
     )
+
+    ;;; Makes sure that there is unread input, reading more from stdin if needed.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - 1 if there is unread input, 0 at the end of the input.
+    (func $fill_input (result i32)
+      (local $result_ptr i32)
+      (local $is_error i32)
+      (local $ptr i32)
+      (local $length i32)
+
+      (if (i32.lt_u (global.get $input_position) (global.get $input_end))
+        (then (return (i32.const 1)))
+      )
+      (if (global.get $input_closed)
+        (then (return (i32.const 0)))
+      )
+      (if (i32.eq (global.get $stdin) (i32.const -1))
+        (then (global.set $stdin (call $get-stdin)))
+      )
+
+      ;; // read up to the size of the buffer
+      ;; match $input-stream.blocking-read($stdin, 4096) {
+      ;;   Ok(list) => { $input_position = list.ptr; $input_end = list.ptr + list.len; }
+      ;;   Err(_) => $input_closed = 1,
+      ;; }
+      (local.set $result_ptr (call $stack_alloc (i32.const 12)))
+      (call $input-stream.blocking-read
+        (global.get $stdin)
+        (i64.const 4096)
+        (local.get $result_ptr)
+      )
+      (local.set $is_error (i32.load8_u (local.get $result_ptr)))
+      (local.set $ptr (i32.load offset=4 (local.get $result_ptr)))
+      (local.set $length (i32.load offset=8 (local.get $result_ptr)))
+      (call $stack_restore (local.get $result_ptr))
+      (if (i32.or (local.get $is_error) (i32.eqz (local.get $length)))
+        (then
+          (global.set $input_closed (i32.const 1))
+          (return (i32.const 0))
+        )
+      )
+      (global.set $input_position (local.get $ptr))
+      (global.set $input_end (i32.add (local.get $ptr) (local.get $length)))
+      (i32.const 1)
+    )
+
+    ;;; Returns the next byte of the input without consuming it, or -1 at the end of the input.
+    (func $peek_byte (result i32)
+      (if (result i32) (call $fill_input)
+        (then (i32.load8_u (global.get $input_position)))
+        (else (i32.const -1))
+      )
+    )
+
+    ;;; Skips spaces, tabs and line breaks.
+    (func $skip_whitespace
+      (local $byte i32)
+
+      (loop $skip
+        (local.set $byte (call $peek_byte))
+        (if (i32.or
+              (i32.or
+                (i32.eq (local.get $byte) (i32.const 32))
+                (i32.eq (local.get $byte) (i32.const 9)))
+              (i32.or
+                (i32.eq (local.get $byte) (i32.const 10))
+                (i32.eq (local.get $byte) (i32.const 13))))
+          (then
+            (global.set $input_position (i32.add (global.get $input_position) (i32.const 1)))
+            (br $skip)
+          )
+        )
+      )
+    )
+
+    ;;; Reads a character from stdin.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The next byte of the input, or -1 at the end of the input.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $read_char) ;; read_char();
+    ;;; ```
+    (func $read_char (export "read_char") (result i32)
+      (local $byte i32)
+
+      (local.set $byte (call $peek_byte))
+      (if (i32.ne (local.get $byte) (i32.const -1))
+        (then
+          (global.set $input_position (i32.add (global.get $input_position) (i32.const 1)))
+        )
+      )
+      (local.get $byte)
+    )
+
+    ;;; Reads a 64-bit integer from stdin, skipping any whitespace before it.
+    ;;;
+    ;;; Traps when the input ends before the integer, when it does not continue with an optional
+    ;;; `-` followed by at least one digit, or when the integer does not fit in an `i64`.
+    ;;;
+    ;;; # Returns
+    ;;; - i64 - The integer.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $read_i64) ;; read_i64();
+    ;;; ```
+    (func $read_i64 (export "read_i64") (result i64)
+      (local $byte i32)
+      (local $digit i64)
+      (local $value i64)
+      (local $is_negative i32)
+
+      (call $skip_whitespace)
+      (if (i32.eq (call $peek_byte) (i32.const -1))
+        (then (call $end_of_input))
+      )
+      (if (i32.eq (call $peek_byte) (i32.const 45))
+        (then
+          (local.set $is_negative (i32.const 1))
+          (drop (call $read_char))
+        )
+      )
+
+      ;; // the integer has at least one digit
+      ;; if peek_byte() < '0' || peek_byte() > '9' { invalid_integer(); }
+      (local.set $byte (call $peek_byte))
+      (if (i32.or
+            (i32.lt_s (local.get $byte) (i32.const 48))
+            (i32.gt_s (local.get $byte) (i32.const 57)))
+        (then (call $invalid_integer))
+      )
+
+      ;; // $value is the magnitude, at most 2^63 - 1, or 2^63 for a negative integer
+      ;; loop {
+      ;;   $byte = peek_byte();
+      ;;   if $byte < '0' || $byte > '9' { break; }
+      ;;   $digit = $byte - '0';
+      ;;   if $value > 922337203685477580
+      ;;     || $value == 922337203685477580 && $digit > 7 + $is_negative {
+      ;;     integer_out_of_range();
+      ;;   }
+      ;;   $value = $value * 10 + $digit;
+      ;;   read_char();
+      ;; }
+      (block $done
+        (loop $read_digits
+          (local.set $byte (call $peek_byte))
+          (br_if $done (i32.lt_s (local.get $byte) (i32.const 48)))
+          (br_if $done (i32.gt_s (local.get $byte) (i32.const 57)))
+          (local.set $digit (i64.extend_i32_u (i32.sub (local.get $byte) (i32.const 48))))
+          (if (i32.or
+                (i64.gt_u (local.get $value) (i64.const 922337203685477580))
+                (i32.and
+                  (i64.eq (local.get $value) (i64.const 922337203685477580))
+                  (i64.gt_u
+                    (local.get $digit)
+                    (i64.extend_i32_u (i32.add (i32.const 7) (local.get $is_negative))))))
+            (then (call $integer_out_of_range))
+          )
+          (local.set $value
+            (i64.add (i64.mul (local.get $value) (i64.const 10)) (local.get $digit))
+          )
+          (drop (call $read_char))
+          (br $read_digits)
+        )
+      )
+
+      ;; 0 - 2^63 wraps to the smallest i64
+      (if (result i64) (local.get $is_negative)
+        (then (i64.sub (i64.const 0) (local.get $value)))
+        (else (local.get $value))
+      )
+    )
+
+    ;;; Reads an integer from stdin, skipping any whitespace before it.
+    ;;;
+    ;;; Traps like `$read_i64`, and when the integer does not fit in an `i32`.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The integer.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (call $read_int) ;; read_int();
+    ;;; ```
+    (func $read_int (export "read_int") (result i32)
+      (local $value i64)
+
+      (local.set $value (call $read_i64))
+      (if (i32.or
+            (i64.lt_s (local.get $value) (i64.const -2147483648))
+            (i64.gt_s (local.get $value) (i64.const 2147483647)))
+        (then (call $integer_out_of_range))
+      )
+      (i32.wrap_i64 (local.get $value))
+    )
+
+    ;;; Reads the rest of the current line from stdin, consuming the line break that ends it.
+    ;;;
+    ;;; # Returns
+    ;;; - i32 - The pointer to an array of `i32`s holding the characters of the line, without the
+    ;;;   line break and carriage returns. At the end of the input, the array holds only -1, which
+    ;;;   no character is, like the value `$read_char` returns there.
+    ;;;
+    ;;; # Example
+    ;;; ```wat
+    ;;; (local.set $line (call $read_line)) ;; let line: [i32] = read_line();
+    ;;; ```
+    (func $read_line (export "read_line") (result i32)
+      (local $byte i32)
+      (local $length i32)
+      (local $capacity i32)
+      (local $array i32)
+      (local $grown i32)
+
+      (if (i32.eq (call $peek_byte) (i32.const -1))
+        (then (return (call $array_new_i32 (i32.const -1) (i32.const 1))))
+      )
+      (local.set $capacity (i32.const 16))
+      (local.set $array (call $array_alloc (local.get $capacity) (i32.const 4)))
+      (block $done
+        (loop $read_bytes
+          (local.set $byte (call $read_char))
+          (br_if $done (i32.eq (local.get $byte) (i32.const -1)))
+          (br_if $done (i32.eq (local.get $byte) (i32.const 10)))
+          (if (i32.ne (local.get $byte) (i32.const 13))
+            (then
+              ;; // move the characters to an array twice as large when the array is full
+              ;; if $length == $capacity {
+              ;;   $capacity *= 2;
+              ;;   $grown = array_alloc($capacity, 4);
+              ;;   copy the $length characters of $array to $grown;
+              ;;   free($array);
+              ;;   $array = $grown;
+              ;; }
+              (if (i32.eq (local.get $length) (local.get $capacity))
+                (then
+                  (local.set $capacity (i32.shl (local.get $capacity) (i32.const 1)))
+                  (local.set $grown
+                    (call $array_alloc (local.get $capacity) (i32.const 4))
+                  )
+                  (memory.copy
+                    (i32.add (local.get $grown) (i32.const 8))
+                    (i32.add (local.get $array) (i32.const 8))
+                    (i32.shl (local.get $length) (i32.const 2))
+                  )
+                  (drop (call $free (local.get $array)))
+                  (local.set $array (local.get $grown))
+                )
+              )
+              (i32.store
+                (i32.add
+                  (i32.add (local.get $array) (i32.const 8))
+                  (i32.shl (local.get $length) (i32.const 2))
+                )
+                (local.get $byte)
+              )
+              (local.set $length (i32.add (local.get $length) (i32.const 1)))
+            )
+          )
+          (br $read_bytes)
+        )
+      )
+
+      ;; the array holds the length of the line rather than its capacity
+      (i32.store (local.get $array) (local.get $length))
+      (local.get $array)
+    )
   )
   (core instance $core|std (instantiate $Std
     (with "env" (instance $core|env))
+    (with "wasi:cli/stdin@0.2.2" (instance $core|wasi:cli/stdin@0.2.2))
     (with "wasi:cli/stdout@0.2.2" (instance $core|wasi:cli/stdout@0.2.2))
     (with "wasi:io/streams@0.2.2" (instance $core|wasi:io/streams@0.2.2))
   ))
 
 
-  ;; main module
+  ;; main module - placeholder replaced by the generated module, which imports the functions
+  ;; listed in `std_functions` and `RUNTIME_FUNCTIONS` from "shuiro:std@0.0.0"
   (core module $Main
     (import "env" "memory" (memory $memory 0))
+    (import "shuiro:std@0.0.0" "print_int" (func (param i32) (result i32)))
 
-    (import "shuiro:std@0.0.0" "print_int"
-      (func $print_int (param i32) (result i32))
-    )
-    (import "shuiro:std@0.0.0" "print_char"
-      (func $print_char (param i32) (result i32))
+    (func (export "main") (result i32)
+      (i32.const 0)
     )
   )
   (core instance $core|main (instantiate $Main
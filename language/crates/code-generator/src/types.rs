@@ -0,0 +1,141 @@
+//! Types of the source language, as checked while lowering.
+//!
+//! The IR only knows the WebAssembly value types, so several source types can be represented by
+//! the same IR type: unsigned integers share the types of signed ones, while booleans, arrays,
+//! structs and enums are all `i32`s. Booleans are 0 or 1, arrays and structs are pointers into
+//! linear memory and enums are either pointers or discriminants.
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    I32,
+    I64,
+    U32,
+    U64,
+    F32,
+    F64,
+
+    /// `true` or `false`, represented by the `i32`s 1 and 0.
+    Bool,
+
+    /// `()`, returned by functions without a return value. No value has this type.
+    Unit,
+
+    /// An array in linear memory, with its length if it is known at compile time.
+    ///
+    /// Arrays are passed by reference: assigning an array to another variable does not copy its
+    /// elements.
+    Array {
+        element: Box<Type>,
+        length: Option<u32>,
+    },
+
+    /// Several values returned together by a function, which are never stored as a whole: a
+    /// tuple is returned as several WebAssembly values and destructured into several locals.
+    Tuple(Vec<Type>),
+
+    /// A struct in linear memory, by name. Structs are passed by reference like arrays.
+    Struct(String),
+
+    /// An enum, by name. Depending on its variants, it is either a discriminant or a pointer to
+    /// its variant in linear memory.
+    Enum(String),
+}
+
+impl Type {
+    /// Type representing values of this type in the IR.
+    pub fn ir_type(&self) -> ir::Type {
+        match self {
+            Type::I32
+            | Type::U32
+            | Type::Bool
+            | Type::Array { .. }
+            | Type::Struct(_)
+            | Type::Enum(_) => ir::Type::I32,
+            Type::I64 | Type::U64 => ir::Type::I64,
+            Type::F32 => ir::Type::F32,
+            Type::F64 => ir::Type::F64,
+            Type::Unit => unreachable!("no value has the unit type"),
+            Type::Tuple(_) => unreachable!("tuples are represented by several values"),
+        }
+    }
+
+    /// Whether a value of this type can be used where a value of type `expected` is expected.
+    ///
+    /// Arrays of a known length can be used as arrays of any length.
+    pub fn is_assignable_to(&self, expected: &Type) -> bool {
+        match (self, expected) {
+            (
+                Type::Array { element, length },
+                Type::Array {
+                    element: expected_element,
+                    length: expected_length,
+                },
+            ) => {
+                element == expected_element
+                    && (expected_length.is_none() || length == expected_length)
+            }
+            _ => self == expected,
+        }
+    }
+
+    /// Whether the type is an integer type, signed or not.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Type::I32 | Type::I64 | Type::U32 | Type::U64)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Type::F32 | Type::F64)
+    }
+
+    /// Whether the type is a numeric type, which arithmetic and comparisons operate on.
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+
+    /// Whether the type is an unsigned integer type, whose division, comparisons and conversions
+    /// differ from those of signed integers.
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Type::U32 | Type::U64)
+    }
+}
+
+impl From<ir::Type> for Type {
+    fn from(ty: ir::Type) -> Self {
+        match ty {
+            ir::Type::I32 => Type::I32,
+            ir::Type::I64 => Type::I64,
+            ir::Type::F32 => Type::F32,
+            ir::Type::F64 => Type::F64,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::I32 => write!(f, "i32"),
+            Type::I64 => write!(f, "i64"),
+            Type::U32 => write!(f, "u32"),
+            Type::U64 => write!(f, "u64"),
+            Type::F32 => write!(f, "f32"),
+            Type::F64 => write!(f, "f64"),
+            Type::Bool => write!(f, "bool"),
+            Type::Unit => write!(f, "()"),
+            Type::Array {
+                element,
+                length: Some(length),
+            } => write!(f, "[{}; {}]", element, length),
+            Type::Array {
+                element,
+                length: None,
+            } => write!(f, "[{}]", element),
+            Type::Tuple(elements) => {
+                let elements: Vec<String> = elements.iter().map(Type::to_string).collect();
+                write!(f, "({})", elements.join(", "))
+            }
+            Type::Struct(name) | Type::Enum(name) => write!(f, "{}", name),
+        }
+    }
+}
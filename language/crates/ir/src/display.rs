@@ -0,0 +1,403 @@
+//! Textual dump of the IR, for debugging.
+//!
+//! ```text
+//! global @0 calls: i32 = 0i32
+//!
+//! fn add(%0 a: i32, %1 b: i32) -> i32 {
+//!     local %2 c: i32
+//!     %2 = i32.add(%0, %1);
+//!     print_int(%2);
+//!     @0 = i32.add(@0, 1i32);
+//!     %2
+//! }
+//!
+//! fn divmod(%0 a: i32, %1 b: i32) -> (i32, i32) {
+//!     return i32.div(%0, %1), i32.rem(%0, %1);
+//! }
+//!
+//! fn main() -> i32 {
+//!     local %0 q: i32
+//!     %0, _ = divmod(7i32, 2i32);
+//!     %0
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::{
+    BinaryOperator, Block, Constant, Expression, ExpressionKind, Function, GlobalId, LocalId,
+    Program, Statement, Type, UnaryOperator,
+};
+
+const INDENT: &str = "    ";
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, global) in self.globals.iter().enumerate() {
+            writeln!(
+                f,
+                "global {} {}: {} = {}",
+                GlobalId(index),
+                global.name,
+                global.value.ty(),
+                global.value
+            )?;
+        }
+        for (index, function) in self.functions.iter().enumerate() {
+            if index > 0 || !self.globals.is_empty() {
+                writeln!(f)?;
+            }
+            write!(f, "{}", function)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Printer { f, indent: 0 }.function(self)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::I32 => write!(f, "i32"),
+            Type::I64 => write!(f, "i64"),
+            Type::F32 => write!(f, "f32"),
+            Type::F64 => write!(f, "f64"),
+        }
+    }
+}
+
+impl fmt::Display for LocalId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "%{}", self.0)
+    }
+}
+
+impl fmt::Display for GlobalId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.0)
+    }
+}
+
+impl fmt::Display for Constant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constant::I32(value) => write!(f, "{}i32", value),
+            Constant::I64(value) => write!(f, "{}i64", value),
+            Constant::F32(value) => write!(f, "{:?}f32", value),
+            Constant::F64(value) => write!(f, "{:?}f64", value),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryOperator::Add => write!(f, "add"),
+            BinaryOperator::Subtract => write!(f, "sub"),
+            BinaryOperator::Multiply => write!(f, "mul"),
+            BinaryOperator::Divide => write!(f, "div"),
+            BinaryOperator::DivideUnsigned => write!(f, "div_u"),
+            BinaryOperator::Remainder => write!(f, "rem"),
+            BinaryOperator::RemainderUnsigned => write!(f, "rem_u"),
+            BinaryOperator::And => write!(f, "and"),
+            BinaryOperator::Or => write!(f, "or"),
+            BinaryOperator::Xor => write!(f, "xor"),
+            BinaryOperator::ShiftLeft => write!(f, "shl"),
+            BinaryOperator::ShiftRight => write!(f, "shr"),
+            BinaryOperator::ShiftRightUnsigned => write!(f, "shr_u"),
+            BinaryOperator::Equal => write!(f, "eq"),
+            BinaryOperator::NotEqual => write!(f, "ne"),
+            BinaryOperator::LessThan => write!(f, "lt"),
+            BinaryOperator::LessThanUnsigned => write!(f, "lt_u"),
+            BinaryOperator::LessThanOrEqual => write!(f, "le"),
+            BinaryOperator::LessThanOrEqualUnsigned => write!(f, "le_u"),
+            BinaryOperator::GreaterThan => write!(f, "gt"),
+            BinaryOperator::GreaterThanUnsigned => write!(f, "gt_u"),
+            BinaryOperator::GreaterThanOrEqual => write!(f, "ge"),
+            BinaryOperator::GreaterThanOrEqualUnsigned => write!(f, "ge_u"),
+        }
+    }
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnaryOperator::Eqz => write!(f, "eqz"),
+        }
+    }
+}
+
+struct Printer<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    indent: usize,
+}
+
+impl Printer<'_, '_> {
+    fn new_line(&mut self) -> fmt::Result {
+        writeln!(self.f)?;
+        for _ in 0..self.indent {
+            write!(self.f, "{}", INDENT)?;
+        }
+        Ok(())
+    }
+
+    fn function(&mut self, function: &Function) -> fmt::Result {
+        write!(self.f, "fn {}(", function.name)?;
+        for (index, parameter) in function.parameters().iter().enumerate() {
+            if index > 0 {
+                write!(self.f, ", ")?;
+            }
+            write!(
+                self.f,
+                "{} {}: {}",
+                LocalId(index),
+                parameter.name,
+                parameter.ty
+            )?;
+        }
+        write!(self.f, ")")?;
+        match function.results.as_slice() {
+            [] => {}
+            [result] => write!(self.f, " -> {}", result)?,
+            results => {
+                let results: Vec<String> = results.iter().map(Type::to_string).collect();
+                write!(self.f, " -> ({})", results.join(", "))?;
+            }
+        }
+        write!(self.f, " {{")?;
+        self.indent += 1;
+        for (index, variable) in function.variables().iter().enumerate() {
+            self.new_line()?;
+            write!(
+                self.f,
+                "local {} {}: {}",
+                LocalId(function.parameter_count + index),
+                variable.name,
+                variable.ty
+            )?;
+        }
+        self.block_contents(&function.body)?;
+        self.indent -= 1;
+        self.new_line()?;
+        writeln!(self.f, "}}")
+    }
+
+    /// Print a block surrounded by braces.
+    fn block(&mut self, block: &Block) -> fmt::Result {
+        write!(self.f, "{{")?;
+        self.indent += 1;
+        self.block_contents(block)?;
+        self.indent -= 1;
+        self.new_line()?;
+        write!(self.f, "}}")
+    }
+
+    fn block_contents(&mut self, block: &Block) -> fmt::Result {
+        for statement in &block.statements {
+            self.new_line()?;
+            self.statement(statement)?;
+        }
+        if let Some(result) = &block.result {
+            self.new_line()?;
+            self.expression(result)?;
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &Statement) -> fmt::Result {
+        match statement {
+            Statement::Expression(expression) => {
+                self.expression(expression)?;
+                write!(self.f, ";")
+            }
+            Statement::LocalSet { local, value } => {
+                write!(self.f, "{} = ", local)?;
+                self.expression(value)?;
+                write!(self.f, ";")
+            }
+            Statement::GlobalSet { global, value } => {
+                write!(self.f, "{} = ", global)?;
+                self.expression(value)?;
+                write!(self.f, ";")
+            }
+            Statement::Store {
+                address,
+                value,
+                offset,
+            } => {
+                write!(self.f, "{}.store(", value.ty)?;
+                self.address(address, *offset)?;
+                write!(self.f, ", ")?;
+                self.expression(value)?;
+                write!(self.f, ");")
+            }
+            Statement::Call {
+                function,
+                arguments,
+                results,
+                ..
+            } => {
+                for (index, result) in results.iter().enumerate() {
+                    if index > 0 {
+                        write!(self.f, ", ")?;
+                    }
+                    match result {
+                        Some(local) => write!(self.f, "{}", local)?,
+                        None => write!(self.f, "_")?,
+                    }
+                }
+                if !results.is_empty() {
+                    write!(self.f, " = ")?;
+                }
+                self.call(function, arguments)?;
+                write!(self.f, ";")
+            }
+            Statement::ReturnCall {
+                function,
+                arguments,
+                ..
+            } => {
+                write!(self.f, "return ")?;
+                self.call(function, arguments)?;
+                write!(self.f, ";")
+            }
+            Statement::Return { values, .. } => {
+                write!(self.f, "return ")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(self.f, ", ")?;
+                    }
+                    self.expression(value)?;
+                }
+                write!(self.f, ";")
+            }
+            Statement::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                write!(self.f, "if ")?;
+                self.expression(condition)?;
+                write!(self.f, " ")?;
+                self.block(then_block)?;
+                if let Some(else_block) = else_block {
+                    write!(self.f, " else ")?;
+                    self.block(else_block)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn call(&mut self, function: &str, arguments: &[Expression]) -> fmt::Result {
+        write!(self.f, "{}(", function)?;
+        for (index, argument) in arguments.iter().enumerate() {
+            if index > 0 {
+                write!(self.f, ", ")?;
+            }
+            self.expression(argument)?;
+        }
+        write!(self.f, ")")
+    }
+
+    fn expression(&mut self, expression: &Expression) -> fmt::Result {
+        match &expression.kind {
+            ExpressionKind::Constant(constant) => write!(self.f, "{}", constant),
+            ExpressionKind::LocalGet(local) => write!(self.f, "{}", local),
+            ExpressionKind::GlobalGet(global) => write!(self.f, "{}", global),
+            ExpressionKind::LocalTee { local, value } => {
+                write!(self.f, "({} = ", local)?;
+                self.expression(value)?;
+                write!(self.f, ")")
+            }
+            ExpressionKind::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                write!(self.f, "{}.{}(", left.ty, operator)?;
+                self.expression(left)?;
+                write!(self.f, ", ")?;
+                self.expression(right)?;
+                write!(self.f, ")")
+            }
+            ExpressionKind::Unary { operator, operand } => {
+                write!(self.f, "{}.{}(", operand.ty, operator)?;
+                self.expression(operand)?;
+                write!(self.f, ")")
+            }
+            ExpressionKind::Convert { operand, signed } => {
+                write!(self.f, "{}.convert_{}", expression.ty, operand.ty)?;
+                // the sign only matters for conversions involving an integer
+                let is_integer = !(expression.ty.is_float() && operand.ty.is_float());
+                if !signed && is_integer {
+                    write!(self.f, "_u")?;
+                }
+                write!(self.f, "(")?;
+                self.expression(operand)?;
+                write!(self.f, ")")
+            }
+            ExpressionKind::Load { address, offset } => {
+                write!(self.f, "{}.load(", expression.ty)?;
+                self.address(address, *offset)?;
+                write!(self.f, ")")
+            }
+            ExpressionKind::Call {
+                function,
+                arguments,
+            } => self.call(function, arguments),
+            ExpressionKind::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                write!(self.f, "if ")?;
+                self.expression(condition)?;
+                write!(self.f, " -> {} ", expression.ty)?;
+                self.block(then_block)?;
+                write!(self.f, " else ")?;
+                self.block(else_block)
+            }
+            ExpressionKind::Block(block) => {
+                write!(self.f, "block -> {} ", expression.ty)?;
+                self.block(block)
+            }
+            ExpressionKind::Switch {
+                index,
+                table,
+                default,
+                cases,
+            } => {
+                write!(self.f, "switch ")?;
+                self.expression(index)?;
+                write!(self.f, " [")?;
+                for case in table {
+                    write!(self.f, "{}, ", case)?;
+                }
+                write!(self.f, "default {}] -> {} {{", default, expression.ty)?;
+                self.indent += 1;
+                for (index, case) in cases.iter().enumerate() {
+                    self.new_line()?;
+                    write!(self.f, "case {} ", index)?;
+                    self.block(case)?;
+                }
+                self.indent -= 1;
+                self.new_line()?;
+                write!(self.f, "}}")
+            }
+        }
+    }
+
+    /// Print a memory address as `address + offset`, omitting a zero offset.
+    fn address(&mut self, address: &Expression, offset: u32) -> fmt::Result {
+        self.expression(address)?;
+        if offset > 0 {
+            write!(self.f, " + {}", offset)?;
+        }
+        Ok(())
+    }
+}
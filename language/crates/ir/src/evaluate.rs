@@ -0,0 +1,181 @@
+//! Evaluation of constant expressions, shared by the optimizer and by the lowering of constants,
+//! whose values must be known at compile time.
+
+use crate::{BinaryOperator, Constant, Expression, ExpressionKind, Type, UnaryOperator};
+
+impl Constant {
+    /// Value of an integer constant, or `None` for floats.
+    pub fn as_i64(self) -> Option<i64> {
+        match self {
+            Constant::I32(value) => Some(value as i64),
+            Constant::I64(value) => Some(value),
+            Constant::F32(_) | Constant::F64(_) => None,
+        }
+    }
+
+    /// Convert a constant like [`ExpressionKind::Convert`], whose semantics are those of `as` casts
+    /// in Rust.
+    pub fn convert(self, ty: Type, signed: bool) -> Constant {
+        let float = match self {
+            Constant::I32(value) if !signed => {
+                return Self::convert_integer(value as u32 as i64, ty, signed)
+            }
+            Constant::I32(value) => return Self::convert_integer(value as i64, ty, signed),
+            Constant::I64(value) => return Self::convert_integer(value, ty, signed),
+            Constant::F32(value) => value as f64,
+            Constant::F64(value) => value,
+        };
+        match (ty, signed) {
+            (Type::I32, true) => Constant::I32(float as i32),
+            (Type::I32, false) => Constant::I32(float as u32 as i32),
+            (Type::I64, true) => Constant::I64(float as i64),
+            (Type::I64, false) => Constant::I64(float as u64 as i64),
+            (Type::F32, _) => Constant::F32(float as f32),
+            (Type::F64, _) => Constant::F64(float),
+        }
+    }
+
+    /// Convert an integer, already extended to an `i64` according to its sign.
+    fn convert_integer(value: i64, ty: Type, signed: bool) -> Constant {
+        match (ty, signed) {
+            (Type::I32, _) => Constant::I32(value as i32),
+            (Type::I64, _) => Constant::I64(value),
+            (Type::F32, true) => Constant::F32(value as f32),
+            (Type::F32, false) => Constant::F32(value as u64 as f32),
+            (Type::F64, true) => Constant::F64(value as f64),
+            (Type::F64, false) => Constant::F64(value as u64 as f64),
+        }
+    }
+
+    /// Evaluate a binary operation on constants, unless it would trap at run time.
+    pub fn evaluate_binary(
+        operator: BinaryOperator,
+        left: Constant,
+        right: Constant,
+    ) -> Option<Constant> {
+        fn compare<T: PartialOrd>(operator: BinaryOperator, left: T, right: T) -> Option<Constant> {
+            let result = match operator {
+                BinaryOperator::Equal => left == right,
+                BinaryOperator::NotEqual => left != right,
+                BinaryOperator::LessThan | BinaryOperator::LessThanUnsigned => left < right,
+                BinaryOperator::LessThanOrEqual | BinaryOperator::LessThanOrEqualUnsigned => {
+                    left <= right
+                }
+                BinaryOperator::GreaterThan | BinaryOperator::GreaterThanUnsigned => left > right,
+                BinaryOperator::GreaterThanOrEqual | BinaryOperator::GreaterThanOrEqualUnsigned => {
+                    left >= right
+                }
+                _ => return None,
+            };
+            Some(Constant::I32(result as i32))
+        }
+
+        match (left, right) {
+            (Constant::I32(left), Constant::I32(right)) => match operator {
+                BinaryOperator::Add => Some(Constant::I32(left.wrapping_add(right))),
+                BinaryOperator::Subtract => Some(Constant::I32(left.wrapping_sub(right))),
+                BinaryOperator::Multiply => Some(Constant::I32(left.wrapping_mul(right))),
+                BinaryOperator::Divide => left.checked_div(right).map(Constant::I32),
+                BinaryOperator::DivideUnsigned => (left as u32)
+                    .checked_div(right as u32)
+                    .map(|value| Constant::I32(value as i32)),
+                BinaryOperator::Remainder => left.checked_rem(right).map(Constant::I32),
+                BinaryOperator::RemainderUnsigned => (left as u32)
+                    .checked_rem(right as u32)
+                    .map(|value| Constant::I32(value as i32)),
+                BinaryOperator::And => Some(Constant::I32(left & right)),
+                BinaryOperator::Or => Some(Constant::I32(left | right)),
+                BinaryOperator::Xor => Some(Constant::I32(left ^ right)),
+                // the shift amount is taken modulo the number of bits
+                BinaryOperator::ShiftLeft => Some(Constant::I32(left.wrapping_shl(right as u32))),
+                BinaryOperator::ShiftRight => Some(Constant::I32(left.wrapping_shr(right as u32))),
+                BinaryOperator::ShiftRightUnsigned => Some(Constant::I32(
+                    (left as u32).wrapping_shr(right as u32) as i32,
+                )),
+                _ if operator.is_unsigned() => compare(operator, left as u32, right as u32),
+                _ => compare(operator, left, right),
+            },
+            (Constant::I64(left), Constant::I64(right)) => match operator {
+                BinaryOperator::Add => Some(Constant::I64(left.wrapping_add(right))),
+                BinaryOperator::Subtract => Some(Constant::I64(left.wrapping_sub(right))),
+                BinaryOperator::Multiply => Some(Constant::I64(left.wrapping_mul(right))),
+                BinaryOperator::Divide => left.checked_div(right).map(Constant::I64),
+                BinaryOperator::DivideUnsigned => (left as u64)
+                    .checked_div(right as u64)
+                    .map(|value| Constant::I64(value as i64)),
+                BinaryOperator::Remainder => left.checked_rem(right).map(Constant::I64),
+                BinaryOperator::RemainderUnsigned => (left as u64)
+                    .checked_rem(right as u64)
+                    .map(|value| Constant::I64(value as i64)),
+                BinaryOperator::And => Some(Constant::I64(left & right)),
+                BinaryOperator::Or => Some(Constant::I64(left | right)),
+                BinaryOperator::Xor => Some(Constant::I64(left ^ right)),
+                BinaryOperator::ShiftLeft => Some(Constant::I64(left.wrapping_shl(right as u32))),
+                BinaryOperator::ShiftRight => Some(Constant::I64(left.wrapping_shr(right as u32))),
+                BinaryOperator::ShiftRightUnsigned => Some(Constant::I64(
+                    (left as u64).wrapping_shr(right as u32) as i64,
+                )),
+                _ if operator.is_unsigned() => compare(operator, left as u64, right as u64),
+                _ => compare(operator, left, right),
+            },
+            (Constant::F32(left), Constant::F32(right)) => match operator {
+                BinaryOperator::Add => Some(Constant::F32(left + right)),
+                BinaryOperator::Subtract => Some(Constant::F32(left - right)),
+                BinaryOperator::Multiply => Some(Constant::F32(left * right)),
+                BinaryOperator::Divide => Some(Constant::F32(left / right)),
+                _ => compare(operator, left, right),
+            },
+            (Constant::F64(left), Constant::F64(right)) => match operator {
+                BinaryOperator::Add => Some(Constant::F64(left + right)),
+                BinaryOperator::Subtract => Some(Constant::F64(left - right)),
+                BinaryOperator::Multiply => Some(Constant::F64(left * right)),
+                BinaryOperator::Divide => Some(Constant::F64(left / right)),
+                _ => compare(operator, left, right),
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Expression {
+    /// Value of the expression if it only operates on constants and never traps, without
+    /// modifying it.
+    pub fn evaluate(&self) -> Option<Constant> {
+        match &self.kind {
+            ExpressionKind::Constant(constant) => Some(*constant),
+            ExpressionKind::Binary {
+                operator,
+                left,
+                right,
+            } => Constant::evaluate_binary(*operator, left.evaluate()?, right.evaluate()?),
+            ExpressionKind::Unary {
+                operator: UnaryOperator::Eqz,
+                operand,
+            } => {
+                let value = operand.evaluate()?.as_i64()?;
+                Some(Constant::I32((value == 0) as i32))
+            }
+            ExpressionKind::Convert { operand, signed } => {
+                Some(operand.evaluate()?.convert(self.ty, *signed))
+            }
+            ExpressionKind::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                let taken = match condition.evaluate()?.as_i64()? {
+                    0 => else_block,
+                    _ => then_block,
+                };
+                match &taken.result {
+                    Some(result) if taken.statements.is_empty() => result.evaluate(),
+                    _ => None,
+                }
+            }
+            ExpressionKind::Block(block) if block.statements.is_empty() => {
+                block.result.as_ref()?.evaluate()
+            }
+            _ => None,
+        }
+    }
+}
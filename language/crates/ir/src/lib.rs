@@ -0,0 +1,327 @@
+//! Intermediate representation between the AST and WebAssembly.
+//!
+//! The IR is a typed tree: every expression knows its type, identifiers are resolved to numbered
+//! locals, and control flow is expressed with explicit blocks and `if`s that map directly onto
+//! structured WebAssembly control flow.
+
+mod display;
+mod evaluate;
+
+pub use ast::Location;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    pub globals: Vec<Global>,
+    pub functions: Vec<Function>,
+}
+
+/// A mutable variable shared by every function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Global {
+    pub name: String,
+
+    /// Value of the global when the program starts, which also decides its type.
+    pub value: Constant,
+}
+
+/// Index of a global in [`Program::globals`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GlobalId(pub usize);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    pub name: String,
+
+    /// Parameters and local variables of the function.
+    ///
+    /// The first `parameter_count` locals are the parameters, in order.
+    pub locals: Vec<Local>,
+    pub parameter_count: usize,
+
+    /// Types of the values the function returns, in order. Functions returning a tuple return
+    /// several values, while functions without a return value return none.
+    pub results: Vec<Type>,
+    pub body: Block,
+    pub location: Location,
+}
+
+impl Function {
+    /// Parameters of the function.
+    pub fn parameters(&self) -> &[Local] {
+        &self.locals[..self.parameter_count]
+    }
+
+    /// Local variables of the function, excluding the parameters.
+    pub fn variables(&self) -> &[Local] {
+        &self.locals[self.parameter_count..]
+    }
+}
+
+/// Index of a local in [`Function::locals`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LocalId(pub usize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Local {
+    /// Name of the variable in the source, which may be shared by several locals when shadowed.
+    pub name: String,
+    pub ty: Type,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl Type {
+    pub fn is_float(&self) -> bool {
+        matches!(self, Type::F32 | Type::F64)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Block {
+    pub statements: Vec<Statement>,
+
+    /// Value of the block, if it evaluates to one.
+    pub result: Option<Box<Expression>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement {
+    /// Evaluate an expression and discard its value.
+    Expression(Expression),
+
+    /// Store a value into a local.
+    LocalSet { local: LocalId, value: Expression },
+
+    /// Store a value into a global.
+    GlobalSet { global: GlobalId, value: Expression },
+
+    /// Store a value into linear memory at `address + offset`.
+    Store {
+        address: Expression,
+        value: Expression,
+        offset: u32,
+    },
+
+    /// Call a function and store the values it returns into `results`, in order.
+    ///
+    /// Values whose local is `None` are discarded, as are all the values when `results` is
+    /// empty, such as the exit code returned by `main`.
+    Call {
+        function: String,
+        arguments: Vec<Expression>,
+        results: Vec<Option<LocalId>>,
+        location: Location,
+    },
+
+    /// Return the values from the function. Only ends the body of functions returning several
+    /// values, whose body has no result.
+    Return {
+        values: Vec<Expression>,
+        location: Location,
+    },
+
+    /// Return the values returned by a call from the function, as a tail call. Only ends the body
+    /// of functions returning several values, like `Return`.
+    ReturnCall {
+        function: String,
+        arguments: Vec<Expression>,
+        location: Location,
+    },
+
+    /// Conditionally run a block without a value.
+    If {
+        condition: Expression,
+        then_block: Block,
+        else_block: Option<Block>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub ty: Type,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionKind {
+    Constant(Constant),
+    LocalGet(LocalId),
+    GlobalGet(GlobalId),
+
+    /// Store a value into a local and evaluate to the stored value.
+    LocalTee {
+        local: LocalId,
+        value: Box<Expression>,
+    },
+
+    /// Binary operation on two operands of the same type.
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+
+    /// Convert the operand to the type of this expression.
+    ///
+    /// `signed` tells whether the integer operand, or the integer result when converting from a
+    /// float, is signed. Floats are converted to integers by truncating them and saturating at the
+    /// bounds of the integer type, with NaN converted to 0.
+    Convert {
+        operand: Box<Expression>,
+        signed: bool,
+    },
+
+    /// Load a value of the type of this expression from linear memory at `address + offset`.
+    Load {
+        address: Box<Expression>,
+        offset: u32,
+    },
+
+    Call {
+        function: String,
+        arguments: Vec<Expression>,
+    },
+
+    /// Evaluate one of the blocks depending on the `i32` condition.
+    If {
+        condition: Box<Expression>,
+        then_block: Block,
+        else_block: Block,
+    },
+
+    /// Run the statements of a block and evaluate to its result.
+    Block(Block),
+
+    /// Evaluate the case `table[index]` for the `i32` index, or the case `default` when the index
+    /// is out of the bounds of the table. Cases are indices into `cases`.
+    Switch {
+        index: Box<Expression>,
+        table: Vec<usize>,
+        default: usize,
+        cases: Vec<Block>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Constant {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+}
+
+impl Constant {
+    pub fn ty(&self) -> Type {
+        match self {
+            Constant::I32(_) => Type::I32,
+            Constant::I64(_) => Type::I64,
+            Constant::F32(_) => Type::F32,
+            Constant::F64(_) => Type::F64,
+        }
+    }
+}
+
+/// Binary operators. Arithmetic operators produce the type of their operands, while comparison
+/// operators produce an `i32` that is either 0 or 1.
+///
+/// Operators whose result depends on the sign of integer operands treat them as signed, unless
+/// they are one of the `*Unsigned` variants, which only apply to integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    DivideUnsigned,
+    Remainder,
+    RemainderUnsigned,
+    And,
+    Or,
+    Xor,
+    ShiftLeft,
+    ShiftRight,
+    ShiftRightUnsigned,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanUnsigned,
+    LessThanOrEqual,
+    LessThanOrEqualUnsigned,
+    GreaterThan,
+    GreaterThanUnsigned,
+    GreaterThanOrEqual,
+    GreaterThanOrEqualUnsigned,
+}
+
+impl BinaryOperator {
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::Equal
+                | BinaryOperator::NotEqual
+                | BinaryOperator::LessThan
+                | BinaryOperator::LessThanUnsigned
+                | BinaryOperator::LessThanOrEqual
+                | BinaryOperator::LessThanOrEqualUnsigned
+                | BinaryOperator::GreaterThan
+                | BinaryOperator::GreaterThanUnsigned
+                | BinaryOperator::GreaterThanOrEqual
+                | BinaryOperator::GreaterThanOrEqualUnsigned
+        )
+    }
+
+    /// Whether the operator traps on a zero divisor.
+    pub fn is_division(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::Divide
+                | BinaryOperator::DivideUnsigned
+                | BinaryOperator::Remainder
+                | BinaryOperator::RemainderUnsigned
+        )
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::DivideUnsigned
+                | BinaryOperator::RemainderUnsigned
+                | BinaryOperator::ShiftRightUnsigned
+                | BinaryOperator::LessThanUnsigned
+                | BinaryOperator::LessThanOrEqualUnsigned
+                | BinaryOperator::GreaterThanUnsigned
+                | BinaryOperator::GreaterThanOrEqualUnsigned
+        )
+    }
+
+    /// The operator treating integer operands as unsigned.
+    pub fn unsigned(self) -> Self {
+        match self {
+            BinaryOperator::Divide => BinaryOperator::DivideUnsigned,
+            BinaryOperator::Remainder => BinaryOperator::RemainderUnsigned,
+            BinaryOperator::ShiftRight => BinaryOperator::ShiftRightUnsigned,
+            BinaryOperator::LessThan => BinaryOperator::LessThanUnsigned,
+            BinaryOperator::LessThanOrEqual => BinaryOperator::LessThanOrEqualUnsigned,
+            BinaryOperator::GreaterThan => BinaryOperator::GreaterThanUnsigned,
+            BinaryOperator::GreaterThanOrEqual => BinaryOperator::GreaterThanOrEqualUnsigned,
+            operator => operator,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOperator {
+    /// Evaluates to 1 if the operand is zero and 0 otherwise.
+    Eqz,
+}
@@ -0,0 +1,440 @@
+//! Constant folding.
+//!
+//! Evaluates operations on constants at compile time, removes integer operations that do not
+//! change their operand (`x + 0`, `x * 1`, `cmp != 0`) and replaces `if`s with a constant
+//! condition and switches with a constant index by the branch that is taken.
+
+use ir::{BinaryOperator, Block, Constant, Expression, ExpressionKind, Statement, UnaryOperator};
+
+use crate::visit::take_expression;
+
+pub(crate) fn fold_constants(function: &mut ir::Function) {
+    fold_block(&mut function.body);
+}
+
+fn fold_block(block: &mut Block) {
+    let statements = std::mem::take(&mut block.statements);
+    for statement in statements {
+        match statement {
+            Statement::Expression(mut expression) => {
+                fold_expression(&mut expression);
+                block.statements.push(Statement::Expression(expression));
+            }
+            Statement::LocalSet { local, mut value } => {
+                fold_expression(&mut value);
+                block.statements.push(Statement::LocalSet { local, value });
+            }
+            Statement::GlobalSet { global, mut value } => {
+                fold_expression(&mut value);
+                block
+                    .statements
+                    .push(Statement::GlobalSet { global, value });
+            }
+            Statement::Store {
+                mut address,
+                mut value,
+                offset,
+            } => {
+                fold_expression(&mut address);
+                fold_expression(&mut value);
+                block.statements.push(Statement::Store {
+                    address,
+                    value,
+                    offset,
+                });
+            }
+            Statement::Call {
+                function,
+                mut arguments,
+                results,
+                location,
+            } => {
+                arguments.iter_mut().for_each(fold_expression);
+                block.statements.push(Statement::Call {
+                    function,
+                    arguments,
+                    results,
+                    location,
+                });
+            }
+            Statement::Return {
+                mut values,
+                location,
+            } => {
+                values.iter_mut().for_each(fold_expression);
+                block
+                    .statements
+                    .push(Statement::Return { values, location });
+            }
+            Statement::ReturnCall {
+                function,
+                mut arguments,
+                location,
+            } => {
+                arguments.iter_mut().for_each(fold_expression);
+                block.statements.push(Statement::ReturnCall {
+                    function,
+                    arguments,
+                    location,
+                });
+            }
+            Statement::If {
+                mut condition,
+                mut then_block,
+                mut else_block,
+            } => {
+                fold_expression(&mut condition);
+                match constant_value(&condition) {
+                    // locals are already resolved, so the statements of the taken branch can be
+                    // moved into the enclosing block
+                    Some(value) => {
+                        let taken = if value != 0 {
+                            Some(then_block)
+                        } else {
+                            else_block
+                        };
+                        if let Some(mut taken) = taken {
+                            fold_block(&mut taken);
+                            block.statements.append(&mut taken.statements);
+                        }
+                    }
+                    None => {
+                        fold_block(&mut then_block);
+                        if let Some(else_block) = &mut else_block {
+                            fold_block(else_block);
+                        }
+                        block.statements.push(Statement::If {
+                            condition,
+                            then_block,
+                            else_block,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    if let Some(result) = &mut block.result {
+        fold_expression(result);
+    }
+}
+
+fn fold_expression(expression: &mut Expression) {
+    match &mut expression.kind {
+        ExpressionKind::Constant(_)
+        | ExpressionKind::LocalGet(_)
+        | ExpressionKind::GlobalGet(_) => {}
+        ExpressionKind::LocalTee { value, .. } => fold_expression(value),
+        ExpressionKind::Binary { left, right, .. } => {
+            fold_expression(left);
+            fold_expression(right);
+        }
+        ExpressionKind::Unary { operand, .. } | ExpressionKind::Convert { operand, .. } => {
+            fold_expression(operand)
+        }
+        ExpressionKind::Load { address, .. } => fold_expression(address),
+        ExpressionKind::Call { arguments, .. } => arguments.iter_mut().for_each(fold_expression),
+        ExpressionKind::If {
+            condition,
+            then_block,
+            else_block,
+        } => {
+            fold_expression(condition);
+            fold_block(then_block);
+            fold_block(else_block);
+        }
+        ExpressionKind::Block(block) => fold_block(block),
+        ExpressionKind::Switch { index, cases, .. } => {
+            fold_expression(index);
+            cases.iter_mut().for_each(fold_block);
+        }
+    }
+
+    if let Some(simplified) = simplify(expression) {
+        *expression = simplified;
+    }
+}
+
+/// Simplify an expression whose operands are already folded.
+fn simplify(expression: &mut Expression) -> Option<Expression> {
+    let location = expression.location.clone();
+    let constant = |constant: Constant| Expression {
+        kind: ExpressionKind::Constant(constant),
+        ty: constant.ty(),
+        location: location.clone(),
+    };
+
+    match &mut expression.kind {
+        ExpressionKind::Binary {
+            operator,
+            left,
+            right,
+        } => {
+            if let (ExpressionKind::Constant(left), ExpressionKind::Constant(right)) =
+                (&left.kind, &right.kind)
+            {
+                return Constant::evaluate_binary(*operator, *left, *right).map(constant);
+            }
+            match (operator, constant_value(left), constant_value(right)) {
+                (BinaryOperator::Add, Some(0), _) | (BinaryOperator::Multiply, Some(1), _) => {
+                    Some(take_expression(right).with_location(location))
+                }
+                (BinaryOperator::Add | BinaryOperator::Subtract, _, Some(0))
+                | (BinaryOperator::Multiply | BinaryOperator::Divide, _, Some(1)) => {
+                    Some(take_expression(left).with_location(location))
+                }
+                (BinaryOperator::NotEqual, _, Some(0)) if is_boolean(left) => {
+                    Some(take_expression(left).with_location(location))
+                }
+                _ => None,
+            }
+        }
+        ExpressionKind::Unary { operator, operand } => match (operator, &operand.kind) {
+            (UnaryOperator::Eqz, ExpressionKind::Constant(value)) => value
+                .as_i64()
+                .map(|value| constant(Constant::I32((value == 0) as i32))),
+            _ => None,
+        },
+        ExpressionKind::Convert { operand, signed } => match operand.kind {
+            ExpressionKind::Constant(value) => {
+                Some(constant(value.convert(expression.ty, *signed)))
+            }
+            _ => None,
+        },
+        ExpressionKind::If {
+            condition,
+            then_block,
+            else_block,
+        } => {
+            let taken = match constant_value(condition)? {
+                0 => else_block,
+                _ => then_block,
+            };
+            let ty = expression.ty;
+            Some(block_expression(std::mem::take(taken), ty, location))
+        }
+        ExpressionKind::Block(block) if block.statements.is_empty() => {
+            let result = block.result.take()?;
+            Some(result.with_location(location))
+        }
+        ExpressionKind::Switch {
+            index,
+            table,
+            default,
+            cases,
+        } => {
+            // the index is an unsigned offset into the table
+            let case = match constant_value(index)? as u32 as usize {
+                index if index < table.len() => table[index],
+                _ => *default,
+            };
+            let ty = expression.ty;
+            Some(block_expression(
+                std::mem::take(&mut cases[case]),
+                ty,
+                location,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Build an expression evaluating a block, or just its result if it has no statements.
+fn block_expression(mut block: Block, ty: ir::Type, location: ir::Location) -> Expression {
+    match block.result.take() {
+        Some(result) if block.statements.is_empty() => *result,
+        result => {
+            block.result = result;
+            Expression {
+                kind: ExpressionKind::Block(block),
+                ty,
+                location,
+            }
+        }
+    }
+}
+
+trait WithLocation {
+    /// Keep the location of the replaced expression, so that it still covers the whole source
+    /// expression.
+    fn with_location(self, location: ir::Location) -> Expression;
+}
+
+impl WithLocation for Expression {
+    fn with_location(self, location: ir::Location) -> Expression {
+        Expression { location, ..self }
+    }
+}
+
+impl WithLocation for Box<Expression> {
+    fn with_location(self, location: ir::Location) -> Expression {
+        (*self).with_location(location)
+    }
+}
+
+/// Value of an integer constant. Floats have none, so that identities such as `x + 0` are only
+/// applied to integers, for which they hold for every `x`.
+fn constant_value(expression: &Expression) -> Option<i64> {
+    match expression.kind {
+        ExpressionKind::Constant(constant) => constant.as_i64(),
+        _ => None,
+    }
+}
+
+/// Whether the expression always evaluates to 0 or 1.
+fn is_boolean(expression: &Expression) -> bool {
+    match &expression.kind {
+        ExpressionKind::Constant(Constant::I32(value)) => *value == 0 || *value == 1,
+        ExpressionKind::Binary { operator, .. } => operator.is_comparison(),
+        ExpressionKind::Unary {
+            operator: UnaryOperator::Eqz,
+            ..
+        } => true,
+        ExpressionKind::If {
+            then_block,
+            else_block,
+            ..
+        } => [then_block, else_block]
+            .iter()
+            .all(|block| block.result.as_deref().is_some_and(is_boolean)),
+        ExpressionKind::Block(block) => block.result.as_deref().is_some_and(is_boolean),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fold_constants_evaluates_arithmetic() {
+        // (1 + 2) * 3 - 0 - x
+        let mut function = function(
+            vec![local("x", ir::Type::I32)],
+            vec![],
+            binary(
+                BinaryOperator::Subtract,
+                binary(
+                    BinaryOperator::Subtract,
+                    binary(
+                        BinaryOperator::Multiply,
+                        binary(BinaryOperator::Add, i32(1), i32(2)),
+                        i32(3),
+                    ),
+                    i32(0),
+                ),
+                get(0, ir::Type::I32),
+            ),
+        );
+        fold_constants(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f(%0 x: i32) -> i32 {\n    i32.sub(9i32, %0)\n}\n"
+        );
+    }
+
+    #[test]
+    fn fold_constants_evaluates_unsigned_and_float_operations() {
+        // (-1 as u32) / 2 > 1; (3 as f64) / 2.0
+        let mut function = function(
+            vec![],
+            vec![Statement::Expression(binary(
+                BinaryOperator::GreaterThanUnsigned,
+                binary(BinaryOperator::DivideUnsigned, i32(-1), i32(2)),
+                i32(1),
+            ))],
+            binary(
+                BinaryOperator::Divide,
+                convert(i32(3), ir::Type::F64, true),
+                f64(2.0),
+            ),
+        );
+        fold_constants(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f() -> f64 {\n    1i32;\n    1.5f64\n}\n"
+        );
+    }
+
+    #[test]
+    fn fold_constants_evaluates_remainder_and_bitwise_operations() {
+        // -7 % 3; (-16 >> 2) ^ (1 << 33)
+        let mut function = function(
+            vec![],
+            vec![Statement::Expression(binary(
+                BinaryOperator::Remainder,
+                i32(-7),
+                i32(3),
+            ))],
+            binary(
+                BinaryOperator::Xor,
+                binary(BinaryOperator::ShiftRight, i32(-16), i32(2)),
+                binary(BinaryOperator::ShiftLeft, i32(1), i32(33)),
+            ),
+        );
+        fold_constants(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f() -> i32 {\n    -1i32;\n    -2i32\n}\n"
+        );
+    }
+
+    #[test]
+    fn fold_constants_keeps_division_by_zero() {
+        let mut function = function(
+            vec![],
+            vec![],
+            binary(
+                BinaryOperator::Add,
+                binary(BinaryOperator::Divide, i32(1), i32(0)),
+                binary(BinaryOperator::Remainder, i32(1), i32(0)),
+            ),
+        );
+        fold_constants(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f() -> i32 {\n    i32.add(i32.div(1i32, 0i32), i32.rem(1i32, 0i32))\n}\n"
+        );
+    }
+
+    #[test]
+    fn fold_constants_removes_boolean_normalization() {
+        // (x < 1) != 0
+        let mut function = function(
+            vec![local("x", ir::Type::I32)],
+            vec![],
+            binary(
+                BinaryOperator::NotEqual,
+                binary(BinaryOperator::LessThan, get(0, ir::Type::I32), i32(1)),
+                i32(0),
+            ),
+        );
+        fold_constants(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f(%0 x: i32) -> i32 {\n    i32.lt(%0, 1i32)\n}\n"
+        );
+    }
+
+    #[test]
+    fn fold_constants_selects_taken_branch() {
+        let mut function = function(
+            vec![],
+            vec![Statement::If {
+                condition: i32(0),
+                then_block: block(vec![Statement::Expression(call("print_int", i32(1)))]),
+                else_block: Some(block(vec![Statement::Expression(call(
+                    "print_int",
+                    i32(2),
+                ))])),
+            }],
+            if_expression(i32(1), i32(3), i32(4)),
+        );
+        fold_constants(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f() -> i32 {\n    print_int(2i32);\n    3i32\n}\n"
+        );
+    }
+}
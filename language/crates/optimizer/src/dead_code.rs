@@ -0,0 +1,325 @@
+//! Dead code elimination.
+//!
+//! Removes statements whose value is discarded and that have no effect, stores to locals that are
+//! never read, and the locals themselves.
+
+use ir::{Block, Expression, ExpressionKind, LocalId, Statement};
+
+use crate::visit::{is_pure, take_expression, visit_block_mut, visit_locals_mut};
+
+pub(crate) fn eliminate_dead_code(function: &mut ir::Function) {
+    loop {
+        remove_dead_statements(&mut function.body);
+        if !remove_unused_locals(function) {
+            break;
+        }
+    }
+}
+
+fn remove_dead_statements(block: &mut Block) {
+    let statements = std::mem::take(&mut block.statements);
+    for statement in statements {
+        match statement {
+            Statement::Expression(mut expression) => {
+                remove_dead_statements_in_expression(&mut expression);
+                match expression.kind {
+                    // the value of the assignment is discarded
+                    ExpressionKind::LocalTee { local, value } => {
+                        block.statements.push(Statement::LocalSet {
+                            local,
+                            value: *value,
+                        });
+                    }
+                    _ if is_pure(&expression) => {}
+                    _ => block.statements.push(Statement::Expression(expression)),
+                }
+            }
+            Statement::LocalSet { local, mut value } => {
+                remove_dead_statements_in_expression(&mut value);
+                block.statements.push(Statement::LocalSet { local, value });
+            }
+            Statement::GlobalSet { global, mut value } => {
+                remove_dead_statements_in_expression(&mut value);
+                block
+                    .statements
+                    .push(Statement::GlobalSet { global, value });
+            }
+            Statement::Store {
+                mut address,
+                mut value,
+                offset,
+            } => {
+                remove_dead_statements_in_expression(&mut address);
+                remove_dead_statements_in_expression(&mut value);
+                block.statements.push(Statement::Store {
+                    address,
+                    value,
+                    offset,
+                });
+            }
+            Statement::Call {
+                function,
+                mut arguments,
+                results,
+                location,
+            } => {
+                arguments
+                    .iter_mut()
+                    .for_each(remove_dead_statements_in_expression);
+                block.statements.push(Statement::Call {
+                    function,
+                    arguments,
+                    results,
+                    location,
+                });
+            }
+            Statement::Return {
+                mut values,
+                location,
+            } => {
+                values
+                    .iter_mut()
+                    .for_each(remove_dead_statements_in_expression);
+                block
+                    .statements
+                    .push(Statement::Return { values, location });
+            }
+            Statement::ReturnCall {
+                function,
+                mut arguments,
+                location,
+            } => {
+                arguments
+                    .iter_mut()
+                    .for_each(remove_dead_statements_in_expression);
+                block.statements.push(Statement::ReturnCall {
+                    function,
+                    arguments,
+                    location,
+                });
+            }
+            Statement::If {
+                mut condition,
+                mut then_block,
+                mut else_block,
+            } => {
+                remove_dead_statements_in_expression(&mut condition);
+                remove_dead_statements(&mut then_block);
+                if let Some(block) = &mut else_block {
+                    remove_dead_statements(block);
+                }
+                let else_block = else_block.filter(|block| !block.statements.is_empty());
+                if then_block.statements.is_empty() && else_block.is_none() {
+                    if !is_pure(&condition) {
+                        block.statements.push(Statement::Expression(condition));
+                    }
+                } else {
+                    block.statements.push(Statement::If {
+                        condition,
+                        then_block,
+                        else_block,
+                    });
+                }
+            }
+        }
+    }
+    if let Some(result) = &mut block.result {
+        remove_dead_statements_in_expression(result);
+    }
+}
+
+fn remove_dead_statements_in_expression(expression: &mut Expression) {
+    visit_block_mut_shallow(expression, &mut remove_dead_statements);
+}
+
+/// Call `f` on the blocks directly nested in the expression or its operands.
+fn visit_block_mut_shallow(expression: &mut Expression, f: &mut impl FnMut(&mut Block)) {
+    match &mut expression.kind {
+        ExpressionKind::Constant(_)
+        | ExpressionKind::LocalGet(_)
+        | ExpressionKind::GlobalGet(_) => {}
+        ExpressionKind::LocalTee { value, .. } => visit_block_mut_shallow(value, f),
+        ExpressionKind::Binary { left, right, .. } => {
+            visit_block_mut_shallow(left, f);
+            visit_block_mut_shallow(right, f);
+        }
+        ExpressionKind::Unary { operand, .. } | ExpressionKind::Convert { operand, .. } => {
+            visit_block_mut_shallow(operand, f)
+        }
+        ExpressionKind::Load { address, .. } => visit_block_mut_shallow(address, f),
+        ExpressionKind::Call { arguments, .. } => {
+            for argument in arguments {
+                visit_block_mut_shallow(argument, f);
+            }
+        }
+        ExpressionKind::If {
+            condition,
+            then_block,
+            else_block,
+        } => {
+            visit_block_mut_shallow(condition, f);
+            f(then_block);
+            f(else_block);
+        }
+        ExpressionKind::Block(block) => f(block),
+        ExpressionKind::Switch { index, cases, .. } => {
+            visit_block_mut_shallow(index, f);
+            cases.iter_mut().for_each(f);
+        }
+    }
+}
+
+/// Remove the locals that are never read, keeping the evaluation of any value stored to them.
+///
+/// Returns whether any local was removed.
+fn remove_unused_locals(function: &mut ir::Function) -> bool {
+    let mut reads = vec![0; function.locals.len()];
+    visit_block_mut(&mut function.body, &mut |expression| {
+        if let ExpressionKind::LocalGet(local) = expression.kind {
+            reads[local.0] += 1;
+        }
+    });
+    let unused: Vec<bool> = reads
+        .iter()
+        .enumerate()
+        .map(|(index, reads)| index >= function.parameter_count && *reads == 0)
+        .collect();
+    if !unused.contains(&true) {
+        return false;
+    }
+
+    remove_stores(&mut function.body, &unused);
+
+    // renumber the remaining locals
+    let mut ids = Vec::with_capacity(function.locals.len());
+    let mut next = 0;
+    for unused in &unused {
+        ids.push(LocalId(next));
+        if !unused {
+            next += 1;
+        }
+    }
+    visit_locals_mut(&mut function.body, &mut |local| *local = ids[local.0]);
+    let mut unused = unused.into_iter();
+    function.locals.retain(|_| !unused.next().unwrap_or(false));
+    true
+}
+
+/// Replace stores to unused locals by the evaluation of the stored value.
+fn remove_stores(block: &mut Block, unused: &[bool]) {
+    remove_set_statements(block, unused);
+    visit_block_mut(block, &mut |expression| match &mut expression.kind {
+        ExpressionKind::LocalTee { local, value } if unused[local.0] => {
+            *expression = take_expression(value);
+        }
+        ExpressionKind::If {
+            then_block,
+            else_block,
+            ..
+        } => {
+            remove_set_statements(then_block, unused);
+            remove_set_statements(else_block, unused);
+        }
+        ExpressionKind::Block(block) => remove_set_statements(block, unused),
+        ExpressionKind::Switch { cases, .. } => {
+            for case in cases {
+                remove_set_statements(case, unused);
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Replace `local.set`s of unused locals in the block and its nested `if` statements, and
+/// discard the values calls would store into them.
+fn remove_set_statements(block: &mut Block, unused: &[bool]) {
+    for statement in &mut block.statements {
+        match statement {
+            Statement::LocalSet { local, value } if unused[local.0] => {
+                *statement = Statement::Expression(take_expression(value));
+            }
+            Statement::Call { results, .. } => {
+                for result in results {
+                    if result.is_some_and(|local| unused[local.0]) {
+                        *result = None;
+                    }
+                }
+            }
+            Statement::If {
+                then_block,
+                else_block,
+                ..
+            } => {
+                remove_set_statements(then_block, unused);
+                if let Some(else_block) = else_block {
+                    remove_set_statements(else_block, unused);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn eliminate_dead_code_removes_unused_locals() {
+        // let a = 1; let b = print_int(2); let c = 3; c
+        let mut function = function(
+            vec![
+                local("a", ir::Type::I32),
+                local("b", ir::Type::I32),
+                local("c", ir::Type::I32),
+            ],
+            vec![
+                Statement::LocalSet {
+                    local: LocalId(0),
+                    value: i32(1),
+                },
+                Statement::LocalSet {
+                    local: LocalId(1),
+                    value: call("print_int", i32(2)),
+                },
+                Statement::LocalSet {
+                    local: LocalId(2),
+                    value: i32(3),
+                },
+            ],
+            get(2, ir::Type::I32),
+        );
+        function.parameter_count = 0;
+        eliminate_dead_code(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f() -> i32 {\n    local %0 c: i32\n    print_int(2i32);\n    %0 = 3i32;\n    %0\n}\n"
+        );
+    }
+
+    #[test]
+    fn eliminate_dead_code_replaces_discarded_assignment() {
+        let mut function = function(
+            vec![local("x", ir::Type::I32)],
+            vec![
+                Statement::Expression(Expression {
+                    kind: ExpressionKind::LocalTee {
+                        local: LocalId(0),
+                        value: Box::new(i32(1)),
+                    },
+                    ty: ir::Type::I32,
+                    location: location(),
+                }),
+                Statement::Expression(get(0, ir::Type::I32)),
+            ],
+            get(0, ir::Type::I32),
+        );
+        eliminate_dead_code(&mut function);
+        assert_eq!(
+            function.to_string(),
+            "fn f(%0 x: i32) -> i32 {\n    %0 = 1i32;\n    %0\n}\n"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Inlining of small functions.
+//!
+//! Calls to small functions that do not call other functions of the program are replaced by a
+//! block evaluating the body of the callee, whose locals are appended to the caller. The callee
+//! itself is kept, since every function is exported.
+
+use std::collections::{HashMap, HashSet};
+
+use ir::{Block, Expression, ExpressionKind, Local, LocalId, Statement};
+
+use crate::visit::{visit_block_mut, visit_locals_mut};
+
+/// Maximum number of expressions in the body of an inlined function.
+const INLINE_THRESHOLD: usize = 24;
+
+pub(crate) fn inline_functions(program: &mut ir::Program) {
+    let names: HashSet<&str> = program
+        .functions
+        .iter()
+        .map(|function| function.name.as_str())
+        .collect();
+    let candidates: HashMap<String, ir::Function> = program
+        .functions
+        .iter()
+        .filter(|function| is_inlinable(function, &names))
+        .map(|function| (function.name.clone(), function.clone()))
+        .collect();
+
+    for function in &mut program.functions {
+        let locals = &mut function.locals;
+        visit_block_mut(&mut function.body, &mut |expression| {
+            let ExpressionKind::Call {
+                function: name,
+                arguments,
+            } = &mut expression.kind
+            else {
+                return;
+            };
+            let Some(callee) = candidates.get(name.as_str()) else {
+                return;
+            };
+            let arguments = std::mem::take(arguments);
+            *expression = inline_call(callee, arguments, locals, expression);
+        });
+    }
+}
+
+/// Whether the function is small, evaluates to a value and only calls the standard library, so
+/// that inlining it does not need to be repeated and cannot recurse.
+///
+/// Calls to functions without a return value are statements, which are not visited: such
+/// functions are never inlined themselves.
+fn is_inlinable(function: &ir::Function, names: &HashSet<&str>) -> bool {
+    let mut body = function.body.clone();
+    let mut size = 0;
+    let mut calls_program = false;
+    visit_block_mut(&mut body, &mut |expression| {
+        size += 1;
+        if let ExpressionKind::Call { function, .. } = &expression.kind {
+            calls_program |= names.contains(function.as_str());
+        }
+    });
+    body.result.is_some() && !calls_program && size <= INLINE_THRESHOLD
+}
+
+fn inline_call(
+    callee: &ir::Function,
+    arguments: Vec<Expression>,
+    locals: &mut Vec<Local>,
+    call: &Expression,
+) -> Expression {
+    let base = locals.len();
+    locals.extend(callee.locals.iter().cloned());
+    let mut body = callee.body.clone();
+    visit_locals_mut(&mut body, &mut |local| local.0 += base);
+
+    // Constants and locals of the caller cannot be changed by the callee, so they can be used in
+    // place of the parameters. Other arguments are evaluated once, in order, into the parameters.
+    let substitute = arguments.iter().all(|argument| {
+        matches!(
+            argument.kind,
+            ExpressionKind::Constant(_) | ExpressionKind::LocalGet(_)
+        )
+    }) && !assigns_parameters(callee);
+    let mut statements = Vec::new();
+    if substitute {
+        let parameters = base..base + callee.parameter_count;
+        visit_block_mut(&mut body, &mut |expression| {
+            if let ExpressionKind::LocalGet(local) = expression.kind {
+                if parameters.contains(&local.0) {
+                    *expression = arguments[local.0 - base].clone();
+                }
+            }
+        });
+    } else {
+        statements.extend(arguments.into_iter().enumerate().map(|(index, value)| {
+            Statement::LocalSet {
+                local: LocalId(base + index),
+                value,
+            }
+        }));
+    }
+    statements.append(&mut body.statements);
+
+    Expression {
+        kind: ExpressionKind::Block(Block {
+            statements,
+            result: body.result,
+        }),
+        ty: call.ty,
+        location: call.location.clone(),
+    }
+}
+
+fn assigns_parameters(function: &ir::Function) -> bool {
+    let mut body = function.body.clone();
+    let mut stores = vec![0isize; function.locals.len()];
+    visit_locals_mut(&mut body, &mut |local| stores[local.0] += 1);
+    visit_block_mut(&mut body, &mut |expression| {
+        if let ExpressionKind::LocalGet(local) = expression.kind {
+            stores[local.0] -= 1;
+        }
+    });
+    stores[..function.parameter_count]
+        .iter()
+        .any(|stores| *stores > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use ir::{BinaryOperator, Type};
+    use pretty_assertions::assert_eq;
+
+    fn square() -> ir::Function {
+        let mut square = function(
+            vec![local("x", Type::I32)],
+            vec![],
+            binary(
+                BinaryOperator::Multiply,
+                get(0, Type::I32),
+                get(0, Type::I32),
+            ),
+        );
+        square.name = "square".to_string();
+        square
+    }
+
+    #[test]
+    fn inline_functions_substitutes_constant_arguments() {
+        let mut main = function(vec![], vec![], call("square", i32(3)));
+        main.name = "main".to_string();
+        let mut program = ir::Program {
+            globals: vec![],
+            functions: vec![square(), main],
+        };
+        inline_functions(&mut program);
+        assert_eq!(
+            program.functions[1].to_string(),
+            "fn main() -> i32 {\n    local %0 x: i32\n    block -> i32 {\n        i32.mul(3i32, 3i32)\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn inline_functions_evaluates_other_arguments_once() {
+        let mut main = function(vec![], vec![], call("square", call("print_int", i32(3))));
+        main.name = "main".to_string();
+        let mut program = ir::Program {
+            globals: vec![],
+            functions: vec![square(), main],
+        };
+        inline_functions(&mut program);
+        assert_eq!(
+            program.functions[1].to_string(),
+            "fn main() -> i32 {\n    local %0 x: i32\n    block -> i32 {\n        %0 = print_int(3i32);\n        i32.mul(%0, %0)\n    }\n}\n"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Optimization passes over the IR.
+//!
+//! - `-O0` leaves the program as lowered.
+//! - `-O1` folds constants and removes dead code and unused locals.
+//! - `-O2` additionally inlines small functions before running the `-O1` passes.
+
+mod constant_folding;
+mod dead_code;
+mod inline;
+mod visit;
+
+use constant_folding::fold_constants;
+use dead_code::eliminate_dead_code;
+use inline::inline_functions;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptimizationLevel {
+    #[default]
+    O0,
+    O1,
+    O2,
+}
+
+impl std::str::FromStr for OptimizationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Self::O0),
+            "1" => Ok(Self::O1),
+            "2" => Ok(Self::O2),
+            _ => Err("Invalid optimization level".to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for OptimizationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::O0 => write!(f, "0"),
+            Self::O1 => write!(f, "1"),
+            Self::O2 => write!(f, "2"),
+        }
+    }
+}
+
+impl TryFrom<u8> for OptimizationLevel {
+    type Error = String;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        level.to_string().parse()
+    }
+}
+
+pub fn optimize(program: &mut ir::Program, level: OptimizationLevel) {
+    if level >= OptimizationLevel::O2 {
+        inline_functions(program);
+    }
+    if level >= OptimizationLevel::O1 {
+        for function in &mut program.functions {
+            fold_constants(function);
+            eliminate_dead_code(function);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Builders for the IR used in the tests of the passes.
+
+    use ir::{
+        BinaryOperator, Block, Constant, Expression, ExpressionKind, Local, LocalId, Location,
+        Statement, Type,
+    };
+    use tokenizer::position::Position;
+
+    pub fn location() -> Location {
+        Location {
+            start: Position::new(0, 1, 1),
+            end: Position::new(0, 1, 1),
+        }
+    }
+
+    /// A function `f` taking all of `locals` as parameters.
+    pub fn function(
+        locals: Vec<Local>,
+        statements: Vec<Statement>,
+        result: Expression,
+    ) -> ir::Function {
+        ir::Function {
+            name: "f".to_string(),
+            parameter_count: locals.len(),
+            locals,
+            results: vec![result.ty],
+            body: Block {
+                statements,
+                result: Some(Box::new(result)),
+            },
+            location: location(),
+        }
+    }
+
+    pub fn local(name: &str, ty: Type) -> Local {
+        Local {
+            name: name.to_string(),
+            ty,
+        }
+    }
+
+    fn expression(kind: ExpressionKind, ty: Type) -> Expression {
+        Expression {
+            kind,
+            ty,
+            location: location(),
+        }
+    }
+
+    pub fn i32(value: i32) -> Expression {
+        expression(ExpressionKind::Constant(Constant::I32(value)), Type::I32)
+    }
+
+    pub fn f64(value: f64) -> Expression {
+        expression(ExpressionKind::Constant(Constant::F64(value)), Type::F64)
+    }
+
+    pub fn get(local: usize, ty: Type) -> Expression {
+        expression(ExpressionKind::LocalGet(LocalId(local)), ty)
+    }
+
+    pub fn binary(operator: BinaryOperator, left: Expression, right: Expression) -> Expression {
+        let ty = if operator.is_comparison() {
+            Type::I32
+        } else {
+            left.ty
+        };
+        expression(
+            ExpressionKind::Binary {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+            ty,
+        )
+    }
+
+    pub fn convert(operand: Expression, ty: Type, signed: bool) -> Expression {
+        expression(
+            ExpressionKind::Convert {
+                operand: Box::new(operand),
+                signed,
+            },
+            ty,
+        )
+    }
+
+    pub fn call(function: &str, argument: Expression) -> Expression {
+        expression(
+            ExpressionKind::Call {
+                function: function.to_string(),
+                arguments: vec![argument],
+            },
+            Type::I32,
+        )
+    }
+
+    pub fn block(statements: Vec<Statement>) -> Block {
+        Block {
+            statements,
+            result: None,
+        }
+    }
+
+    pub fn if_expression(
+        condition: Expression,
+        then_result: Expression,
+        else_result: Expression,
+    ) -> Expression {
+        let ty = then_result.ty;
+        expression(
+            ExpressionKind::If {
+                condition: Box::new(condition),
+                then_block: Block {
+                    statements: vec![],
+                    result: Some(Box::new(then_result)),
+                },
+                else_block: Block {
+                    statements: vec![],
+                    result: Some(Box::new(else_result)),
+                },
+            },
+            ty,
+        )
+    }
+}
@@ -0,0 +1,222 @@
+//! Traversal helpers shared by the passes.
+
+use ir::{Block, Expression, ExpressionKind, LocalId, Statement};
+
+/// Call `f` on every expression in the block, children before their parents.
+pub(crate) fn visit_block_mut(block: &mut Block, f: &mut impl FnMut(&mut Expression)) {
+    for statement in &mut block.statements {
+        visit_statement_mut(statement, f);
+    }
+    if let Some(result) = &mut block.result {
+        visit_expression_mut(result, f);
+    }
+}
+
+pub(crate) fn visit_statement_mut(statement: &mut Statement, f: &mut impl FnMut(&mut Expression)) {
+    match statement {
+        Statement::Expression(expression) => visit_expression_mut(expression, f),
+        Statement::LocalSet { value, .. } | Statement::GlobalSet { value, .. } => {
+            visit_expression_mut(value, f)
+        }
+        Statement::Store { address, value, .. } => {
+            visit_expression_mut(address, f);
+            visit_expression_mut(value, f);
+        }
+        Statement::Call { arguments, .. }
+        | Statement::ReturnCall { arguments, .. }
+        | Statement::Return {
+            values: arguments, ..
+        } => {
+            for argument in arguments {
+                visit_expression_mut(argument, f);
+            }
+        }
+        Statement::If {
+            condition,
+            then_block,
+            else_block,
+        } => {
+            visit_expression_mut(condition, f);
+            visit_block_mut(then_block, f);
+            if let Some(else_block) = else_block {
+                visit_block_mut(else_block, f);
+            }
+        }
+    }
+}
+
+pub(crate) fn visit_expression_mut(
+    expression: &mut Expression,
+    f: &mut impl FnMut(&mut Expression),
+) {
+    match &mut expression.kind {
+        ExpressionKind::Constant(_)
+        | ExpressionKind::LocalGet(_)
+        | ExpressionKind::GlobalGet(_) => {}
+        ExpressionKind::LocalTee { value, .. } => visit_expression_mut(value, f),
+        ExpressionKind::Binary { left, right, .. } => {
+            visit_expression_mut(left, f);
+            visit_expression_mut(right, f);
+        }
+        ExpressionKind::Unary { operand, .. } | ExpressionKind::Convert { operand, .. } => {
+            visit_expression_mut(operand, f)
+        }
+        ExpressionKind::Load { address, .. } => visit_expression_mut(address, f),
+        ExpressionKind::Call { arguments, .. } => {
+            for argument in arguments {
+                visit_expression_mut(argument, f);
+            }
+        }
+        ExpressionKind::If {
+            condition,
+            then_block,
+            else_block,
+        } => {
+            visit_expression_mut(condition, f);
+            visit_block_mut(then_block, f);
+            visit_block_mut(else_block, f);
+        }
+        ExpressionKind::Block(block) => visit_block_mut(block, f),
+        ExpressionKind::Switch { index, cases, .. } => {
+            visit_expression_mut(index, f);
+            for case in cases {
+                visit_block_mut(case, f);
+            }
+        }
+    }
+    f(expression);
+}
+
+/// Call `f` on every reference to a local in the block, including stores.
+pub(crate) fn visit_locals_mut(block: &mut Block, f: &mut impl FnMut(&mut LocalId)) {
+    for statement in &mut block.statements {
+        match statement {
+            Statement::Expression(expression) => visit_expression_locals_mut(expression, f),
+            Statement::LocalSet { local, value } => {
+                visit_expression_locals_mut(value, f);
+                f(local);
+            }
+            Statement::GlobalSet { value, .. } => visit_expression_locals_mut(value, f),
+            Statement::Store { address, value, .. } => {
+                visit_expression_locals_mut(address, f);
+                visit_expression_locals_mut(value, f);
+            }
+            Statement::Call {
+                arguments, results, ..
+            } => {
+                for argument in arguments {
+                    visit_expression_locals_mut(argument, f);
+                }
+                results.iter_mut().flatten().for_each(&mut *f);
+            }
+            Statement::Return {
+                values: arguments, ..
+            }
+            | Statement::ReturnCall { arguments, .. } => {
+                for argument in arguments {
+                    visit_expression_locals_mut(argument, f);
+                }
+            }
+            Statement::If {
+                condition,
+                then_block,
+                else_block,
+            } => {
+                visit_expression_locals_mut(condition, f);
+                visit_locals_mut(then_block, f);
+                if let Some(else_block) = else_block {
+                    visit_locals_mut(else_block, f);
+                }
+            }
+        }
+    }
+    if let Some(result) = &mut block.result {
+        visit_expression_locals_mut(result, f);
+    }
+}
+
+fn visit_expression_locals_mut(expression: &mut Expression, f: &mut impl FnMut(&mut LocalId)) {
+    match &mut expression.kind {
+        ExpressionKind::Constant(_) | ExpressionKind::GlobalGet(_) => {}
+        ExpressionKind::LocalGet(local) => f(local),
+        ExpressionKind::LocalTee { local, value } => {
+            visit_expression_locals_mut(value, f);
+            f(local);
+        }
+        ExpressionKind::Binary { left, right, .. } => {
+            visit_expression_locals_mut(left, f);
+            visit_expression_locals_mut(right, f);
+        }
+        ExpressionKind::Unary { operand, .. } | ExpressionKind::Convert { operand, .. } => {
+            visit_expression_locals_mut(operand, f)
+        }
+        ExpressionKind::Load { address, .. } => visit_expression_locals_mut(address, f),
+        ExpressionKind::Call { arguments, .. } => {
+            for argument in arguments {
+                visit_expression_locals_mut(argument, f);
+            }
+        }
+        ExpressionKind::If {
+            condition,
+            then_block,
+            else_block,
+        } => {
+            visit_expression_locals_mut(condition, f);
+            visit_locals_mut(then_block, f);
+            visit_locals_mut(else_block, f);
+        }
+        ExpressionKind::Block(block) => visit_locals_mut(block, f),
+        ExpressionKind::Switch { index, cases, .. } => {
+            visit_expression_locals_mut(index, f);
+            for case in cases {
+                visit_locals_mut(case, f);
+            }
+        }
+    }
+}
+
+/// Whether evaluating the expression can be skipped without changing the behavior of the
+/// program: it does not call functions, store to locals, or trap.
+///
+/// Loads are not pure, since they trap when the address is out of bounds.
+pub(crate) fn is_pure(expression: &Expression) -> bool {
+    match &expression.kind {
+        ExpressionKind::Constant(_)
+        | ExpressionKind::LocalGet(_)
+        | ExpressionKind::GlobalGet(_) => true,
+        ExpressionKind::LocalTee { .. }
+        | ExpressionKind::Call { .. }
+        | ExpressionKind::Load { .. } => false,
+        ExpressionKind::Binary {
+            operator,
+            left,
+            right,
+        } => !operator.is_division() && is_pure(left) && is_pure(right),
+        ExpressionKind::Unary { operand, .. } | ExpressionKind::Convert { operand, .. } => {
+            is_pure(operand)
+        }
+        ExpressionKind::If {
+            condition,
+            then_block,
+            else_block,
+        } => is_pure(condition) && is_pure_block(then_block) && is_pure_block(else_block),
+        ExpressionKind::Block(block) => is_pure_block(block),
+        ExpressionKind::Switch { index, cases, .. } => {
+            is_pure(index) && cases.iter().all(is_pure_block)
+        }
+    }
+}
+
+fn is_pure_block(block: &Block) -> bool {
+    block.statements.is_empty() && block.result.as_deref().is_none_or(is_pure)
+}
+
+/// Move the expression out, leaving a placeholder constant in its place.
+pub(crate) fn take_expression(expression: &mut Expression) -> Expression {
+    let placeholder = Expression {
+        kind: ExpressionKind::Constant(ir::Constant::I32(0)),
+        ty: ir::Type::I32,
+        location: expression.location.clone(),
+    };
+    std::mem::replace(expression, placeholder)
+}
@@ -1,8 +1,13 @@
 use ast::{
-    AssignmentExpression, BinaryExpression, Block, Expression, ExpressionStatement, FunctionCall,
-    FunctionDefinition, Identifier, IfElseExpression, IfStatement, IntegerLiteral, Location,
-    Operator, OperatorKind, Parameter, Parameters, Program, Statement, Statements, Type, TypeKind,
-    UnaryExpression, VariableDefinition,
+    ArrayLiteral, ArrayRepeat, AssignmentExpression, BinaryExpression, Block, BooleanLiteral,
+    CastExpression, CompoundAssignmentExpression, ConstantDefinition, DestructuringDefinition,
+    EnumDefinition, EnumVariant, Expression, ExpressionStatement, Field, FieldAccess,
+    FieldAssignmentExpression, FieldInitializer, FloatLiteral, FunctionCall, FunctionDefinition,
+    Identifier, IfElseExpression, IfStatement, IndexAssignmentExpression, IndexExpression,
+    IntegerLiteral, Location, MatchArm, MatchExpression, Operator, OperatorKind, Parameter,
+    Parameters, Pattern, Program, RangePattern, Statement, Statements, StaticDefinition,
+    StructDefinition, StructLiteral, TupleLiteral, Type, TypeKind, UnaryExpression,
+    VariableDefinition, Variant, VariantPattern,
 };
 use tokenizer::{
     position::Position,
@@ -13,6 +18,37 @@ pub fn parse(tokens: Vec<Token>) -> ast::Program {
     Parser::new(tokens).parse()
 }
 
+/// An index or a field following an expression.
+enum Accessor {
+    /// The index and the end position of the closing bracket.
+    Index(Expression, Position),
+    Field(Identifier),
+}
+
+impl Accessor {
+    /// Access the element or the field of the expression.
+    fn apply(self, expression: Expression) -> Expression {
+        match self {
+            Accessor::Index(index, end_position) => Expression::IndexExpression(IndexExpression {
+                location: Location {
+                    start: expression.location().start,
+                    end: end_position,
+                },
+                array: Box::new(expression),
+                index: Box::new(index),
+            }),
+            Accessor::Field(field) => Expression::FieldAccess(FieldAccess {
+                location: Location {
+                    start: expression.location().start,
+                    end: field.location.end,
+                },
+                object: Box::new(expression),
+                field,
+            }),
+        }
+    }
+}
+
 pub struct Parser {
     /// The tokens to parse.
     tokens: Vec<Token>,
@@ -90,26 +126,213 @@ impl Parser {
     }
 
     /// ```bnf
-    /// program = function_definition*
+    /// program = (function_definition | struct_definition | enum_definition | constant_definition
+    ///     | static_definition)*
     /// ```
     fn program(&mut self) -> Program {
         let mut functions: Vec<FunctionDefinition> = Vec::new();
-        while let Some(function) = self.function_definition() {
-            functions.push(function);
+        let mut structs: Vec<StructDefinition> = Vec::new();
+        let mut enums: Vec<EnumDefinition> = Vec::new();
+        let mut constants: Vec<ConstantDefinition> = Vec::new();
+        let mut statics: Vec<StaticDefinition> = Vec::new();
+        loop {
+            if let Some(function) = self.function_definition() {
+                functions.push(function);
+            } else if let Some(struct_definition) = self.struct_definition() {
+                structs.push(struct_definition);
+            } else if let Some(enum_definition) = self.enum_definition() {
+                enums.push(enum_definition);
+            } else if let Some(constant_definition) = self.constant_definition() {
+                constants.push(constant_definition);
+            } else if let Some(static_definition) = self.static_definition() {
+                statics.push(static_definition);
+            } else {
+                break;
+            }
+        }
+        Program {
+            functions,
+            structs,
+            enums,
+            constants,
+            statics,
         }
-        Program { functions }
     }
 
     /// ```bnf
-    /// function_definition = "fn" identifier parameters "->" type block
+    /// constant_definition = "const" identifier ":" type "=" expression ";"
+    /// ```
+    fn constant_definition(&mut self) -> Option<ConstantDefinition> {
+        self.transaction(|tx| {
+            let start_position = tx
+                .consume_token(TokenKind::Keyword, "const")?
+                .start_position;
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, ":")?;
+            let constant_type = tx.r#type()?;
+            tx.consume_token(TokenKind::Operator, "=")?;
+            let value = tx.expression()?;
+            let end_position = tx.consume_token(TokenKind::Delimiter, ";")?.end_position;
+            Some(ConstantDefinition {
+                name,
+                constant_type,
+                value,
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
+    /// ```bnf
+    /// static_definition = "static" "var" identifier ":" type "=" expression ";"
+    /// ```
+    fn static_definition(&mut self) -> Option<StaticDefinition> {
+        self.transaction(|tx| {
+            let start_position = tx
+                .consume_token(TokenKind::Keyword, "static")?
+                .start_position;
+            tx.consume_token(TokenKind::Keyword, "var")?;
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, ":")?;
+            let static_type = tx.r#type()?;
+            tx.consume_token(TokenKind::Operator, "=")?;
+            let value = tx.expression()?;
+            let end_position = tx.consume_token(TokenKind::Delimiter, ";")?.end_position;
+            Some(StaticDefinition {
+                name,
+                static_type,
+                value,
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
+    /// ```bnf
+    /// struct_definition = "struct" identifier "{" field ("," field)* ","? "}"
+    /// ```
+    fn struct_definition(&mut self) -> Option<StructDefinition> {
+        self.transaction(|tx| {
+            let start_position = tx
+                .consume_token(TokenKind::Keyword, "struct")?
+                .start_position;
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, "{")?;
+            let mut fields = vec![tx.field()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                match tx.field() {
+                    Some(field) => fields.push(field),
+                    None => break,
+                }
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, "}")?.end_position;
+            Some(StructDefinition {
+                name,
+                fields,
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
+    /// ```bnf
+    /// field = identifier ":" type
+    /// ```
+    fn field(&mut self) -> Option<Field> {
+        self.transaction(|tx| {
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, ":")?;
+            let field_type = tx.r#type()?;
+            Some(Field {
+                location: Location {
+                    start: name.location.start,
+                    end: field_type.location.end,
+                },
+                name,
+                field_type,
+            })
+        })
+    }
+
+    /// ```bnf
+    /// enum_definition = "enum" identifier "{" variant ("," variant)* ","? "}"
+    /// ```
+    fn enum_definition(&mut self) -> Option<EnumDefinition> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Keyword, "enum")?.start_position;
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, "{")?;
+            let mut variants = vec![tx.variant()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                match tx.variant() {
+                    Some(variant) => variants.push(variant),
+                    None => break,
+                }
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, "}")?.end_position;
+            Some(EnumDefinition {
+                name,
+                variants,
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
+    /// ```bnf
+    /// variant = identifier ("(" type ("," type)* ")")?
+    /// ```
+    fn variant(&mut self) -> Option<Variant> {
+        self.transaction(|tx| {
+            let name = tx.identifier()?;
+            let mut fields = Vec::new();
+            let mut end_position = name.location.end;
+            if tx.consume_token(TokenKind::Delimiter, "(").is_some() {
+                fields.push(tx.r#type()?);
+                while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                    fields.push(tx.r#type()?);
+                }
+                end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            }
+            Some(Variant {
+                location: Location {
+                    start: name.location.start,
+                    end: end_position,
+                },
+                name,
+                fields,
+            })
+        })
+    }
+
+    /// ```bnf
+    /// function_definition = "fn" identifier parameters ("->" type)? block
     /// ```
     fn function_definition(&mut self) -> Option<FunctionDefinition> {
         self.transaction(|tx| {
             tx.consume_token(TokenKind::Keyword, "fn")?;
             let name = tx.identifier()?;
             let parameters = tx.parameters()?;
-            tx.consume_token(TokenKind::Operator, "->")?;
-            let return_type = tx.r#type()?;
+            // without a return type, the function returns `()`, located right after the parameters
+            let return_type = if tx.consume_token(TokenKind::Operator, "->").is_some() {
+                tx.r#type()?
+            } else {
+                Type {
+                    name: TypeKind::Unit,
+                    location: Location {
+                        start: parameters.location.end,
+                        end: parameters.location.end,
+                    },
+                }
+            };
             let body = tx.block()?;
             Some(FunctionDefinition {
                 location: Location {
@@ -256,6 +479,7 @@ impl Parser {
     /// ```bnf
     /// statement =
     ///     variable_definition_statement
+    ///   | destructuring_definition_statement
     ///   | if_statement
     ///   | expression_statement
     /// ```
@@ -263,6 +487,10 @@ impl Parser {
         self.transaction(|tx| {
             tx.variable_definition_statement()
                 .map(Statement::VariableDefinition)
+                .or_else(|| {
+                    tx.destructuring_definition_statement()
+                        .map(Statement::DestructuringDefinition)
+                })
                 .or_else(|| tx.if_statement().map(Statement::IfStatement))
                 .or_else(|| {
                     tx.expression_statement()
@@ -273,8 +501,8 @@ impl Parser {
 
     /// ```bnf
     /// variable_definition_statement =
-    ///     "let" identifier ":" type "=" expression ";"     (* immutable *)
-    ///   | "var" identifier ":" type ("=" expression)? ";"  (* mutable *)
+    ///     "let" identifier (":" type)? "=" expression ";"     (* immutable *)
+    ///   | "var" identifier (":" type)? ("=" expression)? ";"  (* mutable *)
     /// ```
     fn variable_definition_statement(&mut self) -> Option<VariableDefinition> {
         self.transaction(|tx| {
@@ -287,8 +515,11 @@ impl Parser {
                 return None;
             };
             let name = tx.identifier()?;
-            tx.consume_token(TokenKind::Delimiter, ":")?;
-            let variable_type = tx.r#type()?;
+            let variable_type = if tx.consume_token(TokenKind::Delimiter, ":").is_some() {
+                Some(tx.r#type()?)
+            } else {
+                None
+            };
             let value = if tx.consume_token(TokenKind::Operator, "=").is_some() {
                 Some(tx.expression()?)
             } else {
@@ -308,6 +539,44 @@ impl Parser {
         })
     }
 
+    /// ```bnf
+    /// destructuring_definition_statement =
+    ///     ("let" | "var") "(" identifier ("," identifier)+ ")" "=" expression ";"
+    /// ```
+    fn destructuring_definition_statement(&mut self) -> Option<DestructuringDefinition> {
+        self.transaction(|tx| {
+            let start_position = tx.peek_token(0)?.start_position;
+            let mutable = if tx.consume_token(TokenKind::Keyword, "let").is_some() {
+                false
+            } else if tx.consume_token(TokenKind::Keyword, "var").is_some() {
+                true
+            } else {
+                return None;
+            };
+            tx.consume_token(TokenKind::Delimiter, "(")?;
+            let mut names = vec![tx.identifier()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                names.push(tx.identifier()?);
+            }
+            if names.len() < 2 {
+                return None;
+            }
+            tx.consume_token(TokenKind::Delimiter, ")")?;
+            tx.consume_token(TokenKind::Operator, "=")?;
+            let value = tx.expression()?;
+            let end_position = tx.consume_token(TokenKind::Delimiter, ";")?.end_position;
+            Some(DestructuringDefinition {
+                names,
+                mutable,
+                value,
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
     /// ```bnf
     /// if_statement = "if" expression block_without_expression ("else" if_statement | block_without_expression)?
     /// ```
@@ -426,14 +695,14 @@ impl Parser {
     }
 
     /// ```bnf
-    /// comparison_expression = add_expression (("<" | "<=" | ">" | ">=" | "==" | "!=") add_expression)*
+    /// comparison_expression = bit_or_expression (("<" | "<=" | ">" | ">=" | "==" | "!=") bit_or_expression)*
     /// ```
     fn comparison_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let lhs = tx.add_expression()?;
+            let lhs = tx.bit_or_expression()?;
             let mut expression = lhs;
             while let Some(operator) = tx.consume_comparison_operator() {
-                let rhs = tx.add_expression()?;
+                let rhs = tx.bit_or_expression()?;
                 let location = Location {
                     start: expression.location().start,
                     end: rhs.location().end,
@@ -495,14 +764,14 @@ impl Parser {
     }
 
     /// ```bnf
-    /// add_expression = mul_expression (("+" | "-") mul_expression)*
+    /// bit_or_expression = bit_xor_expression ("|" bit_xor_expression)*
     /// ```
-    fn add_expression(&mut self) -> Option<Expression> {
+    fn bit_or_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let lhs = tx.mul_expression()?;
+            let lhs = tx.bit_xor_expression()?;
             let mut expression = lhs;
-            while let Some(operator) = tx.consume_add_operator() {
-                let rhs = tx.mul_expression()?;
+            while let Some(operator) = tx.consume_bit_or_operator() {
+                let rhs = tx.bit_xor_expression()?;
                 let location = Location {
                     start: expression.location().start,
                     end: rhs.location().end,
@@ -518,8 +787,8 @@ impl Parser {
         })
     }
 
-    /// Consume an add operator.
-    fn consume_add_operator(&mut self) -> Option<Operator> {
+    /// Consume a bitwise or operator.
+    fn consume_bit_or_operator(&mut self) -> Option<Operator> {
         let token = {
             let token = self.peek_token(0)?;
             if token.kind != TokenKind::Operator {
@@ -530,12 +799,8 @@ impl Parser {
                 end: token.end_position,
             };
             match token.value.as_str() {
-                "+" => Some(Operator {
-                    operator: OperatorKind::Add,
-                    location,
-                }),
-                "-" => Some(Operator {
-                    operator: OperatorKind::Subtract,
+                "|" => Some(Operator {
+                    operator: OperatorKind::BitOr,
                     location,
                 }),
                 _ => None,
@@ -548,14 +813,14 @@ impl Parser {
     }
 
     /// ```bnf
-    /// mul_expression = unary_expression (("*" | "/") unary_expression)*
+    /// bit_xor_expression = bit_and_expression ("^" bit_and_expression)*
     /// ```
-    fn mul_expression(&mut self) -> Option<Expression> {
+    fn bit_xor_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let lhs = tx.unary_expression()?;
+            let lhs = tx.bit_and_expression()?;
             let mut expression = lhs;
-            while let Some(operator) = tx.consume_mul_operator() {
-                let rhs = tx.unary_expression()?;
+            while let Some(operator) = tx.consume_bit_xor_operator() {
+                let rhs = tx.bit_and_expression()?;
                 let location = Location {
                     start: expression.location().start,
                     end: rhs.location().end,
@@ -571,8 +836,8 @@ impl Parser {
         })
     }
 
-    /// Consume a mul operator.
-    fn consume_mul_operator(&mut self) -> Option<Operator> {
+    /// Consume a bitwise xor operator.
+    fn consume_bit_xor_operator(&mut self) -> Option<Operator> {
         let token = {
             let token = self.peek_token(0)?;
             if token.kind != TokenKind::Operator {
@@ -583,12 +848,8 @@ impl Parser {
                 end: token.end_position,
             };
             match token.value.as_str() {
-                "*" => Some(Operator {
-                    operator: OperatorKind::Multiply,
-                    location,
-                }),
-                "/" => Some(Operator {
-                    operator: OperatorKind::Divide,
+                "^" => Some(Operator {
+                    operator: OperatorKind::BitXor,
                     location,
                 }),
                 _ => None,
@@ -601,352 +862,3166 @@ impl Parser {
     }
 
     /// ```bnf
-    /// unary_expression =
-    ///     "-" unary_expression
-    ///   | "!" unary_expression
-    ///   | primary_expression
+    /// bit_and_expression = shift_expression ("&" shift_expression)*
     /// ```
-    fn unary_expression(&mut self) -> Option<Expression> {
+    fn bit_and_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            if let Some(minus_location) =
-                tx.consume_token(TokenKind::Operator, "-")
-                    .map(|token| Location {
-                        start: token.start_position,
-                        end: token.end_position,
-                    })
-            {
-                let operand = tx.unary_expression()?;
-                Some(Expression::BinaryExpression(BinaryExpression {
-                    location: Location {
-                        start: minus_location.start,
-                        end: operand.location().end,
-                    },
-                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "0".to_string(),
-                        location: Location {
-                            start: minus_location.start,
-                            end: minus_location.start,
-                        },
-                    })),
-                    operator: Operator {
-                        operator: OperatorKind::Subtract,
-                        location: minus_location,
-                    },
-                    right: Box::new(operand),
-                }))
-            } else if let Some(not_location) =
-                tx.consume_token(TokenKind::Operator, "!")
-                    .map(|token| Location {
-                        start: token.start_position,
-                        end: token.end_position,
-                    })
-            {
-                let operand = tx.unary_expression()?;
-                Some(Expression::UnaryExpression(UnaryExpression {
-                    location: Location {
-                        start: not_location.start,
-                        end: operand.location().end,
-                    },
-                    operand: Box::new(operand),
-                    operator: Operator {
-                        operator: OperatorKind::LogicalNot,
-                        location: not_location,
-                    },
-                }))
-            } else {
-                tx.primary_expression()
+            let lhs = tx.shift_expression()?;
+            let mut expression = lhs;
+            while let Some(operator) = tx.consume_bit_and_operator() {
+                let rhs = tx.shift_expression()?;
+                let location = Location {
+                    start: expression.location().start,
+                    end: rhs.location().end,
+                };
+                expression = Expression::BinaryExpression(BinaryExpression {
+                    location,
+                    left: Box::new(expression),
+                    operator,
+                    right: Box::new(rhs),
+                });
             }
+            Some(expression)
         })
     }
 
-    /// ```bnf
-    /// primary_expression =
-    ///     literal
-    ///   | assignment_expression
-    ///   | if_else_expression
-    ///   | function_call
-    ///   | identifier
-    ///   | "(" expression ")"
-    /// ```
-    fn primary_expression(&mut self) -> Option<Expression> {
-        self.literal()
-            .or_else(|| self.assignment_expression())
-            .or_else(|| self.if_else_expression())
-            .or_else(|| self.function_call())
-            .or_else(|| self.identifier().map(Expression::Identifier))
-            .or_else(|| {
-                self.transaction(|tx| {
-                    tx.consume_token(TokenKind::Delimiter, "(")?;
-                    let expression = tx.expression()?;
-                    tx.consume_token(TokenKind::Delimiter, ")")?;
-                    Some(expression)
-                })
-            })
+    /// Consume a bitwise and operator.
+    fn consume_bit_and_operator(&mut self) -> Option<Operator> {
+        let token = {
+            let token = self.peek_token(0)?;
+            if token.kind != TokenKind::Operator {
+                return None;
+            }
+            let location = Location {
+                start: token.start_position,
+                end: token.end_position,
+            };
+            match token.value.as_str() {
+                "&" => Some(Operator {
+                    operator: OperatorKind::BitAnd,
+                    location,
+                }),
+                _ => None,
+            }
+        };
+        if token.is_some() {
+            self.advance_token();
+        }
+        token
     }
 
     /// ```bnf
-    /// assignment_expression = identifier "=" expression
+    /// shift_expression = add_expression (("<<" | ">>") add_expression)*
     /// ```
-    fn assignment_expression(&mut self) -> Option<Expression> {
+    fn shift_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let identifier = tx.identifier()?;
-            tx.consume_token(TokenKind::Operator, "=")?;
-            let expression = tx.expression()?;
-            Some(Expression::AssignmentExpression(AssignmentExpression {
-                location: Location {
-                    start: identifier.location.start,
-                    end: expression.location().end,
-                },
-                name: identifier,
-                value: Box::new(expression),
-            }))
+            let lhs = tx.add_expression()?;
+            let mut expression = lhs;
+            while let Some(operator) = tx.consume_shift_operator() {
+                let rhs = tx.add_expression()?;
+                let location = Location {
+                    start: expression.location().start,
+                    end: rhs.location().end,
+                };
+                expression = Expression::BinaryExpression(BinaryExpression {
+                    location,
+                    left: Box::new(expression),
+                    operator,
+                    right: Box::new(rhs),
+                });
+            }
+            Some(expression)
         })
     }
 
+    /// Consume a shift operator.
+    fn consume_shift_operator(&mut self) -> Option<Operator> {
+        let token = {
+            let token = self.peek_token(0)?;
+            if token.kind != TokenKind::Operator {
+                return None;
+            }
+            let location = Location {
+                start: token.start_position,
+                end: token.end_position,
+            };
+            match token.value.as_str() {
+                "<<" => Some(Operator {
+                    operator: OperatorKind::ShiftLeft,
+                    location,
+                }),
+                ">>" => Some(Operator {
+                    operator: OperatorKind::ShiftRight,
+                    location,
+                }),
+                _ => None,
+            }
+        };
+        if token.is_some() {
+            self.advance_token();
+        }
+        token
+    }
+
     /// ```bnf
-    /// // TODO: remove `"as" type` and instead use a type inference algorithm
-    /// if_else_expression = "if" expression block_with_expression "else" else_if_expression "as" type
+    /// add_expression = mul_expression (("+" | "-") mul_expression)*
     /// ```
-    fn if_else_expression(&mut self) -> Option<Expression> {
+    fn add_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let start_position = tx.consume_token(TokenKind::Keyword, "if")?.start_position;
-            let condition = tx.expression()?;
-            let then_block = tx.block_with_expression()?;
-            tx.consume_token(TokenKind::Keyword, "else")?;
-            let mut else_block = tx.else_if_expression(Type {
-                name: TypeKind::I32,
-                location: Location {
-                    start: Position {
-                        index: 0,
-                        line: 0,
-                        column: 0,
-                    },
-                    end: Position {
-                        index: 0,
-                        line: 0,
-                        column: 0,
-                    },
-                },
-            })?;
-            tx.consume_token(TokenKind::Keyword, "as")?;
-            let return_type = tx.r#type()?;
-            else_block
-                .statements
-                .statements
-                .iter_mut()
-                .for_each(|statement| {
-                    if let Statement::Expression(Expression::IfElseExpression(if_else_expression)) =
-                        statement
-                    {
-                        if_else_expression.return_type = return_type.clone();
-                    }
+            let lhs = tx.mul_expression()?;
+            let mut expression = lhs;
+            while let Some(operator) = tx.consume_add_operator() {
+                let rhs = tx.mul_expression()?;
+                let location = Location {
+                    start: expression.location().start,
+                    end: rhs.location().end,
+                };
+                expression = Expression::BinaryExpression(BinaryExpression {
+                    location,
+                    left: Box::new(expression),
+                    operator,
+                    right: Box::new(rhs),
                 });
-            let end_position = return_type.location.end;
-            Some(Expression::IfElseExpression(IfElseExpression {
-                location: Location {
-                    start: start_position,
-                    end: end_position,
-                },
-                condition: Box::new(condition),
-                then_block,
-                else_block,
-                return_type,
-            }))
+            }
+            Some(expression)
         })
     }
 
+    /// Consume an add operator.
+    fn consume_add_operator(&mut self) -> Option<Operator> {
+        let token = {
+            let token = self.peek_token(0)?;
+            if token.kind != TokenKind::Operator {
+                return None;
+            }
+            let location = Location {
+                start: token.start_position,
+                end: token.end_position,
+            };
+            match token.value.as_str() {
+                "+" => Some(Operator {
+                    operator: OperatorKind::Add,
+                    location,
+                }),
+                "-" => Some(Operator {
+                    operator: OperatorKind::Subtract,
+                    location,
+                }),
+                _ => None,
+            }
+        };
+        if token.is_some() {
+            self.advance_token();
+        }
+        token
+    }
+
     /// ```bnf
-    /// else_if_expression =
-    ///     "if" expression block_with_expression "else" else_if_expression
-    ///   | block_with_expression
+    /// mul_expression = cast_expression (("*" | "/" | "%") cast_expression)*
     /// ```
-    fn else_if_expression(&mut self, return_type: Type) -> Option<Block> {
+    fn mul_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            if let Some(start_position) = tx
-                .consume_token(TokenKind::Keyword, "if")
-                .map(|token| token.start_position)
-            {
-                let condition = tx.expression()?;
-                let then_block = tx.block_with_expression()?;
-                tx.consume_token(TokenKind::Keyword, "else")?;
-                let else_block = tx.else_if_expression(return_type.clone())?;
+            let lhs = tx.cast_expression()?;
+            let mut expression = lhs;
+            while let Some(operator) = tx.consume_mul_operator() {
+                let rhs = tx.cast_expression()?;
                 let location = Location {
-                    start: start_position,
-                    end: else_block.location.end,
+                    start: expression.location().start,
+                    end: rhs.location().end,
                 };
-                let expression = Expression::IfElseExpression(IfElseExpression {
-                    location: location.clone(),
-                    condition: Box::new(condition),
-                    then_block,
-                    else_block,
-                    return_type,
-                });
-                Some(Block {
-                    statements: Statements {
-                        statements: vec![Statement::Expression(expression)],
-                        location: location.clone(),
-                    },
+                expression = Expression::BinaryExpression(BinaryExpression {
                     location,
-                })
-            } else {
-                Some(tx.block_with_expression()?)
+                    left: Box::new(expression),
+                    operator,
+                    right: Box::new(rhs),
+                });
             }
+            Some(expression)
         })
     }
 
+    /// Consume a mul operator.
+    fn consume_mul_operator(&mut self) -> Option<Operator> {
+        let token = {
+            let token = self.peek_token(0)?;
+            if token.kind != TokenKind::Operator {
+                return None;
+            }
+            let location = Location {
+                start: token.start_position,
+                end: token.end_position,
+            };
+            match token.value.as_str() {
+                "*" => Some(Operator {
+                    operator: OperatorKind::Multiply,
+                    location,
+                }),
+                "/" => Some(Operator {
+                    operator: OperatorKind::Divide,
+                    location,
+                }),
+                "%" => Some(Operator {
+                    operator: OperatorKind::Remainder,
+                    location,
+                }),
+                _ => None,
+            }
+        };
+        if token.is_some() {
+            self.advance_token();
+        }
+        token
+    }
+
     /// ```bnf
-    /// function_call = identifier "(" expression* ")"
+    /// cast_expression = unary_expression ("as" type)*
     /// ```
-    fn function_call(&mut self) -> Option<Expression> {
+    fn cast_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let identifier = tx.identifier()?;
-            tx.consume_token(TokenKind::Delimiter, "(")?;
-            let mut arguments: Vec<Expression> = Vec::new();
-            while let Some(expression) = tx.expression() {
-                arguments.push(expression);
-                if tx.consume_token(TokenKind::Delimiter, ",").is_none() {
-                    break;
-                }
+            let mut expression = tx.unary_expression()?;
+            while tx.consume_token(TokenKind::Keyword, "as").is_some() {
+                let target_type = tx.r#type()?;
+                expression = Expression::CastExpression(CastExpression {
+                    location: Location {
+                        start: expression.location().start,
+                        end: target_type.location.end,
+                    },
+                    expression: Box::new(expression),
+                    target_type,
+                });
             }
-            let end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
-            Some(Expression::FunctionCall(FunctionCall {
-                location: Location {
-                    start: identifier.location.start,
-                    end: end_position,
-                },
-                name: identifier,
-                arguments,
-            }))
+            Some(expression)
         })
     }
 
     /// ```bnf
-    /// literal = INTEGER
+    /// unary_expression =
+    ///     "-" unary_expression
+    ///   | "!" unary_expression
+    ///   | postfix_expression
     /// ```
-    /// where `INTEGER` is a `TokenKind::Integer` token.
-    fn literal(&mut self) -> Option<Expression> {
-        self.consume_token_kind(TokenKind::Integer).map(|token| {
-            Expression::IntegerLiteral(IntegerLiteral {
-                location: Location {
-                    start: token.start_position,
-                    end: token.end_position,
-                },
-                value: token.value.clone(),
-            })
+    fn unary_expression(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            if let Some(minus_location) =
+                tx.consume_token(TokenKind::Operator, "-")
+                    .map(|token| Location {
+                        start: token.start_position,
+                        end: token.end_position,
+                    })
+            {
+                let operand = tx.unary_expression()?;
+                Some(Expression::BinaryExpression(BinaryExpression {
+                    location: Location {
+                        start: minus_location.start,
+                        end: operand.location().end,
+                    },
+                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "0".to_string(),
+                        location: Location {
+                            start: minus_location.start,
+                            end: minus_location.start,
+                        },
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Subtract,
+                        location: minus_location,
+                    },
+                    right: Box::new(operand),
+                }))
+            } else if let Some(not_location) =
+                tx.consume_token(TokenKind::Operator, "!")
+                    .map(|token| Location {
+                        start: token.start_position,
+                        end: token.end_position,
+                    })
+            {
+                let operand = tx.unary_expression()?;
+                Some(Expression::UnaryExpression(UnaryExpression {
+                    location: Location {
+                        start: not_location.start,
+                        end: operand.location().end,
+                    },
+                    operand: Box::new(operand),
+                    operator: Operator {
+                        operator: OperatorKind::LogicalNot,
+                        location: not_location,
+                    },
+                }))
+            } else {
+                tx.postfix_expression()
+            }
         })
     }
 
     /// ```bnf
-    /// identifier = IDENTIFIER
+    /// postfix_expression = primary_expression accessor*
     /// ```
-    /// where `IDENTIFIER` is a `TokenKind::Identifier` token.
-    fn identifier(&mut self) -> Option<Identifier> {
-        self.consume_token_kind(TokenKind::Identifier)
-            .map(|token| Identifier {
-                name: token.value.clone(),
+    fn postfix_expression(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let mut expression = tx.primary_expression()?;
+            while let Some(accessor) = tx.accessor() {
+                expression = accessor.apply(expression);
+            }
+            Some(expression)
+        })
+    }
+
+    /// ```bnf
+    /// accessor = "[" expression "]" | "." identifier
+    /// ```
+    fn accessor(&mut self) -> Option<Accessor> {
+        self.transaction(|tx| {
+            if tx.consume_token(TokenKind::Delimiter, ".").is_some() {
+                return Some(Accessor::Field(tx.identifier()?));
+            }
+            tx.consume_token(TokenKind::Delimiter, "[")?;
+            let index = tx.expression()?;
+            let end_position = tx.consume_token(TokenKind::Delimiter, "]")?.end_position;
+            Some(Accessor::Index(index, end_position))
+        })
+    }
+
+    /// ```bnf
+    /// primary_expression =
+    ///     literal
+    ///   | assignment_expression
+    ///   | compound_assignment_expression
+    ///   | if_else_expression
+    ///   | match_expression
+    ///   | block_with_expression
+    ///   | function_call
+    ///   | enum_variant
+    ///   | struct_literal
+    ///   | array_expression
+    ///   | identifier
+    ///   | tuple_literal
+    ///   | "(" expression ")"
+    /// ```
+    fn primary_expression(&mut self) -> Option<Expression> {
+        self.literal()
+            .or_else(|| self.assignment_expression())
+            .or_else(|| self.compound_assignment_expression())
+            .or_else(|| self.if_else_expression())
+            .or_else(|| self.match_expression())
+            .or_else(|| self.block_with_expression().map(Expression::Block))
+            .or_else(|| self.function_call())
+            .or_else(|| self.enum_variant())
+            .or_else(|| self.struct_literal())
+            .or_else(|| self.array_expression())
+            .or_else(|| self.identifier().map(Expression::Identifier))
+            .or_else(|| self.tuple_literal())
+            .or_else(|| {
+                self.transaction(|tx| {
+                    tx.consume_token(TokenKind::Delimiter, "(")?;
+                    let expression = tx.expression()?;
+                    tx.consume_token(TokenKind::Delimiter, ")")?;
+                    Some(expression)
+                })
+            })
+    }
+
+    /// ```bnf
+    /// tuple_literal = "(" expression ("," expression)+ ")"
+    /// ```
+    fn tuple_literal(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Delimiter, "(")?.start_position;
+            let mut elements = vec![tx.expression()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                elements.push(tx.expression()?);
+            }
+            if elements.len() < 2 {
+                return None;
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            Some(Expression::TupleLiteral(TupleLiteral {
+                elements,
                 location: Location {
-                    start: token.start_position,
-                    end: token.end_position,
+                    start: start_position,
+                    end: end_position,
                 },
-            })
+            }))
+        })
     }
 
     /// ```bnf
-    /// type = "i32" | "i64"
+    /// assignment_expression = identifier accessor* "=" expression
     /// ```
-    fn r#type(&mut self) -> Option<Type> {
+    fn assignment_expression(&mut self) -> Option<Expression> {
         self.transaction(|tx| {
-            let token = tx.advance_token()?;
+            let identifier = tx.identifier()?;
+            let mut accessors = Vec::new();
+            while let Some(accessor) = tx.accessor() {
+                accessors.push(accessor);
+            }
+            tx.consume_token(TokenKind::Operator, "=")?;
+            let expression = tx.expression()?;
             let location = Location {
-                start: token.start_position,
-                end: token.end_position,
+                start: identifier.location.start,
+                end: expression.location().end,
             };
-            match token.kind {
-                TokenKind::Identifier => match token.value.as_str() {
-                    "i32" => Some(Type {
-                        name: TypeKind::I32,
+            let Some(last) = accessors.pop() else {
+                return Some(Expression::AssignmentExpression(AssignmentExpression {
+                    location,
+                    name: identifier,
+                    value: Box::new(expression),
+                }));
+            };
+            // all but the last accessor select the array or the struct to assign into
+            let target = accessors
+                .into_iter()
+                .fold(Expression::Identifier(identifier), |target, accessor| {
+                    accessor.apply(target)
+                });
+            Some(match last {
+                Accessor::Index(index, _) => {
+                    Expression::IndexAssignmentExpression(IndexAssignmentExpression {
                         location,
-                    }),
-                    "i64" => Some(Type {
-                        name: TypeKind::I64,
+                        array: Box::new(target),
+                        index: Box::new(index),
+                        value: Box::new(expression),
+                    })
+                }
+                Accessor::Field(field) => {
+                    Expression::FieldAssignmentExpression(FieldAssignmentExpression {
                         location,
-                    }),
-                    _ => None,
-                },
-                _ => None,
-            }
+                        object: Box::new(target),
+                        field,
+                        value: Box::new(expression),
+                    })
+                }
+            })
         })
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::Parser;
-    use ast::*;
-    use indoc::indoc;
-    use pretty_assertions::assert_eq;
-    use tokenizer::{position::Position, Tokenizer};
 
-    #[test]
-    fn expression_returns_integer_literal() {
-        let source = "234849";
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
-        assert_eq!(
-            ast,
-            Some(Expression::IntegerLiteral(IntegerLiteral {
-                value: "234849".to_string(),
+    /// ```bnf
+    /// compound_assignment_expression = identifier ("+=" | "-=" | "*=" | "/=" | "%=") expression
+    /// ```
+    fn compound_assignment_expression(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let identifier = tx.identifier()?;
+            let token = tx.peek_token(0)?;
+            if token.kind != TokenKind::Operator {
+                return None;
+            }
+            let operator = Operator {
+                operator: match token.value.as_str() {
+                    "+=" => OperatorKind::Add,
+                    "-=" => OperatorKind::Subtract,
+                    "*=" => OperatorKind::Multiply,
+                    "/=" => OperatorKind::Divide,
+                    "%=" => OperatorKind::Remainder,
+                    _ => return None,
+                },
                 location: Location {
-                    start: Position {
-                        index: 0,
-                        line: 1,
-                        column: 1,
-                    },
-                    end: Position {
-                        index: 6,
-                        line: 1,
-                        column: 7,
+                    start: token.start_position,
+                    end: token.end_position,
+                },
+            };
+            tx.advance_token();
+            let expression = tx.expression()?;
+            Some(Expression::CompoundAssignmentExpression(
+                CompoundAssignmentExpression {
+                    location: Location {
+                        start: identifier.location.start,
+                        end: expression.location().end,
                     },
+                    name: identifier,
+                    operator,
+                    value: Box::new(expression),
                 },
-            }))
-        );
+            ))
+        })
     }
 
-    #[test]
-    fn expression_returns_identifier() {
-        let source = "abc";
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
-        assert_eq!(
-            ast,
-            Some(Expression::Identifier(Identifier {
-                name: "abc".to_string(),
+    /// ```bnf
+    /// // TODO: remove `"as" type` and instead use a type inference algorithm
+    /// if_else_expression = "if" expression block_with_expression "else" else_if_expression "as" type
+    /// ```
+    fn if_else_expression(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Keyword, "if")?.start_position;
+            let condition = tx.expression()?;
+            let then_block = tx.block_with_expression()?;
+            tx.consume_token(TokenKind::Keyword, "else")?;
+            let mut else_block = tx.else_if_expression(Type {
+                name: TypeKind::I32,
                 location: Location {
                     start: Position {
                         index: 0,
-                        line: 1,
-                        column: 1,
+                        line: 0,
+                        column: 0,
                     },
                     end: Position {
-                        index: 3,
-                        line: 1,
-                        column: 4,
+                        index: 0,
+                        line: 0,
+                        column: 0,
                     },
                 },
+            })?;
+            tx.consume_token(TokenKind::Keyword, "as")?;
+            let return_type = tx.r#type()?;
+            else_block
+                .statements
+                .statements
+                .iter_mut()
+                .for_each(|statement| {
+                    if let Statement::Expression(Expression::IfElseExpression(if_else_expression)) =
+                        statement
+                    {
+                        if_else_expression.return_type = return_type.clone();
+                    }
+                });
+            let end_position = return_type.location.end;
+            Some(Expression::IfElseExpression(IfElseExpression {
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+                condition: Box::new(condition),
+                then_block,
+                else_block,
+                return_type,
             }))
-        )
+        })
     }
 
-    #[test]
-    fn expression_returns_assignment_expression() {
-        let source = "x = 123";
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
-        assert_eq!(
-            ast,
-            Some(Expression::AssignmentExpression(AssignmentExpression {
-                name: Identifier {
-                    name: "x".to_string(),
+    /// ```bnf
+    /// else_if_expression =
+    ///     "if" expression block_with_expression "else" else_if_expression
+    ///   | block_with_expression
+    /// ```
+    fn else_if_expression(&mut self, return_type: Type) -> Option<Block> {
+        self.transaction(|tx| {
+            if let Some(start_position) = tx
+                .consume_token(TokenKind::Keyword, "if")
+                .map(|token| token.start_position)
+            {
+                let condition = tx.expression()?;
+                let then_block = tx.block_with_expression()?;
+                tx.consume_token(TokenKind::Keyword, "else")?;
+                let else_block = tx.else_if_expression(return_type.clone())?;
+                let location = Location {
+                    start: start_position,
+                    end: else_block.location.end,
+                };
+                let expression = Expression::IfElseExpression(IfElseExpression {
+                    location: location.clone(),
+                    condition: Box::new(condition),
+                    then_block,
+                    else_block,
+                    return_type,
+                });
+                Some(Block {
+                    statements: Statements {
+                        statements: vec![Statement::Expression(expression)],
+                        location: location.clone(),
+                    },
+                    location,
+                })
+            } else {
+                Some(tx.block_with_expression()?)
+            }
+        })
+    }
+
+    /// ```bnf
+    /// match_expression = "match" expression "{" match_arm ("," match_arm)* ","? "}"
+    /// ```
+    fn match_expression(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let start_position = tx
+                .consume_token(TokenKind::Keyword, "match")?
+                .start_position;
+            let value = tx.expression()?;
+            tx.consume_token(TokenKind::Delimiter, "{")?;
+            let mut arms = vec![tx.match_arm()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                match tx.match_arm() {
+                    Some(arm) => arms.push(arm),
+                    None => break,
+                }
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, "}")?.end_position;
+            Some(Expression::MatchExpression(MatchExpression {
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+                value: Box::new(value),
+                arms,
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// match_arm = pattern "=>" expression
+    /// ```
+    fn match_arm(&mut self) -> Option<MatchArm> {
+        self.transaction(|tx| {
+            let pattern = tx.pattern()?;
+            tx.consume_token(TokenKind::Operator, "=>")?;
+            let expression = tx.expression()?;
+            Some(MatchArm {
+                location: Location {
+                    start: pattern.location().start,
+                    end: expression.location().end,
+                },
+                pattern,
+                expression,
+            })
+        })
+    }
+
+    /// ```bnf
+    /// pattern =
+    ///     integer_pattern ("..=" | "..") integer_pattern
+    ///   | integer_pattern
+    ///   | identifier "::" identifier ("(" pattern ("," pattern)* ")")?
+    ///   | "_"
+    ///   | identifier
+    /// ```
+    fn pattern(&mut self) -> Option<Pattern> {
+        self.transaction(|tx| {
+            if let Some(start) = tx.integer_pattern() {
+                let inclusive = if tx.consume_token(TokenKind::Operator, "..=").is_some() {
+                    true
+                } else if tx.consume_token(TokenKind::Operator, "..").is_some() {
+                    false
+                } else {
+                    return Some(Pattern::IntegerLiteral(start));
+                };
+                let end = tx.integer_pattern()?;
+                return Some(Pattern::Range(RangePattern {
+                    location: Location {
+                        start: start.location.start,
+                        end: end.location.end,
+                    },
+                    start,
+                    end,
+                    inclusive,
+                }));
+            }
+            let name = tx.identifier()?;
+            if tx.consume_token(TokenKind::Delimiter, "::").is_none() {
+                return Some(if name.name == "_" {
+                    Pattern::Wildcard(name.location)
+                } else {
+                    Pattern::Binding(name)
+                });
+            }
+            let variant = tx.identifier()?;
+            let mut fields = Vec::new();
+            let mut end_position = variant.location.end;
+            if tx.consume_token(TokenKind::Delimiter, "(").is_some() {
+                fields.push(tx.pattern()?);
+                while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                    fields.push(tx.pattern()?);
+                }
+                end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            }
+            Some(Pattern::Variant(VariantPattern {
+                location: Location {
+                    start: name.location.start,
+                    end: end_position,
+                },
+                enum_name: name,
+                variant,
+                fields,
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// integer_pattern = "-"? INTEGER
+    /// ```
+    fn integer_pattern(&mut self) -> Option<IntegerLiteral> {
+        self.transaction(|tx| {
+            let minus = tx
+                .consume_token(TokenKind::Operator, "-")
+                .map(|token| token.start_position);
+            let Expression::IntegerLiteral(literal) = tx.literal()? else {
+                return None;
+            };
+            Some(match minus {
+                Some(start_position) => IntegerLiteral {
+                    value: format!("-{}", literal.value),
+                    location: Location {
+                        start: start_position,
+                        end: literal.location.end,
+                    },
+                },
+                None => literal,
+            })
+        })
+    }
+
+    /// ```bnf
+    /// function_call = identifier "(" expression* ")"
+    /// ```
+    fn function_call(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let identifier = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, "(")?;
+            let mut arguments: Vec<Expression> = Vec::new();
+            while let Some(expression) = tx.expression() {
+                arguments.push(expression);
+                if tx.consume_token(TokenKind::Delimiter, ",").is_none() {
+                    break;
+                }
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            Some(Expression::FunctionCall(FunctionCall {
+                location: Location {
+                    start: identifier.location.start,
+                    end: end_position,
+                },
+                name: identifier,
+                arguments,
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// enum_variant = identifier "::" identifier ("(" expression ("," expression)* ")")?
+    /// ```
+    fn enum_variant(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let enum_name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, "::")?;
+            let variant = tx.identifier()?;
+            let mut arguments = Vec::new();
+            let mut end_position = variant.location.end;
+            if tx.consume_token(TokenKind::Delimiter, "(").is_some() {
+                arguments.push(tx.expression()?);
+                while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                    arguments.push(tx.expression()?);
+                }
+                end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            }
+            Some(Expression::EnumVariant(EnumVariant {
+                location: Location {
+                    start: enum_name.location.start,
+                    end: end_position,
+                },
+                enum_name,
+                variant,
+                arguments,
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// struct_literal = identifier "{" field_initializer ("," field_initializer)* ","? "}"
+    /// ```
+    fn struct_literal(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, "{")?;
+            let mut fields = vec![tx.field_initializer()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                match tx.field_initializer() {
+                    Some(field) => fields.push(field),
+                    None => break,
+                }
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, "}")?.end_position;
+            Some(Expression::StructLiteral(StructLiteral {
+                location: Location {
+                    start: name.location.start,
+                    end: end_position,
+                },
+                name,
+                fields,
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// field_initializer = identifier ":" expression
+    /// ```
+    fn field_initializer(&mut self) -> Option<FieldInitializer> {
+        self.transaction(|tx| {
+            let name = tx.identifier()?;
+            tx.consume_token(TokenKind::Delimiter, ":")?;
+            let value = tx.expression()?;
+            Some(FieldInitializer {
+                location: Location {
+                    start: name.location.start,
+                    end: value.location().end,
+                },
+                name,
+                value,
+            })
+        })
+    }
+
+    /// ```bnf
+    /// array_expression =
+    ///     "[" expression ";" expression "]"
+    ///   | "[" expression ("," expression)* ","? "]"
+    /// ```
+    fn array_expression(&mut self) -> Option<Expression> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Delimiter, "[")?.start_position;
+            let first = tx.expression()?;
+            if tx.consume_token(TokenKind::Delimiter, ";").is_some() {
+                let length = tx.expression()?;
+                let end_position = tx.consume_token(TokenKind::Delimiter, "]")?.end_position;
+                return Some(Expression::ArrayRepeat(ArrayRepeat {
+                    location: Location {
+                        start: start_position,
+                        end: end_position,
+                    },
+                    value: Box::new(first),
+                    length: Box::new(length),
+                }));
+            }
+            let mut elements = vec![first];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                match tx.expression() {
+                    Some(element) => elements.push(element),
+                    None => break,
+                }
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, "]")?.end_position;
+            Some(Expression::ArrayLiteral(ArrayLiteral {
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+                elements,
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// literal = INTEGER | FLOAT | "true" | "false"
+    /// ```
+    /// where `INTEGER` is a `TokenKind::Integer` token and `FLOAT` is a `TokenKind::Float` token.
+    fn literal(&mut self) -> Option<Expression> {
+        if let Some(token) = self.consume_token_kind(TokenKind::Integer) {
+            return Some(Expression::IntegerLiteral(IntegerLiteral {
+                location: Location {
+                    start: token.start_position,
+                    end: token.end_position,
+                },
+                value: token.value.clone(),
+            }));
+        }
+        if let Some(token) = self.consume_token_kind(TokenKind::Float) {
+            return Some(Expression::FloatLiteral(FloatLiteral {
+                location: Location {
+                    start: token.start_position,
+                    end: token.end_position,
+                },
+                value: token.value.clone(),
+            }));
+        }
+        self.transaction(|tx| {
+            let token = tx.consume_token_kind(TokenKind::Keyword)?;
+            let value = match token.value.as_str() {
+                "true" => true,
+                "false" => false,
+                _ => return None,
+            };
+            Some(Expression::BooleanLiteral(BooleanLiteral {
+                value,
+                location: Location {
+                    start: token.start_position,
+                    end: token.end_position,
+                },
+            }))
+        })
+    }
+
+    /// ```bnf
+    /// identifier = IDENTIFIER
+    /// ```
+    /// where `IDENTIFIER` is a `TokenKind::Identifier` token.
+    fn identifier(&mut self) -> Option<Identifier> {
+        self.consume_token_kind(TokenKind::Identifier)
+            .map(|token| Identifier {
+                name: token.value.clone(),
+                location: Location {
+                    start: token.start_position,
+                    end: token.end_position,
+                },
+            })
+    }
+
+    /// ```bnf
+    /// type =
+    ///     "i32" | "i64" | "u32" | "u64" | "f32" | "f64" | "bool"
+    ///   | unit_type | array_type | tuple_type | identifier
+    /// ```
+    fn r#type(&mut self) -> Option<Type> {
+        if let Some(array_type) = self.array_type() {
+            return Some(array_type);
+        }
+        if let Some(unit_type) = self.unit_type() {
+            return Some(unit_type);
+        }
+        if let Some(tuple_type) = self.tuple_type() {
+            return Some(tuple_type);
+        }
+        self.transaction(|tx| {
+            let token = tx.advance_token()?;
+            let location = Location {
+                start: token.start_position,
+                end: token.end_position,
+            };
+            match token.kind {
+                TokenKind::Identifier => Some(Type {
+                    name: token
+                        .value
+                        .parse()
+                        .unwrap_or_else(|_| TypeKind::Named(token.value.clone())),
+                    location,
+                }),
+                _ => None,
+            }
+        })
+    }
+
+    /// ```bnf
+    /// unit_type = "(" ")"
+    /// ```
+    fn unit_type(&mut self) -> Option<Type> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Delimiter, "(")?.start_position;
+            let end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            Some(Type {
+                name: TypeKind::Unit,
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
+    /// ```bnf
+    /// tuple_type = "(" type ("," type)+ ")"
+    /// ```
+    fn tuple_type(&mut self) -> Option<Type> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Delimiter, "(")?.start_position;
+            let mut elements = vec![tx.r#type()?];
+            while tx.consume_token(TokenKind::Delimiter, ",").is_some() {
+                elements.push(tx.r#type()?);
+            }
+            if elements.len() < 2 {
+                return None;
+            }
+            let end_position = tx.consume_token(TokenKind::Delimiter, ")")?.end_position;
+            Some(Type {
+                name: TypeKind::Tuple(elements),
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+
+    /// ```bnf
+    /// array_type = "[" type (";" INTEGER)? "]"
+    /// ```
+    fn array_type(&mut self) -> Option<Type> {
+        self.transaction(|tx| {
+            let start_position = tx.consume_token(TokenKind::Delimiter, "[")?.start_position;
+            let element = tx.r#type()?;
+            let length = if tx.consume_token(TokenKind::Delimiter, ";").is_some() {
+                let Expression::IntegerLiteral(length) = tx.literal()? else {
+                    return None;
+                };
+                Some(length)
+            } else {
+                None
+            };
+            let end_position = tx.consume_token(TokenKind::Delimiter, "]")?.end_position;
+            Some(Type {
+                name: TypeKind::Array {
+                    element: Box::new(element),
+                    length,
+                },
+                location: Location {
+                    start: start_position,
+                    end: end_position,
+                },
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Parser;
+    use ast::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use tokenizer::{position::Position, Tokenizer};
+
+    #[test]
+    fn expression_returns_integer_literal() {
+        let source = "234849";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::IntegerLiteral(IntegerLiteral {
+                value: "234849".to_string(),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7,
+                    },
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_boolean_literal() {
+        let source = "false";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BooleanLiteral(BooleanLiteral {
+                value: false,
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 5,
+                        line: 1,
+                        column: 6
+                    }
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_float_literal_cast() {
+        let source = "2.5 as f32";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::CastExpression(CastExpression {
+                expression: Box::new(Expression::FloatLiteral(FloatLiteral {
+                    value: "2.5".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4
+                        }
+                    },
+                })),
+                target_type: Type {
+                    name: TypeKind::F32,
+                    location: Location {
+                        start: Position {
+                            index: 7,
+                            line: 1,
+                            column: 8
+                        },
+                        end: Position {
+                            index: 10,
+                            line: 1,
+                            column: 11
+                        }
+                    },
+                },
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 10,
+                        line: 1,
+                        column: 11
+                    }
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_identifier() {
+        let source = "abc";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::Identifier(Identifier {
+                name: "abc".to_string(),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 3,
+                        line: 1,
+                        column: 4,
+                    },
+                },
+            }))
+        )
+    }
+
+    #[test]
+    fn expression_returns_assignment_expression() {
+        let source = "x = 123";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::AssignmentExpression(AssignmentExpression {
+                name: Identifier {
+                    name: "x".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                },
+                value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "123".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        },
+                        end: Position {
+                            index: 7,
+                            line: 1,
+                            column: 8
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 7,
+                        line: 1,
+                        column: 8
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_compound_assignment_expression() {
+        let source = "x %= 2 + 1";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::CompoundAssignmentExpression(
+                CompoundAssignmentExpression {
+                    name: Identifier {
+                        name: "x".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2
+                            }
+                        }
+                    },
+                    operator: Operator {
+                        operator: OperatorKind::Remainder,
+                        location: Location {
+                            start: Position {
+                                index: 2,
+                                line: 1,
+                                column: 3
+                            },
+                            end: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            }
+                        }
+                    },
+                    value: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "2".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 5,
+                                    line: 1,
+                                    column: 6
+                                },
+                                end: Position {
+                                    index: 6,
+                                    line: 1,
+                                    column: 7
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::Add,
+                            location: Location {
+                                start: Position {
+                                    index: 7,
+                                    line: 1,
+                                    column: 8
+                                },
+                                end: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "1".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 9,
+                                    line: 1,
+                                    column: 10
+                                },
+                                end: Position {
+                                    index: 10,
+                                    line: 1,
+                                    column: 11
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            },
+                            end: Position {
+                                index: 10,
+                                line: 1,
+                                column: 11
+                            }
+                        },
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 10,
+                            line: 1,
+                            column: 11
+                        }
+                    },
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn expression_returns_if_else_expression() {
+        let source = "if condition { 1 } else { 0 } as i32";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::IfElseExpression(IfElseExpression {
+                condition: Box::new(Expression::Identifier(Identifier {
+                    name: "condition".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4,
+                        },
+                        end: Position {
+                            index: 12,
+                            line: 1,
+                            column: 13,
+                        },
+                    },
+                },)),
+                then_block: Block {
+                    statements: Statements {
+                        statements: vec![Statement::Expression(Expression::IntegerLiteral(
+                            IntegerLiteral {
+                                value: "1".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 15,
+                                        line: 1,
+                                        column: 16,
+                                    },
+                                    end: Position {
+                                        index: 16,
+                                        line: 1,
+                                        column: 17,
+                                    },
+                                },
+                            },
+                        ),),],
+                        location: Location {
+                            start: Position {
+                                index: 13,
+                                line: 1,
+                                column: 14,
+                            },
+                            end: Position {
+                                index: 18,
+                                line: 1,
+                                column: 19,
+                            },
+                        },
+                    },
+                    location: Location {
+                        start: Position {
+                            index: 13,
+                            line: 1,
+                            column: 14,
+                        },
+                        end: Position {
+                            index: 18,
+                            line: 1,
+                            column: 19,
+                        },
+                    },
+                },
+                else_block: Block {
+                    statements: Statements {
+                        statements: vec![Statement::Expression(Expression::IntegerLiteral(
+                            IntegerLiteral {
+                                value: "0".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 26,
+                                        line: 1,
+                                        column: 27,
+                                    },
+                                    end: Position {
+                                        index: 27,
+                                        line: 1,
+                                        column: 28,
+                                    },
+                                },
+                            },
+                        ),),],
+                        location: Location {
+                            start: Position {
+                                index: 24,
+                                line: 1,
+                                column: 25,
+                            },
+                            end: Position {
+                                index: 29,
+                                line: 1,
+                                column: 30,
+                            },
+                        },
+                    },
+                    location: Location {
+                        start: Position {
+                            index: 24,
+                            line: 1,
+                            column: 25,
+                        },
+                        end: Position {
+                            index: 29,
+                            line: 1,
+                            column: 30,
+                        },
+                    },
+                },
+                return_type: Type {
+                    name: TypeKind::I32,
+                    location: Location {
+                        start: Position {
+                            index: 33,
+                            line: 1,
+                            column: 34,
+                        },
+                        end: Position {
+                            index: 36,
+                            line: 1,
+                            column: 37,
+                        },
+                    },
+                },
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 36,
+                        line: 1,
+                        column: 37,
+                    },
+                },
+            },),)
+        );
+    }
+
+    #[test]
+    fn expression_returns_if_else_if_expression() {
+        let source = "if condition { 1 } else if condition { 2 } else { 0 } as i32";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::IfElseExpression(IfElseExpression {
+                condition: Box::new(Expression::Identifier(Identifier {
+                    name: "condition".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4,
+                        },
+                        end: Position {
+                            index: 12,
+                            line: 1,
+                            column: 13,
+                        },
+                    },
+                },)),
+                then_block: Block {
+                    statements: Statements {
+                        statements: vec![Statement::Expression(Expression::IntegerLiteral(
+                            IntegerLiteral {
+                                value: "1".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 15,
+                                        line: 1,
+                                        column: 16,
+                                    },
+                                    end: Position {
+                                        index: 16,
+                                        line: 1,
+                                        column: 17,
+                                    },
+                                },
+                            },
+                        ),),],
+                        location: Location {
+                            start: Position {
+                                index: 13,
+                                line: 1,
+                                column: 14,
+                            },
+                            end: Position {
+                                index: 18,
+                                line: 1,
+                                column: 19,
+                            },
+                        },
+                    },
+                    location: Location {
+                        start: Position {
+                            index: 13,
+                            line: 1,
+                            column: 14,
+                        },
+                        end: Position {
+                            index: 18,
+                            line: 1,
+                            column: 19,
+                        },
+                    },
+                },
+                else_block: Block {
+                    statements: Statements {
+                        statements: vec![Statement::Expression(Expression::IfElseExpression(
+                            IfElseExpression {
+                                condition: Box::new(Expression::Identifier(Identifier {
+                                    name: "condition".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 27,
+                                            line: 1,
+                                            column: 28,
+                                        },
+                                        end: Position {
+                                            index: 36,
+                                            line: 1,
+                                            column: 37,
+                                        },
+                                    },
+                                },)),
+                                then_block: Block {
+                                    statements: Statements {
+                                        statements: vec![Statement::Expression(
+                                            Expression::IntegerLiteral(IntegerLiteral {
+                                                value: "2".to_string(),
+                                                location: Location {
+                                                    start: Position {
+                                                        index: 39,
+                                                        line: 1,
+                                                        column: 40,
+                                                    },
+                                                    end: Position {
+                                                        index: 40,
+                                                        line: 1,
+                                                        column: 41,
+                                                    },
+                                                },
+                                            },),
+                                        ),],
+                                        location: Location {
+                                            start: Position {
+                                                index: 37,
+                                                line: 1,
+                                                column: 38,
+                                            },
+                                            end: Position {
+                                                index: 42,
+                                                line: 1,
+                                                column: 43,
+                                            },
+                                        },
+                                    },
+                                    location: Location {
+                                        start: Position {
+                                            index: 37,
+                                            line: 1,
+                                            column: 38,
+                                        },
+                                        end: Position {
+                                            index: 42,
+                                            line: 1,
+                                            column: 43,
+                                        },
+                                    },
+                                },
+                                else_block: Block {
+                                    statements: Statements {
+                                        statements: vec![Statement::Expression(
+                                            Expression::IntegerLiteral(IntegerLiteral {
+                                                value: "0".to_string(),
+                                                location: Location {
+                                                    start: Position {
+                                                        index: 50,
+                                                        line: 1,
+                                                        column: 51,
+                                                    },
+                                                    end: Position {
+                                                        index: 51,
+                                                        line: 1,
+                                                        column: 52,
+                                                    },
+                                                },
+                                            },),
+                                        ),],
+                                        location: Location {
+                                            start: Position {
+                                                index: 48,
+                                                line: 1,
+                                                column: 49,
+                                            },
+                                            end: Position {
+                                                index: 53,
+                                                line: 1,
+                                                column: 54,
+                                            },
+                                        },
+                                    },
+                                    location: Location {
+                                        start: Position {
+                                            index: 48,
+                                            line: 1,
+                                            column: 49,
+                                        },
+                                        end: Position {
+                                            index: 53,
+                                            line: 1,
+                                            column: 54,
+                                        },
+                                    },
+                                },
+                                return_type: Type {
+                                    name: TypeKind::I32,
+                                    location: Location {
+                                        start: Position {
+                                            index: 57,
+                                            line: 1,
+                                            column: 58,
+                                        },
+                                        end: Position {
+                                            index: 60,
+                                            line: 1,
+                                            column: 61,
+                                        },
+                                    },
+                                },
+                                location: Location {
+                                    start: Position {
+                                        index: 24,
+                                        line: 1,
+                                        column: 25,
+                                    },
+                                    end: Position {
+                                        index: 53,
+                                        line: 1,
+                                        column: 54,
+                                    },
+                                },
+                            },
+                        ),),],
+                        location: Location {
+                            start: Position {
+                                index: 24,
+                                line: 1,
+                                column: 25,
+                            },
+                            end: Position {
+                                index: 53,
+                                line: 1,
+                                column: 54,
+                            },
+                        },
+                    },
+                    location: Location {
+                        start: Position {
+                            index: 24,
+                            line: 1,
+                            column: 25,
+                        },
+                        end: Position {
+                            index: 53,
+                            line: 1,
+                            column: 54,
+                        },
+                    },
+                },
+                return_type: Type {
+                    name: TypeKind::I32,
+                    location: Location {
+                        start: Position {
+                            index: 57,
+                            line: 1,
+                            column: 58,
+                        },
+                        end: Position {
+                            index: 60,
+                            line: 1,
+                            column: 61,
+                        },
+                    },
+                },
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 60,
+                        line: 1,
+                        column: 61,
+                    },
+                },
+            },),)
+        );
+    }
+
+    #[test]
+    fn expression_returns_binary_expression() {
+        let source = "1 + 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1,
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2,
+                        },
+                    },
+                },)),
+                operator: Operator {
+                    operator: OperatorKind::Add,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3,
+                        },
+                        end: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4,
+                        },
+                    },
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5,
+                        },
+                        end: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6,
+                        },
+                    },
+                },)),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 5,
+                        line: 1,
+                        column: 6,
+                    },
+                },
+            },),)
+        );
+    }
+
+    #[test]
+    fn expression_returns_binary_expression_with_unary_minus() {
+        let source = "-1 - -2 * 3";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "0".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Subtract,
+                        location: Location {
+                            start: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "1".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2
+                            },
+                            end: Position {
+                                index: 2,
+                                line: 1,
+                                column: 3
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::Subtract,
+                    location: Location {
+                        start: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "0".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 5,
+                                    line: 1,
+                                    column: 6
+                                },
+                                end: Position {
+                                    index: 5,
+                                    line: 1,
+                                    column: 6
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::Subtract,
+                            location: Location {
+                                start: Position {
+                                    index: 5,
+                                    line: 1,
+                                    column: 6
+                                },
+                                end: Position {
+                                    index: 6,
+                                    line: 1,
+                                    column: 7
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "2".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 6,
+                                    line: 1,
+                                    column: 7
+                                },
+                                end: Position {
+                                    index: 7,
+                                    line: 1,
+                                    column: 8
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            },
+                            end: Position {
+                                index: 7,
+                                line: 1,
+                                column: 8
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Multiply,
+                        location: Location {
+                            start: Position {
+                                index: 8,
+                                line: 1,
+                                column: 9
+                            },
+                            end: Position {
+                                index: 9,
+                                line: 1,
+                                column: 10
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "3".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 10,
+                                line: 1,
+                                column: 11
+                            },
+                            end: Position {
+                                index: 11,
+                                line: 1,
+                                column: 12
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        },
+                        end: Position {
+                            index: 11,
+                            line: 1,
+                            column: 12
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 11,
+                        line: 1,
+                        column: 12
+                    }
+                }
+            }))
+        )
+    }
+
+    #[test]
+    fn expression_returns_complicated_binary_expression() {
+        let source = "100 + 2 * x - (abc + 12 / 2)";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "100".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 3,
+                                line: 1,
+                                column: 4
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Add,
+                        location: Location {
+                            start: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            },
+                            end: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "2".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 6,
+                                    line: 1,
+                                    column: 7
+                                },
+                                end: Position {
+                                    index: 7,
+                                    line: 1,
+                                    column: 8
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::Multiply,
+                            location: Location {
+                                start: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                },
+                                end: Position {
+                                    index: 9,
+                                    line: 1,
+                                    column: 10
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::Identifier(Identifier {
+                            name: "x".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 10,
+                                    line: 1,
+                                    column: 11
+                                },
+                                end: Position {
+                                    index: 11,
+                                    line: 1,
+                                    column: 12
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7
+                            },
+                            end: Position {
+                                index: 11,
+                                line: 1,
+                                column: 12
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 11,
+                            line: 1,
+                            column: 12
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::Subtract,
+                    location: Location {
+                        start: Position {
+                            index: 12,
+                            line: 1,
+                            column: 13
+                        },
+                        end: Position {
+                            index: 13,
+                            line: 1,
+                            column: 14
+                        }
+                    }
+                },
+                right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::Identifier(Identifier {
+                        name: "abc".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 15,
+                                line: 1,
+                                column: 16
+                            },
+                            end: Position {
+                                index: 18,
+                                line: 1,
+                                column: 19
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Add,
+                        location: Location {
+                            start: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20
+                            },
+                            end: Position {
+                                index: 20,
+                                line: 1,
+                                column: 21
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "12".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 21,
+                                    line: 1,
+                                    column: 22
+                                },
+                                end: Position {
+                                    index: 23,
+                                    line: 1,
+                                    column: 24
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::Divide,
+                            location: Location {
+                                start: Position {
+                                    index: 24,
+                                    line: 1,
+                                    column: 25
+                                },
+                                end: Position {
+                                    index: 25,
+                                    line: 1,
+                                    column: 26
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "2".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 26,
+                                    line: 1,
+                                    column: 27
+                                },
+                                end: Position {
+                                    index: 27,
+                                    line: 1,
+                                    column: 28
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 21,
+                                line: 1,
+                                column: 22
+                            },
+                            end: Position {
+                                index: 27,
+                                line: 1,
+                                column: 28
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 15,
+                            line: 1,
+                            column: 16
+                        },
+                        end: Position {
+                            index: 27,
+                            line: 1,
+                            column: 28
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 27,
+                        line: 1,
+                        column: 28
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_bitwise_expression_with_precedence() {
+        let source = "1 | 2 & 3 << 4 % 5";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::BitOr,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4
+                        }
+                    }
+                },
+                right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "2".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            },
+                            end: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::BitAnd,
+                        location: Location {
+                            start: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7
+                            },
+                            end: Position {
+                                index: 7,
+                                line: 1,
+                                column: 8
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "3".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                },
+                                end: Position {
+                                    index: 9,
+                                    line: 1,
+                                    column: 10
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::ShiftLeft,
+                            location: Location {
+                                start: Position {
+                                    index: 10,
+                                    line: 1,
+                                    column: 11
+                                },
+                                end: Position {
+                                    index: 12,
+                                    line: 1,
+                                    column: 13
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                            left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                                value: "4".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 13,
+                                        line: 1,
+                                        column: 14
+                                    },
+                                    end: Position {
+                                        index: 14,
+                                        line: 1,
+                                        column: 15
+                                    }
+                                }
+                            })),
+                            operator: Operator {
+                                operator: OperatorKind::Remainder,
+                                location: Location {
+                                    start: Position {
+                                        index: 15,
+                                        line: 1,
+                                        column: 16
+                                    },
+                                    end: Position {
+                                        index: 16,
+                                        line: 1,
+                                        column: 17
+                                    }
+                                }
+                            },
+                            right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                                value: "5".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 17,
+                                        line: 1,
+                                        column: 18
+                                    },
+                                    end: Position {
+                                        index: 18,
+                                        line: 1,
+                                        column: 19
+                                    }
+                                }
+                            })),
+                            location: Location {
+                                start: Position {
+                                    index: 13,
+                                    line: 1,
+                                    column: 14
+                                },
+                                end: Position {
+                                    index: 18,
+                                    line: 1,
+                                    column: 19
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 8,
+                                line: 1,
+                                column: 9
+                            },
+                            end: Position {
+                                index: 18,
+                                line: 1,
+                                column: 19
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        },
+                        end: Position {
+                            index: 18,
+                            line: 1,
+                            column: 19
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 18,
+                        line: 1,
+                        column: 19
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_less_than_expression() {
+        let source = "1 < 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::LessThan,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4
+                        }
+                    }
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        },
+                        end: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 5,
+                        line: 1,
+                        column: 6
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_less_than_or_equal_expression() {
+        let source = "1 <= 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::LessThanOrEqual,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        },
+                        end: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7
+                    }
+                }
+            }))
+        )
+    }
+
+    #[test]
+    fn expression_returns_greater_than() {
+        let source = "1 > 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::GreaterThan,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4
+                        }
+                    }
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        },
+                        end: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 5,
+                        line: 1,
+                        column: 6
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_greater_than_or_equal() {
+        let source = "1 >= 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::GreaterThanOrEqual,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        },
+                        end: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_return_equal_expression() {
+        let source = "1 == 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::Equal,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        },
+                        end: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_return_not_equal_expression() {
+        let source = "1 != 2";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "1".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::NotEqual,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "2".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        },
+                        end: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_return_comparison_expression_with_calculation() {
+        let source = "1 + 2 == 3 * 4 != 5 / 6";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "1".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 0,
+                                    line: 1,
+                                    column: 1
+                                },
+                                end: Position {
+                                    index: 1,
+                                    line: 1,
+                                    column: 2
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::Add,
+                            location: Location {
+                                start: Position {
+                                    index: 2,
+                                    line: 1,
+                                    column: 3
+                                },
+                                end: Position {
+                                    index: 3,
+                                    line: 1,
+                                    column: 4
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "2".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 4,
+                                    line: 1,
+                                    column: 5
+                                },
+                                end: Position {
+                                    index: 5,
+                                    line: 1,
+                                    column: 6
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Equal,
+                        location: Location {
+                            start: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7
+                            },
+                            end: Position {
+                                index: 8,
+                                line: 1,
+                                column: 9
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "3".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 9,
+                                    line: 1,
+                                    column: 10
+                                },
+                                end: Position {
+                                    index: 10,
+                                    line: 1,
+                                    column: 11
+                                }
+                            }
+                        })),
+                        operator: Operator {
+                            operator: OperatorKind::Multiply,
+                            location: Location {
+                                start: Position {
+                                    index: 11,
+                                    line: 1,
+                                    column: 12
+                                },
+                                end: Position {
+                                    index: 12,
+                                    line: 1,
+                                    column: 13
+                                }
+                            }
+                        },
+                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "4".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 13,
+                                    line: 1,
+                                    column: 14
+                                },
+                                end: Position {
+                                    index: 14,
+                                    line: 1,
+                                    column: 15
+                                }
+                            }
+                        })),
+                        location: Location {
+                            start: Position {
+                                index: 9,
+                                line: 1,
+                                column: 10
+                            },
+                            end: Position {
+                                index: 14,
+                                line: 1,
+                                column: 15
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 14,
+                            line: 1,
+                            column: 15
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::NotEqual,
+                    location: Location {
+                        start: Position {
+                            index: 15,
+                            line: 1,
+                            column: 16
+                        },
+                        end: Position {
+                            index: 17,
+                            line: 1,
+                            column: 18
+                        }
+                    }
+                },
+                right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "5".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 18,
+                                line: 1,
+                                column: 19
+                            },
+                            end: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20
+                            }
+                        }
+                    })),
+                    operator: Operator {
+                        operator: OperatorKind::Divide,
+                        location: Location {
+                            start: Position {
+                                index: 20,
+                                line: 1,
+                                column: 21
+                            },
+                            end: Position {
+                                index: 21,
+                                line: 1,
+                                column: 22
+                            }
+                        }
+                    },
+                    right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "6".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 22,
+                                line: 1,
+                                column: 23
+                            },
+                            end: Position {
+                                index: 23,
+                                line: 1,
+                                column: 24
+                            }
+                        }
+                    })),
+                    location: Location {
+                        start: Position {
+                            index: 18,
+                            line: 1,
+                            column: 19
+                        },
+                        end: Position {
+                            index: 23,
+                            line: 1,
+                            column: 24
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 23,
+                        line: 1,
+                        column: 24
+                    }
+                }
+            }))
+        )
+    }
+
+    #[test]
+    fn expression_return_logical_and_expression() {
+        let source = "a && b";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::Identifier(Identifier {
+                    name: "a".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                operator: Operator {
+                    operator: OperatorKind::LogicalAnd,
+                    location: Location {
+                        start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::Identifier(Identifier {
+                    name: "b".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        },
+                        end: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7
+                        }
+                    }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7
+                    }
+                }
+            }))
+        )
+    }
+
+    #[test]
+    fn expression_return_logical_or_expression() {
+        let source = "a || b";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::BinaryExpression(BinaryExpression {
+                left: Box::new(Expression::Identifier(Identifier {
+                    name: "a".to_string(),
                     location: Location {
                         start: Position {
                             index: 0,
@@ -959,19 +4034,34 @@ mod tests {
                             column: 2
                         }
                     }
-                },
-                value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "123".to_string(),
+                })),
+                operator: Operator {
+                    operator: OperatorKind::LogicalOr,
                     location: Location {
                         start: Position {
+                            index: 2,
+                            line: 1,
+                            column: 3
+                        },
+                        end: Position {
                             index: 4,
                             line: 1,
                             column: 5
+                        }
+                    }
+                },
+                right: Box::new(Expression::Identifier(Identifier {
+                    name: "b".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
                         },
                         end: Position {
-                            index: 7,
+                            index: 6,
                             line: 1,
-                            column: 8
+                            column: 7
                         }
                     }
                 })),
@@ -982,406 +4072,282 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 7,
+                        index: 6,
                         line: 1,
-                        column: 8
+                        column: 7
                     }
                 }
             }))
-        );
+        )
     }
 
     #[test]
-    fn expression_returns_if_else_expression() {
-        let source = "if condition { 1 } else { 0 } as i32";
+    fn expression_return_logical_not_expression() {
+        let source = "!a";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::IfElseExpression(IfElseExpression {
-                condition: Box::new(Expression::Identifier(Identifier {
-                    name: "condition".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 3,
-                            line: 1,
-                            column: 4,
-                        },
-                        end: Position {
-                            index: 12,
-                            line: 1,
-                            column: 13,
-                        },
-                    },
-                },)),
-                then_block: Block {
-                    statements: Statements {
-                        statements: vec![Statement::Expression(Expression::IntegerLiteral(
-                            IntegerLiteral {
-                                value: "1".to_string(),
-                                location: Location {
-                                    start: Position {
-                                        index: 15,
-                                        line: 1,
-                                        column: 16,
-                                    },
-                                    end: Position {
-                                        index: 16,
-                                        line: 1,
-                                        column: 17,
-                                    },
-                                },
-                            },
-                        ),),],
-                        location: Location {
-                            start: Position {
-                                index: 13,
-                                line: 1,
-                                column: 14,
-                            },
-                            end: Position {
-                                index: 18,
-                                line: 1,
-                                column: 19,
-                            },
-                        },
-                    },
-                    location: Location {
-                        start: Position {
-                            index: 13,
-                            line: 1,
-                            column: 14,
-                        },
-                        end: Position {
-                            index: 18,
-                            line: 1,
-                            column: 19,
-                        },
-                    },
-                },
-                else_block: Block {
-                    statements: Statements {
-                        statements: vec![Statement::Expression(Expression::IntegerLiteral(
-                            IntegerLiteral {
-                                value: "0".to_string(),
-                                location: Location {
-                                    start: Position {
-                                        index: 26,
-                                        line: 1,
-                                        column: 27,
-                                    },
-                                    end: Position {
-                                        index: 27,
-                                        line: 1,
-                                        column: 28,
-                                    },
-                                },
-                            },
-                        ),),],
-                        location: Location {
-                            start: Position {
-                                index: 24,
-                                line: 1,
-                                column: 25,
-                            },
-                            end: Position {
-                                index: 29,
-                                line: 1,
-                                column: 30,
-                            },
-                        },
-                    },
+            Some(Expression::UnaryExpression(UnaryExpression {
+                operator: Operator {
+                    operator: OperatorKind::LogicalNot,
                     location: Location {
                         start: Position {
-                            index: 24,
+                            index: 0,
                             line: 1,
-                            column: 25,
+                            column: 1
                         },
                         end: Position {
-                            index: 29,
+                            index: 1,
                             line: 1,
-                            column: 30,
-                        },
-                    },
+                            column: 2
+                        }
+                    }
                 },
-                return_type: Type {
-                    name: TypeKind::I32,
+                operand: Box::new(Expression::Identifier(Identifier {
+                    name: "a".to_string(),
                     location: Location {
                         start: Position {
-                            index: 33,
+                            index: 1,
                             line: 1,
-                            column: 34,
+                            column: 2
                         },
                         end: Position {
-                            index: 36,
+                            index: 2,
                             line: 1,
-                            column: 37,
-                        },
-                    },
-                },
+                            column: 3
+                        }
+                    }
+                })),
                 location: Location {
                     start: Position {
                         index: 0,
                         line: 1,
-                        column: 1,
+                        column: 1
                     },
                     end: Position {
-                        index: 36,
+                        index: 2,
                         line: 1,
-                        column: 37,
-                    },
-                },
-            },),)
+                        column: 3
+                    }
+                }
+            }))
         );
     }
 
     #[test]
-    fn expression_returns_if_else_if_expression() {
-        let source = "if condition { 1 } else if condition { 2 } else { 0 } as i32";
+    fn expression_return_repeated_unary_expression() {
+        let source = "!!- -!  !a";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::IfElseExpression(IfElseExpression {
-                condition: Box::new(Expression::Identifier(Identifier {
-                    name: "condition".to_string(),
+            Some(Expression::UnaryExpression(UnaryExpression {
+                operator: Operator {
+                    operator: OperatorKind::LogicalNot,
                     location: Location {
                         start: Position {
-                            index: 3,
+                            index: 0,
                             line: 1,
-                            column: 4,
+                            column: 1,
                         },
                         end: Position {
-                            index: 12,
+                            index: 1,
                             line: 1,
-                            column: 13,
+                            column: 2,
                         },
                     },
-                },)),
-                then_block: Block {
-                    statements: Statements {
-                        statements: vec![Statement::Expression(Expression::IntegerLiteral(
-                            IntegerLiteral {
-                                value: "1".to_string(),
+                },
+                operand: Box::new(Expression::UnaryExpression(UnaryExpression {
+                    operator: Operator {
+                        operator: OperatorKind::LogicalNot,
+                        location: Location {
+                            start: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2,
+                            },
+                            end: Position {
+                                index: 2,
+                                line: 1,
+                                column: 3,
+                            },
+                        },
+                    },
+                    operand: Box::new(Expression::BinaryExpression(BinaryExpression {
+                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                            value: "0".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 2,
+                                    line: 1,
+                                    column: 3,
+                                },
+                                end: Position {
+                                    index: 2,
+                                    line: 1,
+                                    column: 3,
+                                },
+                            },
+                        },)),
+                        operator: Operator {
+                            operator: OperatorKind::Subtract,
+                            location: Location {
+                                start: Position {
+                                    index: 2,
+                                    line: 1,
+                                    column: 3,
+                                },
+                                end: Position {
+                                    index: 3,
+                                    line: 1,
+                                    column: 4,
+                                },
+                            },
+                        },
+                        right: Box::new(Expression::BinaryExpression(BinaryExpression {
+                            left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                                value: "0".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 4,
+                                        line: 1,
+                                        column: 5,
+                                    },
+                                    end: Position {
+                                        index: 4,
+                                        line: 1,
+                                        column: 5,
+                                    },
+                                },
+                            },)),
+                            operator: Operator {
+                                operator: OperatorKind::Subtract,
                                 location: Location {
                                     start: Position {
-                                        index: 15,
+                                        index: 4,
                                         line: 1,
-                                        column: 16,
+                                        column: 5,
                                     },
                                     end: Position {
-                                        index: 16,
+                                        index: 5,
                                         line: 1,
-                                        column: 17,
+                                        column: 6,
                                     },
                                 },
                             },
-                        ),),],
-                        location: Location {
-                            start: Position {
-                                index: 13,
-                                line: 1,
-                                column: 14,
-                            },
-                            end: Position {
-                                index: 18,
-                                line: 1,
-                                column: 19,
-                            },
-                        },
-                    },
-                    location: Location {
-                        start: Position {
-                            index: 13,
-                            line: 1,
-                            column: 14,
-                        },
-                        end: Position {
-                            index: 18,
-                            line: 1,
-                            column: 19,
-                        },
-                    },
-                },
-                else_block: Block {
-                    statements: Statements {
-                        statements: vec![Statement::Expression(Expression::IfElseExpression(
-                            IfElseExpression {
-                                condition: Box::new(Expression::Identifier(Identifier {
-                                    name: "condition".to_string(),
+                            right: Box::new(Expression::UnaryExpression(UnaryExpression {
+                                operator: Operator {
+                                    operator: OperatorKind::LogicalNot,
                                     location: Location {
                                         start: Position {
-                                            index: 27,
+                                            index: 5,
                                             line: 1,
-                                            column: 28,
+                                            column: 6,
                                         },
                                         end: Position {
-                                            index: 36,
+                                            index: 6,
                                             line: 1,
-                                            column: 37,
+                                            column: 7,
                                         },
                                     },
-                                },)),
-                                then_block: Block {
-                                    statements: Statements {
-                                        statements: vec![Statement::Expression(
-                                            Expression::IntegerLiteral(IntegerLiteral {
-                                                value: "2".to_string(),
-                                                location: Location {
-                                                    start: Position {
-                                                        index: 39,
-                                                        line: 1,
-                                                        column: 40,
-                                                    },
-                                                    end: Position {
-                                                        index: 40,
-                                                        line: 1,
-                                                        column: 41,
-                                                    },
-                                                },
-                                            },),
-                                        ),],
+                                },
+                                operand: Box::new(Expression::UnaryExpression(UnaryExpression {
+                                    operator: Operator {
+                                        operator: OperatorKind::LogicalNot,
                                         location: Location {
                                             start: Position {
-                                                index: 37,
+                                                index: 8,
                                                 line: 1,
-                                                column: 38,
+                                                column: 9,
                                             },
                                             end: Position {
-                                                index: 42,
+                                                index: 9,
                                                 line: 1,
-                                                column: 43,
+                                                column: 10,
                                             },
                                         },
                                     },
-                                    location: Location {
-                                        start: Position {
-                                            index: 37,
-                                            line: 1,
-                                            column: 38,
-                                        },
-                                        end: Position {
-                                            index: 42,
-                                            line: 1,
-                                            column: 43,
-                                        },
-                                    },
-                                },
-                                else_block: Block {
-                                    statements: Statements {
-                                        statements: vec![Statement::Expression(
-                                            Expression::IntegerLiteral(IntegerLiteral {
-                                                value: "0".to_string(),
-                                                location: Location {
-                                                    start: Position {
-                                                        index: 50,
-                                                        line: 1,
-                                                        column: 51,
-                                                    },
-                                                    end: Position {
-                                                        index: 51,
-                                                        line: 1,
-                                                        column: 52,
-                                                    },
-                                                },
-                                            },),
-                                        ),],
+                                    operand: Box::new(Expression::Identifier(Identifier {
+                                        name: "a".to_string(),
                                         location: Location {
                                             start: Position {
-                                                index: 48,
+                                                index: 9,
                                                 line: 1,
-                                                column: 49,
+                                                column: 10,
                                             },
                                             end: Position {
-                                                index: 53,
+                                                index: 10,
                                                 line: 1,
-                                                column: 54,
+                                                column: 11,
                                             },
                                         },
-                                    },
-                                    location: Location {
-                                        start: Position {
-                                            index: 48,
-                                            line: 1,
-                                            column: 49,
-                                        },
-                                        end: Position {
-                                            index: 53,
-                                            line: 1,
-                                            column: 54,
-                                        },
-                                    },
-                                },
-                                return_type: Type {
-                                    name: TypeKind::I32,
+                                    },)),
                                     location: Location {
                                         start: Position {
-                                            index: 57,
+                                            index: 8,
                                             line: 1,
-                                            column: 58,
+                                            column: 9,
                                         },
                                         end: Position {
-                                            index: 60,
+                                            index: 10,
                                             line: 1,
-                                            column: 61,
+                                            column: 11,
                                         },
                                     },
-                                },
+                                },)),
                                 location: Location {
                                     start: Position {
-                                        index: 24,
+                                        index: 5,
                                         line: 1,
-                                        column: 25,
+                                        column: 6,
                                     },
                                     end: Position {
-                                        index: 53,
+                                        index: 10,
                                         line: 1,
-                                        column: 54,
+                                        column: 11,
                                     },
                                 },
+                            },)),
+                            location: Location {
+                                start: Position {
+                                    index: 4,
+                                    line: 1,
+                                    column: 5,
+                                },
+                                end: Position {
+                                    index: 10,
+                                    line: 1,
+                                    column: 11,
+                                },
                             },
-                        ),),],
+                        },)),
                         location: Location {
                             start: Position {
-                                index: 24,
+                                index: 2,
                                 line: 1,
-                                column: 25,
+                                column: 3,
                             },
                             end: Position {
-                                index: 53,
+                                index: 10,
                                 line: 1,
-                                column: 54,
+                                column: 11,
                             },
                         },
-                    },
-                    location: Location {
-                        start: Position {
-                            index: 24,
-                            line: 1,
-                            column: 25,
-                        },
-                        end: Position {
-                            index: 53,
-                            line: 1,
-                            column: 54,
-                        },
-                    },
-                },
-                return_type: Type {
-                    name: TypeKind::I32,
+                    },)),
                     location: Location {
                         start: Position {
-                            index: 57,
+                            index: 1,
                             line: 1,
-                            column: 58,
+                            column: 2,
                         },
                         end: Position {
-                            index: 60,
+                            index: 10,
                             line: 1,
-                            column: 61,
+                            column: 11,
                         },
                     },
-                },
+                },)),
                 location: Location {
                     start: Position {
                         index: 0,
@@ -1389,138 +4355,79 @@ mod tests {
                         column: 1,
                     },
                     end: Position {
-                        index: 60,
+                        index: 10,
                         line: 1,
-                        column: 61,
+                        column: 11,
                     },
                 },
             },),)
-        );
+        )
     }
 
     #[test]
-    fn expression_returns_binary_expression() {
-        let source = "1 + 2";
+    fn expression_returns_cast_expression() {
+        let source = "a as i64";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
+            Some(Expression::CastExpression(CastExpression {
+                expression: Box::new(Expression::Identifier(Identifier {
+                    name: "a".to_string(),
                     location: Location {
                         start: Position {
                             index: 0,
                             line: 1,
-                            column: 1,
-                        },
-                        end: Position {
-                            index: 1,
-                            line: 1,
-                            column: 2,
-                        },
-                    },
-                },)),
-                operator: Operator {
-                    operator: OperatorKind::Add,
-                    location: Location {
-                        start: Position {
-                            index: 2,
-                            line: 1,
-                            column: 3,
+                            column: 1
                         },
                         end: Position {
-                            index: 3,
-                            line: 1,
-                            column: 4,
-                        },
-                    },
-                },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
+                            index: 1,
+                            line: 1,
+                            column: 2
+                        }
+                    }
+                })),
+                target_type: Type {
+                    name: TypeKind::I64,
                     location: Location {
                         start: Position {
-                            index: 4,
+                            index: 5,
                             line: 1,
-                            column: 5,
+                            column: 6
                         },
                         end: Position {
-                            index: 5,
+                            index: 8,
                             line: 1,
-                            column: 6,
-                        },
-                    },
-                },)),
+                            column: 9
+                        }
+                    }
+                },
                 location: Location {
                     start: Position {
                         index: 0,
                         line: 1,
-                        column: 1,
+                        column: 1
                     },
                     end: Position {
-                        index: 5,
+                        index: 8,
                         line: 1,
-                        column: 6,
-                    },
-                },
-            },),)
+                        column: 9
+                    }
+                }
+            }))
         );
     }
 
     #[test]
-    fn expression_returns_binary_expression_with_unary_minus() {
-        let source = "-1 - -2 * 3";
+    fn expression_returns_cast_expression_with_higher_precedence_than_mul() {
+        let source = "a * b as i64";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
             Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::BinaryExpression(BinaryExpression {
-                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "0".to_string(),
-                        location: Location {
-                            start: Position {
-                                index: 0,
-                                line: 1,
-                                column: 1
-                            },
-                            end: Position {
-                                index: 0,
-                                line: 1,
-                                column: 1
-                            }
-                        }
-                    })),
-                    operator: Operator {
-                        operator: OperatorKind::Subtract,
-                        location: Location {
-                            start: Position {
-                                index: 0,
-                                line: 1,
-                                column: 1
-                            },
-                            end: Position {
-                                index: 1,
-                                line: 1,
-                                column: 2
-                            }
-                        }
-                    },
-                    right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "1".to_string(),
-                        location: Location {
-                            start: Position {
-                                index: 1,
-                                line: 1,
-                                column: 2
-                            },
-                            end: Position {
-                                index: 2,
-                                line: 1,
-                                column: 3
-                            }
-                        }
-                    })),
+                left: Box::new(Expression::Identifier(Identifier {
+                    name: "a".to_string(),
                     location: Location {
                         start: Position {
                             index: 0,
@@ -1528,127 +4435,68 @@ mod tests {
                             column: 1
                         },
                         end: Position {
-                            index: 2,
+                            index: 1,
                             line: 1,
-                            column: 3
+                            column: 2
                         }
                     }
                 })),
                 operator: Operator {
-                    operator: OperatorKind::Subtract,
+                    operator: OperatorKind::Multiply,
                     location: Location {
                         start: Position {
-                            index: 3,
+                            index: 2,
                             line: 1,
-                            column: 4
+                            column: 3
                         },
                         end: Position {
-                            index: 4,
+                            index: 3,
                             line: 1,
-                            column: 5
+                            column: 4
                         }
                     }
                 },
-                right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                    left: Box::new(Expression::BinaryExpression(BinaryExpression {
-                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "0".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 5,
-                                    line: 1,
-                                    column: 6
-                                },
-                                end: Position {
-                                    index: 5,
-                                    line: 1,
-                                    column: 6
-                                }
-                            }
-                        })),
-                        operator: Operator {
-                            operator: OperatorKind::Subtract,
-                            location: Location {
-                                start: Position {
-                                    index: 5,
-                                    line: 1,
-                                    column: 6
-                                },
-                                end: Position {
-                                    index: 6,
-                                    line: 1,
-                                    column: 7
-                                }
-                            }
-                        },
-                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "2".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 6,
-                                    line: 1,
-                                    column: 7
-                                },
-                                end: Position {
-                                    index: 7,
-                                    line: 1,
-                                    column: 8
-                                }
-                            }
-                        })),
+                right: Box::new(Expression::CastExpression(CastExpression {
+                    expression: Box::new(Expression::Identifier(Identifier {
+                        name: "b".to_string(),
                         location: Location {
                             start: Position {
-                                index: 5,
+                                index: 4,
                                 line: 1,
-                                column: 6
+                                column: 5
                             },
                             end: Position {
-                                index: 7,
+                                index: 5,
                                 line: 1,
-                                column: 8
+                                column: 6
                             }
                         }
                     })),
-                    operator: Operator {
-                        operator: OperatorKind::Multiply,
+                    target_type: Type {
+                        name: TypeKind::I64,
                         location: Location {
                             start: Position {
-                                index: 8,
-                                line: 1,
-                                column: 9
-                            },
-                            end: Position {
                                 index: 9,
                                 line: 1,
                                 column: 10
-                            }
-                        }
-                    },
-                    right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "3".to_string(),
-                        location: Location {
-                            start: Position {
-                                index: 10,
-                                line: 1,
-                                column: 11
                             },
                             end: Position {
-                                index: 11,
+                                index: 12,
                                 line: 1,
-                                column: 12
+                                column: 13
                             }
                         }
-                    })),
+                    },
                     location: Location {
                         start: Position {
-                            index: 5,
+                            index: 4,
                             line: 1,
-                            column: 6
+                            column: 5
                         },
                         end: Position {
-                            index: 11,
+                            index: 12,
                             line: 1,
-                            column: 12
+                            column: 13
                         }
                     }
                 })),
@@ -1659,110 +4507,51 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 11,
+                        index: 12,
                         line: 1,
-                        column: 12
+                        column: 13
                     }
                 }
             }))
-        )
+        );
     }
 
     #[test]
-    fn expression_returns_complicated_binary_expression() {
-        let source = "100 + 2 * x - (abc + 12 / 2)";
+    fn expression_returns_index_expression() {
+        let source = "a[i][0]";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::BinaryExpression(BinaryExpression {
-                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "100".to_string(),
+            Some(Expression::IndexExpression(IndexExpression {
+                array: Box::new(Expression::IndexExpression(IndexExpression {
+                    array: Box::new(Expression::Identifier(Identifier {
+                        name: "a".to_string(),
                         location: Location {
                             start: Position {
                                 index: 0,
-                                line: 1,
-                                column: 1
-                            },
-                            end: Position {
-                                index: 3,
-                                line: 1,
-                                column: 4
-                            }
-                        }
-                    })),
-                    operator: Operator {
-                        operator: OperatorKind::Add,
-                        location: Location {
-                            start: Position {
-                                index: 4,
-                                line: 1,
-                                column: 5
-                            },
-                            end: Position {
-                                index: 5,
-                                line: 1,
-                                column: 6
-                            }
-                        }
-                    },
-                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "2".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 6,
-                                    line: 1,
-                                    column: 7
-                                },
-                                end: Position {
-                                    index: 7,
-                                    line: 1,
-                                    column: 8
-                                }
-                            }
-                        })),
-                        operator: Operator {
-                            operator: OperatorKind::Multiply,
-                            location: Location {
-                                start: Position {
-                                    index: 8,
-                                    line: 1,
-                                    column: 9
-                                },
-                                end: Position {
-                                    index: 9,
-                                    line: 1,
-                                    column: 10
-                                }
-                            }
-                        },
-                        right: Box::new(Expression::Identifier(Identifier {
-                            name: "x".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 10,
-                                    line: 1,
-                                    column: 11
-                                },
-                                end: Position {
-                                    index: 11,
-                                    line: 1,
-                                    column: 12
-                                }
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2
                             }
-                        })),
+                        }
+                    })),
+                    index: Box::new(Expression::Identifier(Identifier {
+                        name: "i".to_string(),
                         location: Location {
                             start: Position {
-                                index: 6,
+                                index: 2,
                                 line: 1,
-                                column: 7
+                                column: 3
                             },
                             end: Position {
-                                index: 11,
+                                index: 3,
                                 line: 1,
-                                column: 12
+                                column: 4
                             }
                         }
                     })),
@@ -1773,130 +4562,154 @@ mod tests {
                             column: 1
                         },
                         end: Position {
-                            index: 11,
+                            index: 4,
                             line: 1,
-                            column: 12
+                            column: 5
                         }
                     }
                 })),
-                operator: Operator {
-                    operator: OperatorKind::Subtract,
+                index: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "0".to_string(),
                     location: Location {
                         start: Position {
-                            index: 12,
+                            index: 5,
                             line: 1,
-                            column: 13
+                            column: 6
                         },
                         end: Position {
-                            index: 13,
+                            index: 6,
                             line: 1,
-                            column: 14
+                            column: 7
                         }
                     }
-                },
-                right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                    left: Box::new(Expression::Identifier(Identifier {
-                        name: "abc".to_string(),
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 7,
+                        line: 1,
+                        column: 8
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn expression_returns_index_assignment_expression() {
+        let source = "a[0] = 1";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::IndexAssignmentExpression(
+                IndexAssignmentExpression {
+                    array: Box::new(Expression::Identifier(Identifier {
+                        name: "a".to_string(),
                         location: Location {
                             start: Position {
-                                index: 15,
+                                index: 0,
                                 line: 1,
-                                column: 16
+                                column: 1
                             },
                             end: Position {
-                                index: 18,
+                                index: 1,
                                 line: 1,
-                                column: 19
+                                column: 2
                             }
                         }
                     })),
-                    operator: Operator {
-                        operator: OperatorKind::Add,
+                    index: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "0".to_string(),
                         location: Location {
                             start: Position {
-                                index: 19,
+                                index: 2,
                                 line: 1,
-                                column: 20
+                                column: 3
                             },
                             end: Position {
-                                index: 20,
+                                index: 3,
                                 line: 1,
-                                column: 21
+                                column: 4
                             }
                         }
-                    },
-                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "12".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 21,
-                                    line: 1,
-                                    column: 22
-                                },
-                                end: Position {
-                                    index: 23,
-                                    line: 1,
-                                    column: 24
-                                }
-                            }
-                        })),
-                        operator: Operator {
-                            operator: OperatorKind::Divide,
-                            location: Location {
-                                start: Position {
-                                    index: 24,
-                                    line: 1,
-                                    column: 25
-                                },
-                                end: Position {
-                                    index: 25,
-                                    line: 1,
-                                    column: 26
-                                }
-                            }
-                        },
-                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "2".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 26,
-                                    line: 1,
-                                    column: 27
-                                },
-                                end: Position {
-                                    index: 27,
-                                    line: 1,
-                                    column: 28
-                                }
-                            }
-                        })),
+                    })),
+                    value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "1".to_string(),
                         location: Location {
                             start: Position {
-                                index: 21,
+                                index: 7,
                                 line: 1,
-                                column: 22
+                                column: 8
                             },
                             end: Position {
-                                index: 27,
+                                index: 8,
                                 line: 1,
-                                column: 28
+                                column: 9
                             }
                         }
                     })),
                     location: Location {
                         start: Position {
-                            index: 15,
+                            index: 0,
                             line: 1,
-                            column: 16
+                            column: 1
                         },
                         end: Position {
-                            index: 27,
+                            index: 8,
                             line: 1,
-                            column: 28
+                            column: 9
                         }
                     }
-                })),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn expression_returns_array_literal() {
+        let source = "[1, 2]";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::ArrayLiteral(ArrayLiteral {
+                elements: vec![
+                    Expression::IntegerLiteral(IntegerLiteral {
+                        value: "1".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2
+                            },
+                            end: Position {
+                                index: 2,
+                                line: 1,
+                                column: 3
+                            }
+                        }
+                    }),
+                    Expression::IntegerLiteral(IntegerLiteral {
+                        value: "2".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            },
+                            end: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            }
+                        }
+                    })
+                ],
                 location: Location {
                     start: Position {
                         index: 0,
@@ -1904,9 +4717,9 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 27,
+                        index: 6,
                         line: 1,
-                        column: 28
+                        column: 7
                     }
                 }
             }))
@@ -1914,57 +4727,112 @@ mod tests {
     }
 
     #[test]
-    fn expression_returns_less_than_expression() {
-        let source = "1 < 2";
+    fn expression_returns_array_repeat() {
+        let source = "[0; n]";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
+            Some(Expression::ArrayRepeat(ArrayRepeat {
+                value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "0".to_string(),
                     location: Location {
                         start: Position {
-                            index: 0,
+                            index: 1,
                             line: 1,
-                            column: 1
+                            column: 2
                         },
                         end: Position {
-                            index: 1,
+                            index: 2,
                             line: 1,
-                            column: 2
+                            column: 3
                         }
                     }
                 })),
-                operator: Operator {
-                    operator: OperatorKind::LessThan,
+                length: Box::new(Expression::Identifier(Identifier {
+                    name: "n".to_string(),
                     location: Location {
                         start: Position {
-                            index: 2,
+                            index: 4,
                             line: 1,
-                            column: 3
+                            column: 5
                         },
                         end: Position {
-                            index: 3,
+                            index: 5,
                             line: 1,
-                            column: 4
+                            column: 6
                         }
                     }
+                })),
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1
+                    },
+                    end: Position {
+                        index: 6,
+                        line: 1,
+                        column: 7
+                    }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn statement_returns_bool_variable_definition_statement() {
+        let source = "let b: bool = true;";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).statement();
+        assert_eq!(
+            ast,
+            Some(Statement::VariableDefinition(VariableDefinition {
+                name: Identifier {
+                    name: "b".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5
+                        },
+                        end: Position {
+                            index: 5,
+                            line: 1,
+                            column: 6
+                        }
+                    },
                 },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
+                mutable: false,
+                variable_type: Some(Type {
+                    name: TypeKind::Bool,
+                    location: Location {
+                        start: Position {
+                            index: 7,
+                            line: 1,
+                            column: 8
+                        },
+                        end: Position {
+                            index: 11,
+                            line: 1,
+                            column: 12
+                        }
+                    },
+                }),
+                value: Some(Expression::BooleanLiteral(BooleanLiteral {
+                    value: true,
                     location: Location {
                         start: Position {
-                            index: 4,
+                            index: 14,
                             line: 1,
-                            column: 5
+                            column: 15
                         },
                         end: Position {
-                            index: 5,
+                            index: 18,
                             line: 1,
-                            column: 6
+                            column: 19
                         }
-                    }
+                    },
                 })),
                 location: Location {
                     start: Position {
@@ -1973,67 +4841,54 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 5,
+                        index: 19,
                         line: 1,
-                        column: 6
+                        column: 20
                     }
-                }
+                },
             }))
         );
     }
 
     #[test]
-    fn expression_returns_less_than_or_equal_expression() {
-        let source = "1 <= 2";
+    fn statement_returns_variable_definition_statement_without_type() {
+        let source = "let x = 5;";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 0,
-                            line: 1,
-                            column: 1
-                        },
-                        end: Position {
-                            index: 1,
-                            line: 1,
-                            column: 2
-                        }
-                    }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::LessThanOrEqual,
+            Some(Statement::VariableDefinition(VariableDefinition {
+                name: Identifier {
+                    name: "x".to_string(),
                     location: Location {
                         start: Position {
-                            index: 2,
+                            index: 4,
                             line: 1,
-                            column: 3
+                            column: 5
                         },
                         end: Position {
-                            index: 4,
+                            index: 5,
                             line: 1,
-                            column: 5
+                            column: 6
                         }
                     }
                 },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
+                mutable: false,
+                variable_type: None,
+                value: Some(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "5".to_string(),
                     location: Location {
                         start: Position {
-                            index: 5,
+                            index: 8,
                             line: 1,
-                            column: 6
+                            column: 9
                         },
                         end: Position {
-                            index: 6,
+                            index: 9,
                             line: 1,
-                            column: 7
+                            column: 10
                         }
-                    }
+                    },
                 })),
                 location: Location {
                     start: Position {
@@ -2042,67 +4897,130 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 6,
+                        index: 10,
                         line: 1,
-                        column: 7
+                        column: 11
                     }
-                }
+                },
             }))
-        )
+        );
     }
 
     #[test]
-    fn expression_returns_greater_than() {
-        let source = "1 > 2";
+    fn statement_returns_variable_definition_statement_with_block_expression() {
+        let source = "let y = { let x = 1; x };";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 0,
-                            line: 1,
-                            column: 1
-                        },
-                        end: Position {
-                            index: 1,
-                            line: 1,
-                            column: 2
-                        }
-                    }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::GreaterThan,
+            Some(Statement::VariableDefinition(VariableDefinition {
+                name: Identifier {
+                    name: "y".to_string(),
                     location: Location {
                         start: Position {
-                            index: 2,
+                            index: 4,
                             line: 1,
-                            column: 3
+                            column: 5
                         },
                         end: Position {
-                            index: 3,
+                            index: 5,
                             line: 1,
-                            column: 4
+                            column: 6
                         }
                     }
                 },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
+                mutable: false,
+                variable_type: None,
+                value: Some(Expression::Block(Block {
+                    statements: Statements {
+                        statements: vec![
+                            Statement::VariableDefinition(VariableDefinition {
+                                name: Identifier {
+                                    name: "x".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 14,
+                                            line: 1,
+                                            column: 15
+                                        },
+                                        end: Position {
+                                            index: 15,
+                                            line: 1,
+                                            column: 16
+                                        }
+                                    }
+                                },
+                                mutable: false,
+                                variable_type: None,
+                                value: Some(Expression::IntegerLiteral(IntegerLiteral {
+                                    value: "1".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 18,
+                                            line: 1,
+                                            column: 19
+                                        },
+                                        end: Position {
+                                            index: 19,
+                                            line: 1,
+                                            column: 20
+                                        }
+                                    },
+                                })),
+                                location: Location {
+                                    start: Position {
+                                        index: 10,
+                                        line: 1,
+                                        column: 11
+                                    },
+                                    end: Position {
+                                        index: 20,
+                                        line: 1,
+                                        column: 21
+                                    }
+                                },
+                            }),
+                            Statement::Expression(Expression::Identifier(Identifier {
+                                name: "x".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 21,
+                                        line: 1,
+                                        column: 22
+                                    },
+                                    end: Position {
+                                        index: 22,
+                                        line: 1,
+                                        column: 23
+                                    }
+                                }
+                            })),
+                        ],
+                        location: Location {
+                            start: Position {
+                                index: 8,
+                                line: 1,
+                                column: 9
+                            },
+                            end: Position {
+                                index: 24,
+                                line: 1,
+                                column: 25
+                            }
+                        },
+                    },
                     location: Location {
                         start: Position {
-                            index: 4,
+                            index: 8,
                             line: 1,
-                            column: 5
+                            column: 9
                         },
                         end: Position {
-                            index: 5,
+                            index: 24,
                             line: 1,
-                            column: 6
+                            column: 25
                         }
-                    }
+                    },
                 })),
                 location: Location {
                     start: Position {
@@ -2110,69 +5028,178 @@ mod tests {
                         line: 1,
                         column: 1
                     },
-                    end: Position {
-                        index: 5,
-                        line: 1,
-                        column: 6
-                    }
+                    end: Position {
+                        index: 25,
+                        line: 1,
+                        column: 26
+                    }
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn statement_returns_destructuring_definition_statement() {
+        let source = "var (q, r) = (1, x);";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).statement();
+        assert_eq!(
+            ast,
+            Some(Statement::DestructuringDefinition(
+                DestructuringDefinition {
+                    names: vec![
+                        Identifier {
+                            name: "q".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 5,
+                                    line: 1,
+                                    column: 6
+                                },
+                                end: Position {
+                                    index: 6,
+                                    line: 1,
+                                    column: 7
+                                }
+                            }
+                        },
+                        Identifier {
+                            name: "r".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                },
+                                end: Position {
+                                    index: 9,
+                                    line: 1,
+                                    column: 10
+                                }
+                            }
+                        }
+                    ],
+                    mutable: true,
+                    value: Expression::TupleLiteral(TupleLiteral {
+                        elements: vec![
+                            Expression::IntegerLiteral(IntegerLiteral {
+                                value: "1".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 14,
+                                        line: 1,
+                                        column: 15
+                                    },
+                                    end: Position {
+                                        index: 15,
+                                        line: 1,
+                                        column: 16
+                                    }
+                                }
+                            }),
+                            Expression::Identifier(Identifier {
+                                name: "x".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 17,
+                                        line: 1,
+                                        column: 18
+                                    },
+                                    end: Position {
+                                        index: 18,
+                                        line: 1,
+                                        column: 19
+                                    }
+                                }
+                            }),
+                        ],
+                        location: Location {
+                            start: Position {
+                                index: 13,
+                                line: 1,
+                                column: 14
+                            },
+                            end: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20
+                            }
+                        },
+                    }),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 20,
+                            line: 1,
+                            column: 21
+                        }
+                    },
                 }
-            }))
+            ))
         );
     }
 
     #[test]
-    fn expression_returns_greater_than_or_equal() {
-        let source = "1 >= 2";
+    fn type_returns_tuple_type() {
+        let source = "(i32, [u64])";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).r#type();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 0,
-                            line: 1,
-                            column: 1
-                        },
-                        end: Position {
-                            index: 1,
-                            line: 1,
-                            column: 2
+            Some(Type {
+                name: TypeKind::Tuple(vec![
+                    Type {
+                        name: TypeKind::I32,
+                        location: Location {
+                            start: Position {
+                                index: 1,
+                                line: 1,
+                                column: 2
+                            },
+                            end: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            }
                         }
-                    }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::GreaterThanOrEqual,
-                    location: Location {
-                        start: Position {
-                            index: 2,
-                            line: 1,
-                            column: 3
+                    },
+                    Type {
+                        name: TypeKind::Array {
+                            element: Box::new(Type {
+                                name: TypeKind::U64,
+                                location: Location {
+                                    start: Position {
+                                        index: 7,
+                                        line: 1,
+                                        column: 8
+                                    },
+                                    end: Position {
+                                        index: 10,
+                                        line: 1,
+                                        column: 11
+                                    }
+                                }
+                            }),
+                            length: None,
                         },
-                        end: Position {
-                            index: 4,
-                            line: 1,
-                            column: 5
-                        }
-                    }
-                },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 5,
-                            line: 1,
-                            column: 6
+                        location: Location {
+                            start: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7
+                            },
+                            end: Position {
+                                index: 11,
+                                line: 1,
+                                column: 12
+                            }
                         },
-                        end: Position {
-                            index: 6,
-                            line: 1,
-                            column: 7
-                        }
-                    }
-                })),
+                    },
+                ]),
                 location: Location {
                     start: Position {
                         index: 0,
@@ -2180,65 +5207,126 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 6,
+                        index: 12,
                         line: 1,
-                        column: 7
+                        column: 13
                     }
-                }
-            }))
+                },
+            })
         );
     }
 
     #[test]
-    fn expression_return_equal_expression() {
-        let source = "1 == 2";
+    fn statement_returns_array_variable_definition_statement() {
+        let source = "let a: [i64; 3] = [0; 3];";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
+            Some(Statement::VariableDefinition(VariableDefinition {
+                name: Identifier {
+                    name: "a".to_string(),
                     location: Location {
                         start: Position {
-                            index: 0,
+                            index: 4,
                             line: 1,
-                            column: 1
+                            column: 5
                         },
                         end: Position {
-                            index: 1,
+                            index: 5,
                             line: 1,
-                            column: 2
+                            column: 6
                         }
                     }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::Equal,
+                },
+                mutable: false,
+                variable_type: Some(Type {
+                    name: TypeKind::Array {
+                        element: Box::new(Type {
+                            name: TypeKind::I64,
+                            location: Location {
+                                start: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                },
+                                end: Position {
+                                    index: 11,
+                                    line: 1,
+                                    column: 12
+                                }
+                            }
+                        }),
+                        length: Some(IntegerLiteral {
+                            value: "3".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 13,
+                                    line: 1,
+                                    column: 14
+                                },
+                                end: Position {
+                                    index: 14,
+                                    line: 1,
+                                    column: 15
+                                }
+                            }
+                        })
+                    },
                     location: Location {
                         start: Position {
-                            index: 2,
+                            index: 7,
                             line: 1,
-                            column: 3
+                            column: 8
                         },
                         end: Position {
-                            index: 4,
+                            index: 15,
                             line: 1,
-                            column: 5
+                            column: 16
                         }
                     }
-                },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
+                }),
+                value: Some(Expression::ArrayRepeat(ArrayRepeat {
+                    value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "0".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20
+                            },
+                            end: Position {
+                                index: 20,
+                                line: 1,
+                                column: 21
+                            }
+                        }
+                    })),
+                    length: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "3".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 22,
+                                line: 1,
+                                column: 23
+                            },
+                            end: Position {
+                                index: 23,
+                                line: 1,
+                                column: 24
+                            }
+                        }
+                    })),
                     location: Location {
                         start: Position {
-                            index: 5,
+                            index: 18,
                             line: 1,
-                            column: 6
+                            column: 19
                         },
                         end: Position {
-                            index: 6,
+                            index: 24,
                             line: 1,
-                            column: 7
+                            column: 25
                         }
                     }
                 })),
@@ -2249,25 +5337,83 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 6,
+                        index: 25,
                         line: 1,
-                        column: 7
+                        column: 26
                     }
-                }
+                },
             }))
         );
     }
 
     #[test]
-    fn expression_return_not_equal_expression() {
-        let source = "1 != 2";
+    fn expression_returns_field_access() {
+        let source = "p.a[0].b";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "1".to_string(),
+            Some(Expression::FieldAccess(FieldAccess {
+                object: Box::new(Expression::IndexExpression(IndexExpression {
+                    array: Box::new(Expression::FieldAccess(FieldAccess {
+                        object: Box::new(Expression::Identifier(Identifier {
+                            name: "p".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 0,
+                                    line: 1,
+                                    column: 1
+                                },
+                                end: Position {
+                                    index: 1,
+                                    line: 1,
+                                    column: 2
+                                }
+                            }
+                        })),
+                        field: Identifier {
+                            name: "a".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 2,
+                                    line: 1,
+                                    column: 3
+                                },
+                                end: Position {
+                                    index: 3,
+                                    line: 1,
+                                    column: 4
+                                }
+                            }
+                        },
+                        location: Location {
+                            start: Position {
+                                index: 0,
+                                line: 1,
+                                column: 1
+                            },
+                            end: Position {
+                                index: 3,
+                                line: 1,
+                                column: 4
+                            }
+                        }
+                    })),
+                    index: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "0".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            },
+                            end: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6
+                            }
+                        }
+                    })),
                     location: Location {
                         start: Position {
                             index: 0,
@@ -2275,42 +5421,27 @@ mod tests {
                             column: 1
                         },
                         end: Position {
-                            index: 1,
+                            index: 6,
                             line: 1,
-                            column: 2
+                            column: 7
                         }
                     }
                 })),
-                operator: Operator {
-                    operator: OperatorKind::NotEqual,
+                field: Identifier {
+                    name: "b".to_string(),
                     location: Location {
                         start: Position {
-                            index: 2,
+                            index: 7,
                             line: 1,
-                            column: 3
+                            column: 8
                         },
                         end: Position {
-                            index: 4,
+                            index: 8,
                             line: 1,
-                            column: 5
+                            column: 9
                         }
                     }
                 },
-                right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "2".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 5,
-                            line: 1,
-                            column: 6
-                        },
-                        end: Position {
-                            index: 6,
-                            line: 1,
-                            column: 7
-                        }
-                    }
-                })),
                 location: Location {
                     start: Position {
                         index: 0,
@@ -2318,9 +5449,9 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 6,
+                        index: 8,
                         line: 1,
-                        column: 7
+                        column: 9
                     }
                 }
             }))
@@ -2328,60 +5459,16 @@ mod tests {
     }
 
     #[test]
-    fn expression_return_comparison_expression_with_calculation() {
-        let source = "1 + 2 == 3 * 4 != 5 / 6";
+    fn expression_returns_field_assignment_expression() {
+        let source = "p.x = 1";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::BinaryExpression(BinaryExpression {
-                    left: Box::new(Expression::BinaryExpression(BinaryExpression {
-                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "1".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 0,
-                                    line: 1,
-                                    column: 1
-                                },
-                                end: Position {
-                                    index: 1,
-                                    line: 1,
-                                    column: 2
-                                }
-                            }
-                        })),
-                        operator: Operator {
-                            operator: OperatorKind::Add,
-                            location: Location {
-                                start: Position {
-                                    index: 2,
-                                    line: 1,
-                                    column: 3
-                                },
-                                end: Position {
-                                    index: 3,
-                                    line: 1,
-                                    column: 4
-                                }
-                            }
-                        },
-                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "2".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 4,
-                                    line: 1,
-                                    column: 5
-                                },
-                                end: Position {
-                                    index: 5,
-                                    line: 1,
-                                    column: 6
-                                }
-                            }
-                        })),
+            Some(Expression::FieldAssignmentExpression(
+                FieldAssignmentExpression {
+                    object: Box::new(Expression::Identifier(Identifier {
+                        name: "p".to_string(),
                         location: Location {
                             start: Position {
                                 index: 0,
@@ -2389,83 +5476,39 @@ mod tests {
                                 column: 1
                             },
                             end: Position {
-                                index: 5,
+                                index: 1,
                                 line: 1,
-                                column: 6
+                                column: 2
                             }
                         }
                     })),
-                    operator: Operator {
-                        operator: OperatorKind::Equal,
+                    field: Identifier {
+                        name: "x".to_string(),
                         location: Location {
                             start: Position {
-                                index: 6,
+                                index: 2,
                                 line: 1,
-                                column: 7
+                                column: 3
                             },
                             end: Position {
-                                index: 8,
+                                index: 3,
                                 line: 1,
-                                column: 9
+                                column: 4
                             }
                         }
                     },
-                    right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "3".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 9,
-                                    line: 1,
-                                    column: 10
-                                },
-                                end: Position {
-                                    index: 10,
-                                    line: 1,
-                                    column: 11
-                                }
-                            }
-                        })),
-                        operator: Operator {
-                            operator: OperatorKind::Multiply,
-                            location: Location {
-                                start: Position {
-                                    index: 11,
-                                    line: 1,
-                                    column: 12
-                                },
-                                end: Position {
-                                    index: 12,
-                                    line: 1,
-                                    column: 13
-                                }
-                            }
-                        },
-                        right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "4".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 13,
-                                    line: 1,
-                                    column: 14
-                                },
-                                end: Position {
-                                    index: 14,
-                                    line: 1,
-                                    column: 15
-                                }
-                            }
-                        })),
+                    value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                        value: "1".to_string(),
                         location: Location {
                             start: Position {
-                                index: 9,
+                                index: 6,
                                 line: 1,
-                                column: 10
+                                column: 7
                             },
                             end: Position {
-                                index: 14,
+                                index: 7,
                                 line: 1,
-                                column: 15
+                                column: 8
                             }
                         }
                     })),
@@ -2476,86 +5519,129 @@ mod tests {
                             column: 1
                         },
                         end: Position {
-                            index: 14,
+                            index: 7,
                             line: 1,
-                            column: 15
+                            column: 8
                         }
                     }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::NotEqual,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn expression_returns_struct_literal() {
+        let source = "Point { x: 1, y: z }";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::StructLiteral(StructLiteral {
+                name: Identifier {
+                    name: "Point".to_string(),
                     location: Location {
                         start: Position {
-                            index: 15,
+                            index: 0,
                             line: 1,
-                            column: 16
+                            column: 1
                         },
                         end: Position {
-                            index: 17,
+                            index: 5,
                             line: 1,
-                            column: 18
+                            column: 6
                         }
                     }
                 },
-                right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                    left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "5".to_string(),
-                        location: Location {
-                            start: Position {
-                                index: 18,
-                                line: 1,
-                                column: 19
-                            },
-                            end: Position {
-                                index: 19,
-                                line: 1,
-                                column: 20
+                fields: vec![
+                    FieldInitializer {
+                        name: Identifier {
+                            name: "x".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                },
+                                end: Position {
+                                    index: 9,
+                                    line: 1,
+                                    column: 10
+                                }
                             }
-                        }
-                    })),
-                    operator: Operator {
-                        operator: OperatorKind::Divide,
+                        },
+                        value: Expression::IntegerLiteral(IntegerLiteral {
+                            value: "1".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 11,
+                                    line: 1,
+                                    column: 12
+                                },
+                                end: Position {
+                                    index: 12,
+                                    line: 1,
+                                    column: 13
+                                }
+                            }
+                        }),
                         location: Location {
                             start: Position {
-                                index: 20,
+                                index: 8,
                                 line: 1,
-                                column: 21
+                                column: 9
                             },
                             end: Position {
-                                index: 21,
+                                index: 12,
                                 line: 1,
-                                column: 22
+                                column: 13
                             }
                         }
                     },
-                    right: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                        value: "6".to_string(),
+                    FieldInitializer {
+                        name: Identifier {
+                            name: "y".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 14,
+                                    line: 1,
+                                    column: 15
+                                },
+                                end: Position {
+                                    index: 15,
+                                    line: 1,
+                                    column: 16
+                                }
+                            }
+                        },
+                        value: Expression::Identifier(Identifier {
+                            name: "z".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 17,
+                                    line: 1,
+                                    column: 18
+                                },
+                                end: Position {
+                                    index: 18,
+                                    line: 1,
+                                    column: 19
+                                }
+                            }
+                        }),
                         location: Location {
                             start: Position {
-                                index: 22,
+                                index: 14,
                                 line: 1,
-                                column: 23
+                                column: 15
                             },
                             end: Position {
-                                index: 23,
+                                index: 18,
                                 line: 1,
-                                column: 24
+                                column: 19
                             }
                         }
-                    })),
-                    location: Location {
-                        start: Position {
-                            index: 18,
-                            line: 1,
-                            column: 19
-                        },
-                        end: Position {
-                            index: 23,
-                            line: 1,
-                            column: 24
-                        }
                     }
-                })),
+                ],
                 location: Location {
                     start: Position {
                         index: 0,
@@ -2563,25 +5649,25 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 23,
+                        index: 20,
                         line: 1,
-                        column: 24
+                        column: 21
                     }
                 }
             }))
-        )
+        );
     }
 
     #[test]
-    fn expression_return_logical_and_expression() {
-        let source = "a && b";
+    fn statement_returns_expression_statement() {
+        let source = "x;";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::Identifier(Identifier {
-                    name: "a".to_string(),
+            Some(Statement::ExpressionStatement(ExpressionStatement {
+                expression: Expression::Identifier(Identifier {
+                    name: "x".to_string(),
                     location: Location {
                         start: Position {
                             index: 0,
@@ -2594,37 +5680,7 @@ mod tests {
                             column: 2
                         }
                     }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::LogicalAnd,
-                    location: Location {
-                        start: Position {
-                            index: 2,
-                            line: 1,
-                            column: 3
-                        },
-                        end: Position {
-                            index: 4,
-                            line: 1,
-                            column: 5
-                        }
-                    }
-                },
-                right: Box::new(Expression::Identifier(Identifier {
-                    name: "b".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 5,
-                            line: 1,
-                            column: 6
-                        },
-                        end: Position {
-                            index: 6,
-                            line: 1,
-                            column: 7
-                        }
-                    }
-                })),
+                }),
                 location: Location {
                     start: Position {
                         index: 0,
@@ -2632,65 +5688,74 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 6,
+                        index: 2,
                         line: 1,
-                        column: 7
+                        column: 3
                     }
                 }
             }))
-        )
+        );
     }
 
     #[test]
-    fn expression_return_logical_or_expression() {
-        let source = "a || b";
+    fn statement_returns_none_when_expression() {
+        let source = "x";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).statement();
+        assert_eq!(ast, None);
+    }
+
+    #[test]
+    fn statement_returns_mutable_variable_definition_statement() {
+        let source = "var x: i32 = 0;";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Expression::BinaryExpression(BinaryExpression {
-                left: Box::new(Expression::Identifier(Identifier {
-                    name: "a".to_string(),
+            Some(Statement::VariableDefinition(VariableDefinition {
+                name: Identifier {
+                    name: "x".to_string(),
                     location: Location {
                         start: Position {
-                            index: 0,
+                            index: 4,
                             line: 1,
-                            column: 1
+                            column: 5
                         },
                         end: Position {
-                            index: 1,
+                            index: 5,
                             line: 1,
-                            column: 2
+                            column: 6
                         }
                     }
-                })),
-                operator: Operator {
-                    operator: OperatorKind::LogicalOr,
+                },
+                mutable: true,
+                variable_type: Some(Type {
+                    name: TypeKind::I32,
                     location: Location {
                         start: Position {
-                            index: 2,
+                            index: 7,
                             line: 1,
-                            column: 3
+                            column: 8
                         },
                         end: Position {
-                            index: 4,
+                            index: 10,
                             line: 1,
-                            column: 5
+                            column: 11
                         }
                     }
-                },
-                right: Box::new(Expression::Identifier(Identifier {
-                    name: "b".to_string(),
+                }),
+                value: Some(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "0".to_string(),
                     location: Location {
                         start: Position {
-                            index: 5,
+                            index: 13,
                             line: 1,
-                            column: 6
+                            column: 14
                         },
                         end: Position {
-                            index: 6,
+                            index: 14,
                             line: 1,
-                            column: 7
+                            column: 15
                         }
                     }
                 })),
@@ -2701,50 +5766,66 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 6,
+                        index: 15,
                         line: 1,
-                        column: 7
+                        column: 16
                     }
                 }
             }))
-        )
+        );
     }
 
     #[test]
-    fn expression_return_logical_not_expression() {
-        let source = "!a";
+    fn statement_returns_immutable_variable_definition_statement() {
+        let source = "let x: i32 = 0;";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
+        let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Expression::UnaryExpression(UnaryExpression {
-                operator: Operator {
-                    operator: OperatorKind::LogicalNot,
+            Some(Statement::VariableDefinition(VariableDefinition {
+                name: Identifier {
+                    name: "x".to_string(),
                     location: Location {
                         start: Position {
-                            index: 0,
+                            index: 4,
                             line: 1,
-                            column: 1
+                            column: 5
                         },
                         end: Position {
-                            index: 1,
+                            index: 5,
                             line: 1,
-                            column: 2
+                            column: 6
                         }
                     }
                 },
-                operand: Box::new(Expression::Identifier(Identifier {
-                    name: "a".to_string(),
+                mutable: false,
+                variable_type: Some(Type {
+                    name: TypeKind::I32,
                     location: Location {
                         start: Position {
-                            index: 1,
+                            index: 7,
                             line: 1,
-                            column: 2
+                            column: 8
                         },
                         end: Position {
-                            index: 2,
+                            index: 10,
                             line: 1,
-                            column: 3
+                            column: 11
+                        }
+                    }
+                }),
+                value: Some(Expression::IntegerLiteral(IntegerLiteral {
+                    value: "0".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 13,
+                            line: 1,
+                            column: 14
+                        },
+                        end: Position {
+                            index: 14,
+                            line: 1,
+                            column: 15
                         }
                     }
                 })),
@@ -2755,228 +5836,152 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 2,
+                        index: 15,
                         line: 1,
-                        column: 3
+                        column: 16
                     }
                 }
             }))
         );
-    }
-
-    #[test]
-    fn expression_return_repeated_unary_expression() {
-        let source = "!!- -!  !a";
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).expression();
-        assert_eq!(
-            ast,
-            Some(Expression::UnaryExpression(UnaryExpression {
-                operator: Operator {
-                    operator: OperatorKind::LogicalNot,
-                    location: Location {
-                        start: Position {
-                            index: 0,
-                            line: 1,
-                            column: 1,
-                        },
-                        end: Position {
-                            index: 1,
-                            line: 1,
-                            column: 2,
-                        },
-                    },
-                },
-                operand: Box::new(Expression::UnaryExpression(UnaryExpression {
-                    operator: Operator {
-                        operator: OperatorKind::LogicalNot,
-                        location: Location {
-                            start: Position {
-                                index: 1,
-                                line: 1,
-                                column: 2,
-                            },
-                            end: Position {
-                                index: 2,
-                                line: 1,
-                                column: 3,
-                            },
-                        },
-                    },
-                    operand: Box::new(Expression::BinaryExpression(BinaryExpression {
-                        left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                            value: "0".to_string(),
-                            location: Location {
-                                start: Position {
-                                    index: 2,
-                                    line: 1,
-                                    column: 3,
-                                },
-                                end: Position {
-                                    index: 2,
-                                    line: 1,
-                                    column: 3,
-                                },
-                            },
-                        },)),
-                        operator: Operator {
-                            operator: OperatorKind::Subtract,
-                            location: Location {
-                                start: Position {
-                                    index: 2,
-                                    line: 1,
-                                    column: 3,
-                                },
-                                end: Position {
-                                    index: 3,
-                                    line: 1,
-                                    column: 4,
-                                },
-                            },
+    }
+
+    #[test]
+    fn statement_returns_if_statement() {
+        let source = "if condition { 1; } else { 0; }";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).statement();
+        assert_eq!(
+            ast,
+            Some(Statement::IfStatement(IfStatement {
+                condition: Expression::Identifier(Identifier {
+                    name: "condition".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4,
                         },
-                        right: Box::new(Expression::BinaryExpression(BinaryExpression {
-                            left: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                                value: "0".to_string(),
+                        end: Position {
+                            index: 12,
+                            line: 1,
+                            column: 13,
+                        },
+                    },
+                },),
+                then_block: Block {
+                    statements: Statements {
+                        statements: vec![Statement::ExpressionStatement(ExpressionStatement {
+                            expression: Expression::IntegerLiteral(IntegerLiteral {
+                                value: "1".to_string(),
                                 location: Location {
                                     start: Position {
-                                        index: 4,
+                                        index: 15,
                                         line: 1,
-                                        column: 5,
+                                        column: 16,
                                     },
                                     end: Position {
-                                        index: 4,
+                                        index: 16,
                                         line: 1,
-                                        column: 5,
+                                        column: 17,
                                     },
                                 },
-                            },)),
-                            operator: Operator {
-                                operator: OperatorKind::Subtract,
-                                location: Location {
-                                    start: Position {
-                                        index: 4,
-                                        line: 1,
-                                        column: 5,
-                                    },
-                                    end: Position {
-                                        index: 5,
-                                        line: 1,
-                                        column: 6,
-                                    },
+                            },),
+                            location: Location {
+                                start: Position {
+                                    index: 15,
+                                    line: 1,
+                                    column: 16,
                                 },
-                            },
-                            right: Box::new(Expression::UnaryExpression(UnaryExpression {
-                                operator: Operator {
-                                    operator: OperatorKind::LogicalNot,
-                                    location: Location {
-                                        start: Position {
-                                            index: 5,
-                                            line: 1,
-                                            column: 6,
-                                        },
-                                        end: Position {
-                                            index: 6,
-                                            line: 1,
-                                            column: 7,
-                                        },
-                                    },
+                                end: Position {
+                                    index: 17,
+                                    line: 1,
+                                    column: 18,
                                 },
-                                operand: Box::new(Expression::UnaryExpression(UnaryExpression {
-                                    operator: Operator {
-                                        operator: OperatorKind::LogicalNot,
-                                        location: Location {
-                                            start: Position {
-                                                index: 8,
-                                                line: 1,
-                                                column: 9,
-                                            },
-                                            end: Position {
-                                                index: 9,
-                                                line: 1,
-                                                column: 10,
-                                            },
-                                        },
-                                    },
-                                    operand: Box::new(Expression::Identifier(Identifier {
-                                        name: "a".to_string(),
-                                        location: Location {
-                                            start: Position {
-                                                index: 9,
-                                                line: 1,
-                                                column: 10,
-                                            },
-                                            end: Position {
-                                                index: 10,
-                                                line: 1,
-                                                column: 11,
-                                            },
-                                        },
-                                    },)),
-                                    location: Location {
-                                        start: Position {
-                                            index: 8,
-                                            line: 1,
-                                            column: 9,
-                                        },
-                                        end: Position {
-                                            index: 10,
-                                            line: 1,
-                                            column: 11,
-                                        },
-                                    },
-                                },)),
+                            },
+                        },),],
+                        location: Location {
+                            start: Position {
+                                index: 13,
+                                line: 1,
+                                column: 14,
+                            },
+                            end: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20,
+                            },
+                        },
+                    },
+                    location: Location {
+                        start: Position {
+                            index: 13,
+                            line: 1,
+                            column: 14,
+                        },
+                        end: Position {
+                            index: 19,
+                            line: 1,
+                            column: 20,
+                        },
+                    },
+                },
+                else_block: Some(Block {
+                    statements: Statements {
+                        statements: vec![Statement::ExpressionStatement(ExpressionStatement {
+                            expression: Expression::IntegerLiteral(IntegerLiteral {
+                                value: "0".to_string(),
                                 location: Location {
                                     start: Position {
-                                        index: 5,
+                                        index: 27,
                                         line: 1,
-                                        column: 6,
+                                        column: 28,
                                     },
                                     end: Position {
-                                        index: 10,
+                                        index: 28,
                                         line: 1,
-                                        column: 11,
+                                        column: 29,
                                     },
                                 },
-                            },)),
+                            },),
                             location: Location {
                                 start: Position {
-                                    index: 4,
+                                    index: 27,
                                     line: 1,
-                                    column: 5,
+                                    column: 28,
                                 },
                                 end: Position {
-                                    index: 10,
+                                    index: 29,
                                     line: 1,
-                                    column: 11,
+                                    column: 30,
                                 },
                             },
-                        },)),
+                        },),],
                         location: Location {
                             start: Position {
-                                index: 2,
+                                index: 25,
                                 line: 1,
-                                column: 3,
+                                column: 26,
                             },
                             end: Position {
-                                index: 10,
+                                index: 31,
                                 line: 1,
-                                column: 11,
+                                column: 32,
                             },
                         },
-                    },)),
+                    },
                     location: Location {
                         start: Position {
-                            index: 1,
+                            index: 25,
                             line: 1,
-                            column: 2,
+                            column: 26,
                         },
                         end: Position {
-                            index: 10,
+                            index: 31,
                             line: 1,
-                            column: 11,
+                            column: 32,
                         },
                     },
-                },)),
+                },),
                 location: Location {
                     start: Position {
                         index: 0,
@@ -2984,9 +5989,9 @@ mod tests {
                         column: 1,
                     },
                     end: Position {
-                        index: 10,
+                        index: 31,
                         line: 1,
-                        column: 11,
+                        column: 32,
                     },
                 },
             },),)
@@ -2994,176 +5999,264 @@ mod tests {
     }
 
     #[test]
-    fn statement_returns_expression_statement() {
-        let source = "x;";
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).statement();
-        assert_eq!(
-            ast,
-            Some(Statement::ExpressionStatement(ExpressionStatement {
-                expression: Expression::Identifier(Identifier {
-                    name: "x".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 0,
-                            line: 1,
-                            column: 1
-                        },
-                        end: Position {
-                            index: 1,
-                            line: 1,
-                            column: 2
-                        }
-                    }
-                }),
-                location: Location {
-                    start: Position {
-                        index: 0,
-                        line: 1,
-                        column: 1
-                    },
-                    end: Position {
-                        index: 2,
-                        line: 1,
-                        column: 3
-                    }
-                }
-            }))
-        );
-    }
-
-    #[test]
-    fn statement_returns_none_when_expression() {
-        let source = "x";
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).statement();
-        assert_eq!(ast, None);
-    }
-
-    #[test]
-    fn statement_returns_mutable_variable_definition_statement() {
-        let source = "var x: i32 = 0;";
+    fn statement_returns_if_statement_without_else() {
+        let source = "if condition { 1; }";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).statement();
         assert_eq!(
             ast,
-            Some(Statement::VariableDefinition(VariableDefinition {
-                name: Identifier {
-                    name: "x".to_string(),
+            Some(Statement::IfStatement(IfStatement {
+                condition: Expression::Identifier(Identifier {
+                    name: "condition".to_string(),
                     location: Location {
                         start: Position {
-                            index: 4,
+                            index: 3,
                             line: 1,
-                            column: 5
+                            column: 4,
                         },
                         end: Position {
-                            index: 5,
-                            line: 1,
-                            column: 6
-                        }
-                    }
-                },
-                mutable: true,
-                variable_type: Type {
-                    name: TypeKind::I32,
-                    location: Location {
-                        start: Position {
-                            index: 7,
+                            index: 12,
                             line: 1,
-                            column: 8
+                            column: 13,
                         },
-                        end: Position {
-                            index: 10,
-                            line: 1,
-                            column: 11
-                        }
-                    }
-                },
-                value: Some(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "0".to_string(),
+                    },
+                },),
+                then_block: Block {
+                    statements: Statements {
+                        statements: vec![Statement::ExpressionStatement(ExpressionStatement {
+                            expression: Expression::IntegerLiteral(IntegerLiteral {
+                                value: "1".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 15,
+                                        line: 1,
+                                        column: 16,
+                                    },
+                                    end: Position {
+                                        index: 16,
+                                        line: 1,
+                                        column: 17,
+                                    },
+                                },
+                            },),
+                            location: Location {
+                                start: Position {
+                                    index: 15,
+                                    line: 1,
+                                    column: 16,
+                                },
+                                end: Position {
+                                    index: 17,
+                                    line: 1,
+                                    column: 18,
+                                },
+                            },
+                        },),],
+                        location: Location {
+                            start: Position {
+                                index: 13,
+                                line: 1,
+                                column: 14,
+                            },
+                            end: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20,
+                            },
+                        },
+                    },
                     location: Location {
                         start: Position {
                             index: 13,
                             line: 1,
-                            column: 14
+                            column: 14,
                         },
                         end: Position {
-                            index: 14,
+                            index: 19,
                             line: 1,
-                            column: 15
-                        }
-                    }
-                })),
+                            column: 20,
+                        },
+                    },
+                },
+                else_block: None,
                 location: Location {
                     start: Position {
                         index: 0,
                         line: 1,
-                        column: 1
+                        column: 1,
                     },
                     end: Position {
-                        index: 15,
+                        index: 19,
                         line: 1,
-                        column: 16
-                    }
-                }
-            }))
-        );
+                        column: 20,
+                    },
+                },
+            },),)
+        )
     }
 
     #[test]
-    fn statement_returns_immutable_variable_definition_statement() {
-        let source = "let x: i32 = 0;";
+    fn block_returns_none_when_multiple_expressions() {
+        let source = indoc! {"
+            {
+                1
+                2
+            }
+        "};
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).statement();
+        let ast = Parser::new(tokens).block();
+        assert_eq!(ast, None);
+    }
+
+    #[test]
+    fn block_returns_statements() {
+        let source = indoc! {"
+            {
+                var x: i32;
+                x = 0;
+                x
+            }
+        "};
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).block();
         assert_eq!(
             ast,
-            Some(Statement::VariableDefinition(VariableDefinition {
-                name: Identifier {
-                    name: "x".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 4,
-                            line: 1,
-                            column: 5
-                        },
-                        end: Position {
-                            index: 5,
-                            line: 1,
-                            column: 6
-                        }
-                    }
-                },
-                mutable: false,
-                variable_type: Type {
-                    name: TypeKind::I32,
+            Some(Block {
+                statements: Statements {
+                    statements: vec![
+                        Statement::VariableDefinition(VariableDefinition {
+                            name: Identifier {
+                                name: "x".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 10,
+                                        line: 2,
+                                        column: 9
+                                    },
+                                    end: Position {
+                                        index: 11,
+                                        line: 2,
+                                        column: 10
+                                    }
+                                }
+                            },
+                            mutable: true,
+                            variable_type: Some(Type {
+                                name: TypeKind::I32,
+                                location: Location {
+                                    start: Position {
+                                        index: 13,
+                                        line: 2,
+                                        column: 12
+                                    },
+                                    end: Position {
+                                        index: 16,
+                                        line: 2,
+                                        column: 15
+                                    }
+                                }
+                            }),
+                            value: None,
+                            location: Location {
+                                start: Position {
+                                    index: 6,
+                                    line: 2,
+                                    column: 5
+                                },
+                                end: Position {
+                                    index: 17,
+                                    line: 2,
+                                    column: 16
+                                }
+                            }
+                        }),
+                        Statement::ExpressionStatement(ExpressionStatement {
+                            expression: Expression::AssignmentExpression(AssignmentExpression {
+                                name: Identifier {
+                                    name: "x".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 22,
+                                            line: 3,
+                                            column: 5
+                                        },
+                                        end: Position {
+                                            index: 23,
+                                            line: 3,
+                                            column: 6
+                                        }
+                                    }
+                                },
+                                value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
+                                    value: "0".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 26,
+                                            line: 3,
+                                            column: 9
+                                        },
+                                        end: Position {
+                                            index: 27,
+                                            line: 3,
+                                            column: 10
+                                        }
+                                    }
+                                })),
+                                location: Location {
+                                    start: Position {
+                                        index: 22,
+                                        line: 3,
+                                        column: 5
+                                    },
+                                    end: Position {
+                                        index: 27,
+                                        line: 3,
+                                        column: 10
+                                    }
+                                }
+                            }),
+                            location: Location {
+                                start: Position {
+                                    index: 22,
+                                    line: 3,
+                                    column: 5
+                                },
+                                end: Position {
+                                    index: 28,
+                                    line: 3,
+                                    column: 11
+                                }
+                            }
+                        }),
+                        Statement::Expression(Expression::Identifier(Identifier {
+                            name: "x".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 33,
+                                    line: 4,
+                                    column: 5
+                                },
+                                end: Position {
+                                    index: 34,
+                                    line: 4,
+                                    column: 6
+                                }
+                            }
+                        }))
+                    ],
                     location: Location {
                         start: Position {
-                            index: 7,
+                            index: 0,
                             line: 1,
-                            column: 8
+                            column: 1
                         },
                         end: Position {
-                            index: 10,
-                            line: 1,
-                            column: 11
+                            index: 36,
+                            line: 5,
+                            column: 2
                         }
                     }
                 },
-                value: Some(Expression::IntegerLiteral(IntegerLiteral {
-                    value: "0".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 13,
-                            line: 1,
-                            column: 14
-                        },
-                        end: Position {
-                            index: 14,
-                            line: 1,
-                            column: 15
-                        }
-                    }
-                })),
                 location: Location {
                     start: Position {
                         index: 0,
@@ -3171,25 +6264,116 @@ mod tests {
                         column: 1
                     },
                     end: Position {
-                        index: 15,
-                        line: 1,
-                        column: 16
+                        index: 36,
+                        line: 5,
+                        column: 2
                     }
                 }
-            }))
-        );
+            })
+        )
     }
 
     #[test]
-    fn statement_returns_if_statement() {
-        let source = "if condition { 1; } else { 0; }";
+    fn parse_returns_function_definition_without_parameters() {
+        let source = indoc! {"
+            fn main() -> i32 { 0 }
+        "};
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).statement();
+        let ast = Parser::new(tokens).parse();
         assert_eq!(
             ast,
-            Some(Statement::IfStatement(IfStatement {
-                condition: Expression::Identifier(Identifier {
-                    name: "condition".to_string(),
+            Program {
+                functions: vec![FunctionDefinition {
+                    name: Identifier {
+                        name: "main".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 3,
+                                line: 1,
+                                column: 4,
+                            },
+                            end: Position {
+                                index: 7,
+                                line: 1,
+                                column: 8,
+                            },
+                        },
+                    },
+                    parameters: Parameters {
+                        parameters: vec![],
+                        location: Location {
+                            start: Position {
+                                index: 7,
+                                line: 1,
+                                column: 8,
+                            },
+                            end: Position {
+                                index: 9,
+                                line: 1,
+                                column: 10,
+                            },
+                        },
+                    },
+                    return_type: Type {
+                        name: TypeKind::I32,
+                        location: Location {
+                            start: Position {
+                                index: 13,
+                                line: 1,
+                                column: 14,
+                            },
+                            end: Position {
+                                index: 16,
+                                line: 1,
+                                column: 17,
+                            },
+                        },
+                    },
+                    body: Block {
+                        statements: Statements {
+                            statements: vec![Statement::Expression(Expression::IntegerLiteral(
+                                IntegerLiteral {
+                                    value: "0".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 19,
+                                            line: 1,
+                                            column: 20,
+                                        },
+                                        end: Position {
+                                            index: 20,
+                                            line: 1,
+                                            column: 21,
+                                        },
+                                    },
+                                },
+                            ),),],
+                            location: Location {
+                                start: Position {
+                                    index: 17,
+                                    line: 1,
+                                    column: 18,
+                                },
+                                end: Position {
+                                    index: 22,
+                                    line: 1,
+                                    column: 23,
+                                },
+                            },
+                        },
+                        location: Location {
+                            start: Position {
+                                index: 17,
+                                line: 1,
+                                column: 18,
+                            },
+                            end: Position {
+                                index: 22,
+                                line: 1,
+                                column: 23,
+                            },
+                        },
+                    },
                     location: Location {
                         start: Position {
                             index: 3,
@@ -3197,421 +6381,662 @@ mod tests {
                             column: 4,
                         },
                         end: Position {
-                            index: 12,
+                            index: 22,
                             line: 1,
-                            column: 13,
+                            column: 23,
                         },
                     },
-                },),
-                then_block: Block {
-                    statements: Statements {
-                        statements: vec![Statement::ExpressionStatement(ExpressionStatement {
-                            expression: Expression::IntegerLiteral(IntegerLiteral {
-                                value: "1".to_string(),
+                },],
+                structs: Vec::new(),
+                enums: Vec::new(),
+                constants: Vec::new(),
+                statics: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_returns_function_definition_without_return_type() {
+        let source = indoc! {"
+            fn f(a: ()) {}
+        "};
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).parse();
+        assert_eq!(
+            ast,
+            Program {
+                functions: vec![FunctionDefinition {
+                    name: Identifier {
+                        name: "f".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 3,
+                                line: 1,
+                                column: 4
+                            },
+                            end: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            }
+                        }
+                    },
+                    parameters: Parameters {
+                        parameters: vec![Parameter {
+                            name: Identifier {
+                                name: "a".to_string(),
                                 location: Location {
                                     start: Position {
-                                        index: 15,
+                                        index: 5,
                                         line: 1,
-                                        column: 16,
+                                        column: 6
                                     },
                                     end: Position {
-                                        index: 16,
+                                        index: 6,
                                         line: 1,
-                                        column: 17,
+                                        column: 7
+                                    }
+                                }
+                            },
+                            parameter_type: Type {
+                                name: TypeKind::Unit,
+                                location: Location {
+                                    start: Position {
+                                        index: 8,
+                                        line: 1,
+                                        column: 9
                                     },
-                                },
-                            },),
+                                    end: Position {
+                                        index: 10,
+                                        line: 1,
+                                        column: 11
+                                    }
+                                }
+                            },
                             location: Location {
                                 start: Position {
-                                    index: 15,
+                                    index: 5,
                                     line: 1,
-                                    column: 16,
+                                    column: 6
                                 },
                                 end: Position {
-                                    index: 17,
+                                    index: 10,
                                     line: 1,
-                                    column: 18,
-                                },
+                                    column: 11
+                                }
                             },
-                        },),],
+                        }],
+                        location: Location {
+                            start: Position {
+                                index: 4,
+                                line: 1,
+                                column: 5
+                            },
+                            end: Position {
+                                index: 11,
+                                line: 1,
+                                column: 12
+                            }
+                        },
+                    },
+                    return_type: Type {
+                        name: TypeKind::Unit,
+                        location: Location {
+                            start: Position {
+                                index: 11,
+                                line: 1,
+                                column: 12
+                            },
+                            end: Position {
+                                index: 11,
+                                line: 1,
+                                column: 12
+                            }
+                        }
+                    },
+                    body: Block {
+                        statements: Statements {
+                            statements: vec![],
+                            location: Location {
+                                start: Position {
+                                    index: 12,
+                                    line: 1,
+                                    column: 13
+                                },
+                                end: Position {
+                                    index: 14,
+                                    line: 1,
+                                    column: 15
+                                }
+                            }
+                        },
                         location: Location {
                             start: Position {
-                                index: 13,
+                                index: 12,
                                 line: 1,
-                                column: 14,
+                                column: 13
                             },
                             end: Position {
-                                index: 19,
+                                index: 14,
                                 line: 1,
-                                column: 20,
-                            },
+                                column: 15
+                            }
                         },
                     },
                     location: Location {
                         start: Position {
-                            index: 13,
+                            index: 3,
                             line: 1,
-                            column: 14,
+                            column: 4
                         },
                         end: Position {
-                            index: 19,
+                            index: 14,
                             line: 1,
-                            column: 20,
-                        },
+                            column: 15
+                        }
                     },
-                },
-                else_block: Some(Block {
-                    statements: Statements {
-                        statements: vec![Statement::ExpressionStatement(ExpressionStatement {
-                            expression: Expression::IntegerLiteral(IntegerLiteral {
-                                value: "0".to_string(),
+                }],
+                structs: Vec::new(),
+                enums: Vec::new(),
+                constants: Vec::new(),
+                statics: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_returns_function_definition_with_parameters() {
+        let source = indoc! {"
+            fn add(x: i64, y: i64) -> i64 {
+                x + y
+            }
+        "};
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).parse();
+        assert_eq!(
+            ast,
+            Program {
+                functions: vec![FunctionDefinition {
+                    name: Identifier {
+                        name: "add".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 3,
+                                line: 1,
+                                column: 4
+                            },
+                            end: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7
+                            }
+                        }
+                    },
+                    parameters: Parameters {
+                        parameters: vec![
+                            Parameter {
+                                name: Identifier {
+                                    name: "x".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 7,
+                                            line: 1,
+                                            column: 8
+                                        },
+                                        end: Position {
+                                            index: 8,
+                                            line: 1,
+                                            column: 9
+                                        }
+                                    }
+                                },
+                                parameter_type: Type {
+                                    name: TypeKind::I64,
+                                    location: Location {
+                                        start: Position {
+                                            index: 10,
+                                            line: 1,
+                                            column: 11
+                                        },
+                                        end: Position {
+                                            index: 13,
+                                            line: 1,
+                                            column: 14
+                                        }
+                                    }
+                                },
                                 location: Location {
                                     start: Position {
-                                        index: 27,
+                                        index: 7,
                                         line: 1,
-                                        column: 28,
+                                        column: 8
                                     },
                                     end: Position {
-                                        index: 28,
+                                        index: 13,
                                         line: 1,
-                                        column: 29,
-                                    },
+                                        column: 14
+                                    }
+                                }
+                            },
+                            Parameter {
+                                name: Identifier {
+                                    name: "y".to_string(),
+                                    location: Location {
+                                        start: Position {
+                                            index: 15,
+                                            line: 1,
+                                            column: 16
+                                        },
+                                        end: Position {
+                                            index: 16,
+                                            line: 1,
+                                            column: 17
+                                        }
+                                    }
                                 },
-                            },),
+                                parameter_type: Type {
+                                    name: TypeKind::I64,
+                                    location: Location {
+                                        start: Position {
+                                            index: 18,
+                                            line: 1,
+                                            column: 19
+                                        },
+                                        end: Position {
+                                            index: 21,
+                                            line: 1,
+                                            column: 22
+                                        }
+                                    }
+                                },
+                                location: Location {
+                                    start: Position {
+                                        index: 15,
+                                        line: 1,
+                                        column: 16
+                                    },
+                                    end: Position {
+                                        index: 21,
+                                        line: 1,
+                                        column: 22
+                                    }
+                                }
+                            }
+                        ],
+                        location: Location {
+                            start: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7
+                            },
+                            end: Position {
+                                index: 22,
+                                line: 1,
+                                column: 23
+                            }
+                        }
+                    },
+                    return_type: Type {
+                        name: TypeKind::I64,
+                        location: Location {
+                            start: Position {
+                                index: 26,
+                                line: 1,
+                                column: 27
+                            },
+                            end: Position {
+                                index: 29,
+                                line: 1,
+                                column: 30
+                            }
+                        }
+                    },
+                    body: Block {
+                        statements: Statements {
+                            statements: vec![Statement::Expression(Expression::BinaryExpression(
+                                BinaryExpression {
+                                    left: Box::new(Expression::Identifier(Identifier {
+                                        name: "x".to_string(),
+                                        location: Location {
+                                            start: Position {
+                                                index: 36,
+                                                line: 2,
+                                                column: 5
+                                            },
+                                            end: Position {
+                                                index: 37,
+                                                line: 2,
+                                                column: 6
+                                            }
+                                        }
+                                    })),
+                                    operator: Operator {
+                                        operator: OperatorKind::Add,
+                                        location: Location {
+                                            start: Position {
+                                                index: 38,
+                                                line: 2,
+                                                column: 7
+                                            },
+                                            end: Position {
+                                                index: 39,
+                                                line: 2,
+                                                column: 8
+                                            }
+                                        }
+                                    },
+                                    right: Box::new(Expression::Identifier(Identifier {
+                                        name: "y".to_string(),
+                                        location: Location {
+                                            start: Position {
+                                                index: 40,
+                                                line: 2,
+                                                column: 9
+                                            },
+                                            end: Position {
+                                                index: 41,
+                                                line: 2,
+                                                column: 10
+                                            }
+                                        }
+                                    })),
+                                    location: Location {
+                                        start: Position {
+                                            index: 36,
+                                            line: 2,
+                                            column: 5
+                                        },
+                                        end: Position {
+                                            index: 41,
+                                            line: 2,
+                                            column: 10
+                                        }
+                                    }
+                                }
+                            ))],
                             location: Location {
                                 start: Position {
-                                    index: 27,
+                                    index: 30,
                                     line: 1,
-                                    column: 28,
+                                    column: 31
                                 },
                                 end: Position {
-                                    index: 29,
-                                    line: 1,
-                                    column: 30,
-                                },
-                            },
-                        },),],
+                                    index: 43,
+                                    line: 3,
+                                    column: 2
+                                }
+                            }
+                        },
                         location: Location {
                             start: Position {
-                                index: 25,
+                                index: 30,
                                 line: 1,
-                                column: 26,
+                                column: 31
                             },
                             end: Position {
-                                index: 31,
-                                line: 1,
-                                column: 32,
-                            },
-                        },
+                                index: 43,
+                                line: 3,
+                                column: 2
+                            }
+                        }
                     },
                     location: Location {
                         start: Position {
-                            index: 25,
+                            index: 3,
                             line: 1,
-                            column: 26,
+                            column: 4
                         },
                         end: Position {
-                            index: 31,
-                            line: 1,
-                            column: 32,
-                        },
-                    },
-                },),
-                location: Location {
-                    start: Position {
-                        index: 0,
-                        line: 1,
-                        column: 1,
-                    },
-                    end: Position {
-                        index: 31,
-                        line: 1,
-                        column: 32,
-                    },
-                },
-            },),)
+                            index: 43,
+                            line: 3,
+                            column: 2
+                        }
+                    }
+                }],
+                structs: Vec::new(),
+                enums: Vec::new(),
+                constants: Vec::new(),
+                statics: Vec::new(),
+            }
         )
     }
 
     #[test]
-    fn statement_returns_if_statement_without_else() {
-        let source = "if condition { 1; }";
+    fn parse_returns_function_definitions() {
+        let source = indoc! {"
+            fn foo() -> i64 { 0 }
+            fn bar() -> i32 { 1 }
+        "};
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).statement();
+        let ast = Parser::new(tokens).parse();
         assert_eq!(
             ast,
-            Some(Statement::IfStatement(IfStatement {
-                condition: Expression::Identifier(Identifier {
-                    name: "condition".to_string(),
-                    location: Location {
-                        start: Position {
-                            index: 3,
-                            line: 1,
-                            column: 4,
-                        },
-                        end: Position {
-                            index: 12,
-                            line: 1,
-                            column: 13,
-                        },
-                    },
-                },),
-                then_block: Block {
-                    statements: Statements {
-                        statements: vec![Statement::ExpressionStatement(ExpressionStatement {
-                            expression: Expression::IntegerLiteral(IntegerLiteral {
-                                value: "1".to_string(),
-                                location: Location {
-                                    start: Position {
-                                        index: 15,
-                                        line: 1,
-                                        column: 16,
-                                    },
-                                    end: Position {
-                                        index: 16,
-                                        line: 1,
-                                        column: 17,
-                                    },
-                                },
-                            },),
+            Program {
+                functions: vec![
+                    FunctionDefinition {
+                        name: Identifier {
+                            name: "foo".to_string(),
                             location: Location {
                                 start: Position {
-                                    index: 15,
+                                    index: 3,
                                     line: 1,
-                                    column: 16,
+                                    column: 4
                                 },
                                 end: Position {
-                                    index: 17,
+                                    index: 6,
                                     line: 1,
-                                    column: 18,
-                                },
-                            },
-                        },),],
-                        location: Location {
-                            start: Position {
-                                index: 13,
-                                line: 1,
-                                column: 14,
-                            },
-                            end: Position {
-                                index: 19,
-                                line: 1,
-                                column: 20,
-                            },
+                                    column: 7
+                                }
+                            }
                         },
-                    },
-                    location: Location {
-                        start: Position {
-                            index: 13,
-                            line: 1,
-                            column: 14,
+                        parameters: Parameters {
+                            parameters: vec![],
+                            location: Location {
+                                start: Position {
+                                    index: 6,
+                                    line: 1,
+                                    column: 7
+                                },
+                                end: Position {
+                                    index: 8,
+                                    line: 1,
+                                    column: 9
+                                }
+                            }
                         },
-                        end: Position {
-                            index: 19,
-                            line: 1,
-                            column: 20,
+                        return_type: Type {
+                            name: TypeKind::I64,
+                            location: Location {
+                                start: Position {
+                                    index: 12,
+                                    line: 1,
+                                    column: 13
+                                },
+                                end: Position {
+                                    index: 15,
+                                    line: 1,
+                                    column: 16
+                                }
+                            }
                         },
-                    },
-                },
-                else_block: None,
-                location: Location {
-                    start: Position {
-                        index: 0,
-                        line: 1,
-                        column: 1,
-                    },
-                    end: Position {
-                        index: 19,
-                        line: 1,
-                        column: 20,
-                    },
-                },
-            },),)
-        )
-    }
-
-    #[test]
-    fn block_returns_none_when_multiple_expressions() {
-        let source = indoc! {"
-            {
-                1
-                2
-            }
-        "};
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).block();
-        assert_eq!(ast, None);
-    }
-
-    #[test]
-    fn block_returns_statements() {
-        let source = indoc! {"
-            {
-                var x: i32;
-                x = 0;
-                x
-            }
-        "};
-        let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).block();
-        assert_eq!(
-            ast,
-            Some(Block {
-                statements: Statements {
-                    statements: vec![
-                        Statement::VariableDefinition(VariableDefinition {
-                            name: Identifier {
-                                name: "x".to_string(),
+                        body: Block {
+                            statements: Statements {
+                                statements: vec![Statement::Expression(
+                                    Expression::IntegerLiteral(IntegerLiteral {
+                                        value: "0".to_string(),
+                                        location: Location {
+                                            start: Position {
+                                                index: 18,
+                                                line: 1,
+                                                column: 19
+                                            },
+                                            end: Position {
+                                                index: 19,
+                                                line: 1,
+                                                column: 20
+                                            }
+                                        }
+                                    })
+                                )],
                                 location: Location {
                                     start: Position {
-                                        index: 10,
-                                        line: 2,
-                                        column: 9
+                                        index: 16,
+                                        line: 1,
+                                        column: 17
                                     },
                                     end: Position {
-                                        index: 11,
-                                        line: 2,
-                                        column: 10
+                                        index: 21,
+                                        line: 1,
+                                        column: 22
                                     }
                                 }
                             },
-                            mutable: true,
-                            variable_type: Type {
-                                name: TypeKind::I32,
-                                location: Location {
-                                    start: Position {
-                                        index: 13,
-                                        line: 2,
-                                        column: 12
-                                    },
-                                    end: Position {
-                                        index: 16,
-                                        line: 2,
-                                        column: 15
-                                    }
+                            location: Location {
+                                start: Position {
+                                    index: 16,
+                                    line: 1,
+                                    column: 17
+                                },
+                                end: Position {
+                                    index: 21,
+                                    line: 1,
+                                    column: 22
                                 }
+                            }
+                        },
+                        location: Location {
+                            start: Position {
+                                index: 3,
+                                line: 1,
+                                column: 4
                             },
-                            value: None,
+                            end: Position {
+                                index: 21,
+                                line: 1,
+                                column: 22
+                            }
+                        }
+                    },
+                    FunctionDefinition {
+                        name: Identifier {
+                            name: "bar".to_string(),
                             location: Location {
                                 start: Position {
-                                    index: 6,
+                                    index: 25,
                                     line: 2,
-                                    column: 5
+                                    column: 4
                                 },
                                 end: Position {
-                                    index: 17,
+                                    index: 28,
                                     line: 2,
-                                    column: 16
+                                    column: 7
                                 }
                             }
-                        }),
-                        Statement::ExpressionStatement(ExpressionStatement {
-                            expression: Expression::AssignmentExpression(AssignmentExpression {
-                                name: Identifier {
-                                    name: "x".to_string(),
-                                    location: Location {
-                                        start: Position {
-                                            index: 22,
-                                            line: 3,
-                                            column: 5
-                                        },
-                                        end: Position {
-                                            index: 23,
-                                            line: 3,
-                                            column: 6
-                                        }
-                                    }
+                        },
+                        parameters: Parameters {
+                            parameters: vec![],
+                            location: Location {
+                                start: Position {
+                                    index: 28,
+                                    line: 2,
+                                    column: 7
                                 },
-                                value: Box::new(Expression::IntegerLiteral(IntegerLiteral {
-                                    value: "0".to_string(),
-                                    location: Location {
-                                        start: Position {
-                                            index: 26,
-                                            line: 3,
-                                            column: 9
-                                        },
-                                        end: Position {
-                                            index: 27,
-                                            line: 3,
-                                            column: 10
-                                        }
-                                    }
-                                })),
-                                location: Location {
-                                    start: Position {
-                                        index: 22,
-                                        line: 3,
-                                        column: 5
-                                    },
-                                    end: Position {
-                                        index: 27,
-                                        line: 3,
-                                        column: 10
-                                    }
+                                end: Position {
+                                    index: 30,
+                                    line: 2,
+                                    column: 9
                                 }
-                            }),
+                            }
+                        },
+                        return_type: Type {
+                            name: TypeKind::I32,
                             location: Location {
                                 start: Position {
-                                    index: 22,
-                                    line: 3,
-                                    column: 5
+                                    index: 34,
+                                    line: 2,
+                                    column: 13
                                 },
                                 end: Position {
-                                    index: 28,
-                                    line: 3,
-                                    column: 11
+                                    index: 37,
+                                    line: 2,
+                                    column: 16
                                 }
                             }
-                        }),
-                        Statement::Expression(Expression::Identifier(Identifier {
-                            name: "x".to_string(),
+                        },
+                        body: Block {
+                            statements: Statements {
+                                statements: vec![Statement::Expression(
+                                    Expression::IntegerLiteral(IntegerLiteral {
+                                        value: "1".to_string(),
+                                        location: Location {
+                                            start: Position {
+                                                index: 40,
+                                                line: 2,
+                                                column: 19
+                                            },
+                                            end: Position {
+                                                index: 41,
+                                                line: 2,
+                                                column: 20
+                                            }
+                                        }
+                                    })
+                                )],
+                                location: Location {
+                                    start: Position {
+                                        index: 38,
+                                        line: 2,
+                                        column: 17
+                                    },
+                                    end: Position {
+                                        index: 43,
+                                        line: 2,
+                                        column: 22
+                                    }
+                                }
+                            },
                             location: Location {
                                 start: Position {
-                                    index: 33,
-                                    line: 4,
-                                    column: 5
+                                    index: 38,
+                                    line: 2,
+                                    column: 17
                                 },
                                 end: Position {
-                                    index: 34,
-                                    line: 4,
-                                    column: 6
+                                    index: 43,
+                                    line: 2,
+                                    column: 22
                                 }
                             }
-                        }))
-                    ],
-                    location: Location {
-                        start: Position {
-                            index: 0,
-                            line: 1,
-                            column: 1
                         },
-                        end: Position {
-                            index: 36,
-                            line: 5,
-                            column: 2
+                        location: Location {
+                            start: Position {
+                                index: 25,
+                                line: 2,
+                                column: 4
+                            },
+                            end: Position {
+                                index: 43,
+                                line: 2,
+                                column: 22
+                            }
                         }
                     }
-                },
-                location: Location {
-                    start: Position {
-                        index: 0,
-                        line: 1,
-                        column: 1
-                    },
-                    end: Position {
-                        index: 36,
-                        line: 5,
-                        column: 2
-                    }
-                }
-            })
+                ],
+                structs: Vec::new(),
+                enums: Vec::new(),
+                constants: Vec::new(),
+                statics: Vec::new(),
+            }
         )
     }
 
     #[test]
-    fn parse_returns_function_definition_without_parameters() {
+    fn parse_returns_function_when_comments() {
         let source = indoc! {"
-            fn main() -> i32 { 0 }
+            /// Main function
+            fn main(/* empty */) -> i32 /* 0 or 1 */ {
+                /* return */ 0 // return 0
+            }
         "};
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).parse();
@@ -3623,46 +7048,46 @@ mod tests {
                         name: "main".to_string(),
                         location: Location {
                             start: Position {
-                                index: 3,
-                                line: 1,
-                                column: 4,
+                                index: 21,
+                                line: 2,
+                                column: 4
                             },
                             end: Position {
-                                index: 7,
-                                line: 1,
-                                column: 8,
-                            },
-                        },
+                                index: 25,
+                                line: 2,
+                                column: 8
+                            }
+                        }
                     },
                     parameters: Parameters {
                         parameters: vec![],
                         location: Location {
                             start: Position {
-                                index: 7,
-                                line: 1,
-                                column: 8,
+                                index: 25,
+                                line: 2,
+                                column: 8
                             },
                             end: Position {
-                                index: 9,
-                                line: 1,
-                                column: 10,
-                            },
-                        },
+                                index: 38,
+                                line: 2,
+                                column: 21
+                            }
+                        }
                     },
                     return_type: Type {
                         name: TypeKind::I32,
                         location: Location {
                             start: Position {
-                                index: 13,
-                                line: 1,
-                                column: 14,
+                                index: 42,
+                                line: 2,
+                                column: 25
                             },
                             end: Position {
-                                index: 16,
-                                line: 1,
-                                column: 17,
-                            },
-                        },
+                                index: 45,
+                                line: 2,
+                                column: 28
+                            }
+                        }
                     },
                     body: Block {
                         statements: Statements {
@@ -3671,656 +7096,1047 @@ mod tests {
                                     value: "0".to_string(),
                                     location: Location {
                                         start: Position {
-                                            index: 19,
-                                            line: 1,
-                                            column: 20,
+                                            index: 78,
+                                            line: 3,
+                                            column: 18
                                         },
                                         end: Position {
-                                            index: 20,
-                                            line: 1,
-                                            column: 21,
-                                        },
-                                    },
-                                },
-                            ),),],
+                                            index: 79,
+                                            line: 3,
+                                            column: 19
+                                        }
+                                    }
+                                }
+                            ))],
                             location: Location {
                                 start: Position {
-                                    index: 17,
-                                    line: 1,
-                                    column: 18,
+                                    index: 59,
+                                    line: 2,
+                                    column: 42
                                 },
                                 end: Position {
-                                    index: 22,
-                                    line: 1,
-                                    column: 23,
-                                },
-                            },
+                                    index: 93,
+                                    line: 4,
+                                    column: 2
+                                }
+                            }
                         },
                         location: Location {
                             start: Position {
-                                index: 17,
-                                line: 1,
-                                column: 18,
+                                index: 59,
+                                line: 2,
+                                column: 42
                             },
                             end: Position {
-                                index: 22,
-                                line: 1,
-                                column: 23,
-                            },
-                        },
+                                index: 93,
+                                line: 4,
+                                column: 2
+                            }
+                        }
                     },
                     location: Location {
                         start: Position {
-                            index: 3,
-                            line: 1,
-                            column: 4,
+                            index: 21,
+                            line: 2,
+                            column: 4
                         },
                         end: Position {
-                            index: 22,
-                            line: 1,
-                            column: 23,
-                        },
-                    },
-                },],
+                            index: 93,
+                            line: 4,
+                            column: 2
+                        }
+                    }
+                }],
+                structs: Vec::new(),
+                enums: Vec::new(),
+                constants: Vec::new(),
+                statics: Vec::new(),
             }
         );
     }
 
     #[test]
-    fn parse_returns_function_definition_with_parameters() {
-        let source = indoc! {"
-            fn add(x: i64, y: i64) -> i64 {
-                x + y
-            }
-        "};
+    fn parse_returns_struct_definition() {
+        let source = "struct P { x: i32, y: Q }";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).parse();
         assert_eq!(
             ast,
             Program {
-                functions: vec![FunctionDefinition {
+                functions: Vec::new(),
+                structs: vec![StructDefinition {
                     name: Identifier {
-                        name: "add".to_string(),
+                        name: "P".to_string(),
                         location: Location {
                             start: Position {
-                                index: 3,
+                                index: 7,
                                 line: 1,
-                                column: 4
+                                column: 8
                             },
                             end: Position {
-                                index: 6,
+                                index: 8,
                                 line: 1,
-                                column: 7
+                                column: 9
                             }
                         }
                     },
-                    parameters: Parameters {
-                        parameters: vec![
-                            Parameter {
-                                name: Identifier {
-                                    name: "x".to_string(),
-                                    location: Location {
-                                        start: Position {
-                                            index: 7,
-                                            line: 1,
-                                            column: 8
-                                        },
-                                        end: Position {
-                                            index: 8,
-                                            line: 1,
-                                            column: 9
-                                        }
-                                    }
-                                },
-                                parameter_type: Type {
-                                    name: TypeKind::I64,
-                                    location: Location {
-                                        start: Position {
-                                            index: 10,
-                                            line: 1,
-                                            column: 11
-                                        },
-                                        end: Position {
-                                            index: 13,
-                                            line: 1,
-                                            column: 14
-                                        }
-                                    }
-                                },
+                    fields: vec![
+                        Field {
+                            name: Identifier {
+                                name: "x".to_string(),
                                 location: Location {
                                     start: Position {
-                                        index: 7,
+                                        index: 11,
                                         line: 1,
-                                        column: 8
+                                        column: 12
                                     },
                                     end: Position {
-                                        index: 13,
+                                        index: 12,
                                         line: 1,
-                                        column: 14
+                                        column: 13
                                     }
                                 }
                             },
-                            Parameter {
-                                name: Identifier {
-                                    name: "y".to_string(),
-                                    location: Location {
-                                        start: Position {
-                                            index: 15,
-                                            line: 1,
-                                            column: 16
-                                        },
-                                        end: Position {
-                                            index: 16,
-                                            line: 1,
-                                            column: 17
-                                        }
+                            field_type: Type {
+                                name: TypeKind::I32,
+                                location: Location {
+                                    start: Position {
+                                        index: 14,
+                                        line: 1,
+                                        column: 15
+                                    },
+                                    end: Position {
+                                        index: 17,
+                                        line: 1,
+                                        column: 18
                                     }
+                                }
+                            },
+                            location: Location {
+                                start: Position {
+                                    index: 11,
+                                    line: 1,
+                                    column: 12
                                 },
-                                parameter_type: Type {
-                                    name: TypeKind::I64,
-                                    location: Location {
-                                        start: Position {
-                                            index: 18,
-                                            line: 1,
-                                            column: 19
-                                        },
-                                        end: Position {
-                                            index: 21,
-                                            line: 1,
-                                            column: 22
-                                        }
+                                end: Position {
+                                    index: 17,
+                                    line: 1,
+                                    column: 18
+                                }
+                            }
+                        },
+                        Field {
+                            name: Identifier {
+                                name: "y".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 19,
+                                        line: 1,
+                                        column: 20
+                                    },
+                                    end: Position {
+                                        index: 20,
+                                        line: 1,
+                                        column: 21
                                     }
-                                },
+                                }
+                            },
+                            field_type: Type {
+                                name: TypeKind::Named("Q".to_string()),
                                 location: Location {
                                     start: Position {
-                                        index: 15,
+                                        index: 22,
                                         line: 1,
-                                        column: 16
+                                        column: 23
                                     },
                                     end: Position {
-                                        index: 21,
+                                        index: 23,
                                         line: 1,
-                                        column: 22
+                                        column: 24
                                     }
                                 }
+                            },
+                            location: Location {
+                                start: Position {
+                                    index: 19,
+                                    line: 1,
+                                    column: 20
+                                },
+                                end: Position {
+                                    index: 23,
+                                    line: 1,
+                                    column: 24
+                                }
                             }
-                        ],
-                        location: Location {
-                            start: Position {
-                                index: 6,
-                                line: 1,
-                                column: 7
+                        }
+                    ],
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 25,
+                            line: 1,
+                            column: 26
+                        }
+                    },
+                }],
+                enums: Vec::new(),
+                constants: Vec::new(),
+                statics: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn expression_returns_match_expression_with_variant_patterns() {
+        let source = "match e { E::A => 1, E::B(x, _) => x, }";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::MatchExpression(MatchExpression {
+                value: Box::new(Expression::Identifier(Identifier {
+                    name: "e".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7,
+                        },
+                        end: Position {
+                            index: 7,
+                            line: 1,
+                            column: 8,
+                        },
+                    },
+                },)),
+                arms: vec![
+                    MatchArm {
+                        pattern: Pattern::Variant(VariantPattern {
+                            enum_name: Identifier {
+                                name: "E".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 10,
+                                        line: 1,
+                                        column: 11,
+                                    },
+                                    end: Position {
+                                        index: 11,
+                                        line: 1,
+                                        column: 12,
+                                    },
+                                },
+                            },
+                            variant: Identifier {
+                                name: "A".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 13,
+                                        line: 1,
+                                        column: 14,
+                                    },
+                                    end: Position {
+                                        index: 14,
+                                        line: 1,
+                                        column: 15,
+                                    },
+                                },
+                            },
+                            fields: Vec::new(),
+                            location: Location {
+                                start: Position {
+                                    index: 10,
+                                    line: 1,
+                                    column: 11,
+                                },
+                                end: Position {
+                                    index: 14,
+                                    line: 1,
+                                    column: 15,
+                                },
                             },
-                            end: Position {
-                                index: 22,
-                                line: 1,
-                                column: 23
-                            }
-                        }
-                    },
-                    return_type: Type {
-                        name: TypeKind::I64,
+                        },),
+                        expression: Expression::IntegerLiteral(IntegerLiteral {
+                            value: "1".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 18,
+                                    line: 1,
+                                    column: 19,
+                                },
+                                end: Position {
+                                    index: 19,
+                                    line: 1,
+                                    column: 20,
+                                },
+                            },
+                        },),
                         location: Location {
                             start: Position {
-                                index: 26,
+                                index: 10,
                                 line: 1,
-                                column: 27
+                                column: 11,
                             },
                             end: Position {
-                                index: 29,
+                                index: 19,
                                 line: 1,
-                                column: 30
-                            }
-                        }
+                                column: 20,
+                            },
+                        },
                     },
-                    body: Block {
-                        statements: Statements {
-                            statements: vec![Statement::Expression(Expression::BinaryExpression(
-                                BinaryExpression {
-                                    left: Box::new(Expression::Identifier(Identifier {
-                                        name: "x".to_string(),
-                                        location: Location {
-                                            start: Position {
-                                                index: 36,
-                                                line: 2,
-                                                column: 5
-                                            },
-                                            end: Position {
-                                                index: 37,
-                                                line: 2,
-                                                column: 6
-                                            }
-                                        }
-                                    })),
-                                    operator: Operator {
-                                        operator: OperatorKind::Add,
-                                        location: Location {
-                                            start: Position {
-                                                index: 38,
-                                                line: 2,
-                                                column: 7
-                                            },
-                                            end: Position {
-                                                index: 39,
-                                                line: 2,
-                                                column: 8
-                                            }
-                                        }
+                    MatchArm {
+                        pattern: Pattern::Variant(VariantPattern {
+                            enum_name: Identifier {
+                                name: "E".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 21,
+                                        line: 1,
+                                        column: 22,
                                     },
-                                    right: Box::new(Expression::Identifier(Identifier {
-                                        name: "y".to_string(),
-                                        location: Location {
-                                            start: Position {
-                                                index: 40,
-                                                line: 2,
-                                                column: 9
-                                            },
-                                            end: Position {
-                                                index: 41,
-                                                line: 2,
-                                                column: 10
-                                            }
-                                        }
-                                    })),
+                                    end: Position {
+                                        index: 22,
+                                        line: 1,
+                                        column: 23,
+                                    },
+                                },
+                            },
+                            variant: Identifier {
+                                name: "B".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 24,
+                                        line: 1,
+                                        column: 25,
+                                    },
+                                    end: Position {
+                                        index: 25,
+                                        line: 1,
+                                        column: 26,
+                                    },
+                                },
+                            },
+                            fields: vec![
+                                Pattern::Binding(Identifier {
+                                    name: "x".to_string(),
                                     location: Location {
                                         start: Position {
-                                            index: 36,
-                                            line: 2,
-                                            column: 5
+                                            index: 26,
+                                            line: 1,
+                                            column: 27,
                                         },
                                         end: Position {
-                                            index: 41,
-                                            line: 2,
-                                            column: 10
-                                        }
-                                    }
-                                }
-                            ))],
+                                            index: 27,
+                                            line: 1,
+                                            column: 28,
+                                        },
+                                    },
+                                },),
+                                Pattern::Wildcard(Location {
+                                    start: Position {
+                                        index: 29,
+                                        line: 1,
+                                        column: 30,
+                                    },
+                                    end: Position {
+                                        index: 30,
+                                        line: 1,
+                                        column: 31,
+                                    },
+                                },),
+                            ],
                             location: Location {
                                 start: Position {
-                                    index: 30,
+                                    index: 21,
                                     line: 1,
-                                    column: 31
+                                    column: 22,
                                 },
                                 end: Position {
-                                    index: 43,
-                                    line: 3,
-                                    column: 2
-                                }
-                            }
-                        },
+                                    index: 31,
+                                    line: 1,
+                                    column: 32,
+                                },
+                            },
+                        },),
+                        expression: Expression::Identifier(Identifier {
+                            name: "x".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 35,
+                                    line: 1,
+                                    column: 36,
+                                },
+                                end: Position {
+                                    index: 36,
+                                    line: 1,
+                                    column: 37,
+                                },
+                            },
+                        },),
                         location: Location {
                             start: Position {
-                                index: 30,
+                                index: 21,
                                 line: 1,
-                                column: 31
+                                column: 22,
                             },
                             end: Position {
-                                index: 43,
-                                line: 3,
-                                column: 2
-                            }
-                        }
-                    },
-                    location: Location {
-                        start: Position {
-                            index: 3,
-                            line: 1,
-                            column: 4
+                                index: 36,
+                                line: 1,
+                                column: 37,
+                            },
                         },
-                        end: Position {
-                            index: 43,
-                            line: 3,
-                            column: 2
-                        }
-                    }
-                }]
-            }
-        )
+                    },
+                ],
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 39,
+                        line: 1,
+                        column: 40,
+                    },
+                },
+            },),)
+        );
     }
 
     #[test]
-    fn parse_returns_function_definitions() {
-        let source = indoc! {"
-            fn foo() -> i64 { 0 }
-            fn bar() -> i32 { 1 }
-        "};
+    fn expression_returns_match_expression_with_integer_patterns() {
+        let source = "match n { -1 => 0, 0..=9 => 1, 10..100 => 2, n => n }";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
-        let ast = Parser::new(tokens).parse();
+        let ast = Parser::new(tokens).expression();
         assert_eq!(
             ast,
-            Program {
-                functions: vec![
-                    FunctionDefinition {
-                        name: Identifier {
-                            name: "foo".to_string(),
+            Some(Expression::MatchExpression(MatchExpression {
+                value: Box::new(Expression::Identifier(Identifier {
+                    name: "n".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 6,
+                            line: 1,
+                            column: 7,
+                        },
+                        end: Position {
+                            index: 7,
+                            line: 1,
+                            column: 8,
+                        },
+                    },
+                },)),
+                arms: vec![
+                    MatchArm {
+                        pattern: Pattern::IntegerLiteral(IntegerLiteral {
+                            value: "-1".to_string(),
                             location: Location {
                                 start: Position {
-                                    index: 3,
+                                    index: 10,
                                     line: 1,
-                                    column: 4
+                                    column: 11,
                                 },
                                 end: Position {
-                                    index: 6,
+                                    index: 12,
                                     line: 1,
-                                    column: 7
-                                }
-                            }
+                                    column: 13,
+                                },
+                            },
+                        },),
+                        expression: Expression::IntegerLiteral(IntegerLiteral {
+                            value: "0".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 16,
+                                    line: 1,
+                                    column: 17,
+                                },
+                                end: Position {
+                                    index: 17,
+                                    line: 1,
+                                    column: 18,
+                                },
+                            },
+                        },),
+                        location: Location {
+                            start: Position {
+                                index: 10,
+                                line: 1,
+                                column: 11,
+                            },
+                            end: Position {
+                                index: 17,
+                                line: 1,
+                                column: 18,
+                            },
                         },
-                        parameters: Parameters {
-                            parameters: vec![],
+                    },
+                    MatchArm {
+                        pattern: Pattern::Range(RangePattern {
+                            start: IntegerLiteral {
+                                value: "0".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 19,
+                                        line: 1,
+                                        column: 20,
+                                    },
+                                    end: Position {
+                                        index: 20,
+                                        line: 1,
+                                        column: 21,
+                                    },
+                                },
+                            },
+                            end: IntegerLiteral {
+                                value: "9".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 23,
+                                        line: 1,
+                                        column: 24,
+                                    },
+                                    end: Position {
+                                        index: 24,
+                                        line: 1,
+                                        column: 25,
+                                    },
+                                },
+                            },
+                            inclusive: true,
                             location: Location {
                                 start: Position {
-                                    index: 6,
+                                    index: 19,
                                     line: 1,
-                                    column: 7
+                                    column: 20,
                                 },
                                 end: Position {
-                                    index: 8,
+                                    index: 24,
                                     line: 1,
-                                    column: 9
-                                }
-                            }
-                        },
-                        return_type: Type {
-                            name: TypeKind::I64,
+                                    column: 25,
+                                },
+                            },
+                        },),
+                        expression: Expression::IntegerLiteral(IntegerLiteral {
+                            value: "1".to_string(),
                             location: Location {
                                 start: Position {
-                                    index: 12,
+                                    index: 28,
                                     line: 1,
-                                    column: 13
+                                    column: 29,
                                 },
                                 end: Position {
-                                    index: 15,
+                                    index: 29,
                                     line: 1,
-                                    column: 16
-                                }
-                            }
+                                    column: 30,
+                                },
+                            },
+                        },),
+                        location: Location {
+                            start: Position {
+                                index: 19,
+                                line: 1,
+                                column: 20,
+                            },
+                            end: Position {
+                                index: 29,
+                                line: 1,
+                                column: 30,
+                            },
                         },
-                        body: Block {
-                            statements: Statements {
-                                statements: vec![Statement::Expression(
-                                    Expression::IntegerLiteral(IntegerLiteral {
-                                        value: "0".to_string(),
-                                        location: Location {
-                                            start: Position {
-                                                index: 18,
-                                                line: 1,
-                                                column: 19
-                                            },
-                                            end: Position {
-                                                index: 19,
-                                                line: 1,
-                                                column: 20
-                                            }
-                                        }
-                                    })
-                                )],
+                    },
+                    MatchArm {
+                        pattern: Pattern::Range(RangePattern {
+                            start: IntegerLiteral {
+                                value: "10".to_string(),
                                 location: Location {
                                     start: Position {
-                                        index: 16,
+                                        index: 31,
                                         line: 1,
-                                        column: 17
+                                        column: 32,
                                     },
                                     end: Position {
-                                        index: 21,
+                                        index: 33,
                                         line: 1,
-                                        column: 22
-                                    }
-                                }
+                                        column: 34,
+                                    },
+                                },
+                            },
+                            end: IntegerLiteral {
+                                value: "100".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 35,
+                                        line: 1,
+                                        column: 36,
+                                    },
+                                    end: Position {
+                                        index: 38,
+                                        line: 1,
+                                        column: 39,
+                                    },
+                                },
                             },
+                            inclusive: false,
                             location: Location {
                                 start: Position {
-                                    index: 16,
+                                    index: 31,
                                     line: 1,
-                                    column: 17
+                                    column: 32,
                                 },
                                 end: Position {
-                                    index: 21,
+                                    index: 38,
                                     line: 1,
-                                    column: 22
-                                }
-                            }
-                        },
+                                    column: 39,
+                                },
+                            },
+                        },),
+                        expression: Expression::IntegerLiteral(IntegerLiteral {
+                            value: "2".to_string(),
+                            location: Location {
+                                start: Position {
+                                    index: 42,
+                                    line: 1,
+                                    column: 43,
+                                },
+                                end: Position {
+                                    index: 43,
+                                    line: 1,
+                                    column: 44,
+                                },
+                            },
+                        },),
                         location: Location {
                             start: Position {
-                                index: 3,
+                                index: 31,
                                 line: 1,
-                                column: 4
+                                column: 32,
                             },
                             end: Position {
-                                index: 21,
+                                index: 43,
                                 line: 1,
-                                column: 22
-                            }
-                        }
+                                column: 44,
+                            },
+                        },
                     },
-                    FunctionDefinition {
-                        name: Identifier {
-                            name: "bar".to_string(),
+                    MatchArm {
+                        pattern: Pattern::Binding(Identifier {
+                            name: "n".to_string(),
                             location: Location {
                                 start: Position {
-                                    index: 25,
-                                    line: 2,
-                                    column: 4
+                                    index: 45,
+                                    line: 1,
+                                    column: 46,
                                 },
                                 end: Position {
-                                    index: 28,
-                                    line: 2,
-                                    column: 7
-                                }
-                            }
-                        },
-                        parameters: Parameters {
-                            parameters: vec![],
+                                    index: 46,
+                                    line: 1,
+                                    column: 47,
+                                },
+                            },
+                        },),
+                        expression: Expression::Identifier(Identifier {
+                            name: "n".to_string(),
                             location: Location {
                                 start: Position {
-                                    index: 28,
-                                    line: 2,
-                                    column: 7
+                                    index: 50,
+                                    line: 1,
+                                    column: 51,
                                 },
                                 end: Position {
-                                    index: 30,
-                                    line: 2,
-                                    column: 9
-                                }
-                            }
+                                    index: 51,
+                                    line: 1,
+                                    column: 52,
+                                },
+                            },
+                        },),
+                        location: Location {
+                            start: Position {
+                                index: 45,
+                                line: 1,
+                                column: 46,
+                            },
+                            end: Position {
+                                index: 51,
+                                line: 1,
+                                column: 52,
+                            },
                         },
-                        return_type: Type {
-                            name: TypeKind::I32,
+                    },
+                ],
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 53,
+                        line: 1,
+                        column: 54,
+                    },
+                },
+            },),)
+        );
+    }
+
+    #[test]
+    fn expression_returns_enum_variant() {
+        let source = "E::B(1, p)";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).expression();
+        assert_eq!(
+            ast,
+            Some(Expression::EnumVariant(EnumVariant {
+                enum_name: Identifier {
+                    name: "E".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1,
+                        },
+                        end: Position {
+                            index: 1,
+                            line: 1,
+                            column: 2,
+                        },
+                    },
+                },
+                variant: Identifier {
+                    name: "B".to_string(),
+                    location: Location {
+                        start: Position {
+                            index: 3,
+                            line: 1,
+                            column: 4,
+                        },
+                        end: Position {
+                            index: 4,
+                            line: 1,
+                            column: 5,
+                        },
+                    },
+                },
+                arguments: vec![
+                    Expression::IntegerLiteral(IntegerLiteral {
+                        value: "1".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6,
+                            },
+                            end: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7,
+                            },
+                        },
+                    },),
+                    Expression::Identifier(Identifier {
+                        name: "p".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 8,
+                                line: 1,
+                                column: 9,
+                            },
+                            end: Position {
+                                index: 9,
+                                line: 1,
+                                column: 10,
+                            },
+                        },
+                    },),
+                ],
+                location: Location {
+                    start: Position {
+                        index: 0,
+                        line: 1,
+                        column: 1,
+                    },
+                    end: Position {
+                        index: 10,
+                        line: 1,
+                        column: 11,
+                    },
+                },
+            },),)
+        );
+    }
+
+    #[test]
+    fn parse_returns_enum_definition() {
+        let source = "enum E { A, B(i32, P), }";
+        let tokens = Tokenizer::new(source.to_string()).tokenize();
+        let ast = Parser::new(tokens).parse();
+        assert_eq!(
+            ast,
+            Program {
+                functions: Vec::new(),
+                structs: Vec::new(),
+                enums: vec![EnumDefinition {
+                    name: Identifier {
+                        name: "E".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 5,
+                                line: 1,
+                                column: 6,
+                            },
+                            end: Position {
+                                index: 6,
+                                line: 1,
+                                column: 7,
+                            },
+                        },
+                    },
+                    variants: vec![
+                        Variant {
+                            name: Identifier {
+                                name: "A".to_string(),
+                                location: Location {
+                                    start: Position {
+                                        index: 9,
+                                        line: 1,
+                                        column: 10,
+                                    },
+                                    end: Position {
+                                        index: 10,
+                                        line: 1,
+                                        column: 11,
+                                    },
+                                },
+                            },
+                            fields: Vec::new(),
                             location: Location {
                                 start: Position {
-                                    index: 34,
-                                    line: 2,
-                                    column: 13
+                                    index: 9,
+                                    line: 1,
+                                    column: 10,
                                 },
                                 end: Position {
-                                    index: 37,
-                                    line: 2,
-                                    column: 16
-                                }
-                            }
+                                    index: 10,
+                                    line: 1,
+                                    column: 11,
+                                },
+                            },
                         },
-                        body: Block {
-                            statements: Statements {
-                                statements: vec![Statement::Expression(
-                                    Expression::IntegerLiteral(IntegerLiteral {
-                                        value: "1".to_string(),
-                                        location: Location {
-                                            start: Position {
-                                                index: 40,
-                                                line: 2,
-                                                column: 19
-                                            },
-                                            end: Position {
-                                                index: 41,
-                                                line: 2,
-                                                column: 20
-                                            }
-                                        }
-                                    })
-                                )],
+                        Variant {
+                            name: Identifier {
+                                name: "B".to_string(),
                                 location: Location {
                                     start: Position {
-                                        index: 38,
-                                        line: 2,
-                                        column: 17
+                                        index: 12,
+                                        line: 1,
+                                        column: 13,
                                     },
                                     end: Position {
-                                        index: 43,
-                                        line: 2,
-                                        column: 22
-                                    }
-                                }
+                                        index: 13,
+                                        line: 1,
+                                        column: 14,
+                                    },
+                                },
                             },
+                            fields: vec![
+                                Type {
+                                    name: TypeKind::I32,
+                                    location: Location {
+                                        start: Position {
+                                            index: 14,
+                                            line: 1,
+                                            column: 15,
+                                        },
+                                        end: Position {
+                                            index: 17,
+                                            line: 1,
+                                            column: 18,
+                                        },
+                                    },
+                                },
+                                Type {
+                                    name: TypeKind::Named("P".to_string(),),
+                                    location: Location {
+                                        start: Position {
+                                            index: 19,
+                                            line: 1,
+                                            column: 20,
+                                        },
+                                        end: Position {
+                                            index: 20,
+                                            line: 1,
+                                            column: 21,
+                                        },
+                                    },
+                                },
+                            ],
                             location: Location {
                                 start: Position {
-                                    index: 38,
-                                    line: 2,
-                                    column: 17
+                                    index: 12,
+                                    line: 1,
+                                    column: 13,
                                 },
                                 end: Position {
-                                    index: 43,
-                                    line: 2,
-                                    column: 22
-                                }
-                            }
-                        },
-                        location: Location {
-                            start: Position {
-                                index: 25,
-                                line: 2,
-                                column: 4
+                                    index: 21,
+                                    line: 1,
+                                    column: 22,
+                                },
                             },
-                            end: Position {
-                                index: 43,
-                                line: 2,
-                                column: 22
-                            }
-                        }
-                    }
-                ]
+                        },
+                    ],
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1,
+                        },
+                        end: Position {
+                            index: 24,
+                            line: 1,
+                            column: 25,
+                        },
+                    },
+                },],
+                constants: Vec::new(),
+                statics: Vec::new(),
             }
-        )
+        );
     }
 
     #[test]
-    fn parse_returns_function_when_comments() {
-        let source = indoc! {"
-            /// Main function
-            fn main(/* empty */) -> i32 /* 0 or 1 */ {
-                /* return */ 0 // return 0
-            }
-        "};
+    fn parse_returns_constant_and_static_definitions() {
+        let source = "const N: i32 = 1; static var c: i64 = N;";
         let tokens = Tokenizer::new(source.to_string()).tokenize();
         let ast = Parser::new(tokens).parse();
         assert_eq!(
             ast,
             Program {
-                functions: vec![FunctionDefinition {
+                functions: Vec::new(),
+                structs: Vec::new(),
+                enums: Vec::new(),
+                constants: vec![ConstantDefinition {
                     name: Identifier {
-                        name: "main".to_string(),
+                        name: "N".to_string(),
                         location: Location {
                             start: Position {
-                                index: 21,
-                                line: 2,
-                                column: 4
+                                index: 6,
+                                line: 1,
+                                column: 7
                             },
                             end: Position {
-                                index: 25,
-                                line: 2,
+                                index: 7,
+                                line: 1,
                                 column: 8
                             }
                         }
                     },
-                    parameters: Parameters {
-                        parameters: vec![],
+                    constant_type: Type {
+                        name: TypeKind::I32,
                         location: Location {
                             start: Position {
-                                index: 25,
-                                line: 2,
-                                column: 8
+                                index: 9,
+                                line: 1,
+                                column: 10
                             },
                             end: Position {
-                                index: 38,
-                                line: 2,
-                                column: 21
+                                index: 12,
+                                line: 1,
+                                column: 13
                             }
                         }
                     },
-                    return_type: Type {
-                        name: TypeKind::I32,
+                    value: Expression::IntegerLiteral(IntegerLiteral {
+                        value: "1".to_string(),
                         location: Location {
                             start: Position {
-                                index: 42,
-                                line: 2,
-                                column: 25
+                                index: 15,
+                                line: 1,
+                                column: 16
                             },
                             end: Position {
-                                index: 45,
-                                line: 2,
-                                column: 28
+                                index: 16,
+                                line: 1,
+                                column: 17
                             }
                         }
+                    }),
+                    location: Location {
+                        start: Position {
+                            index: 0,
+                            line: 1,
+                            column: 1
+                        },
+                        end: Position {
+                            index: 17,
+                            line: 1,
+                            column: 18
+                        }
                     },
-                    body: Block {
-                        statements: Statements {
-                            statements: vec![Statement::Expression(Expression::IntegerLiteral(
-                                IntegerLiteral {
-                                    value: "0".to_string(),
-                                    location: Location {
-                                        start: Position {
-                                            index: 78,
-                                            line: 3,
-                                            column: 18
-                                        },
-                                        end: Position {
-                                            index: 79,
-                                            line: 3,
-                                            column: 19
-                                        }
-                                    }
-                                }
-                            ))],
-                            location: Location {
-                                start: Position {
-                                    index: 59,
-                                    line: 2,
-                                    column: 42
-                                },
-                                end: Position {
-                                    index: 93,
-                                    line: 4,
-                                    column: 2
-                                }
+                }],
+                statics: vec![StaticDefinition {
+                    name: Identifier {
+                        name: "c".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 29,
+                                line: 1,
+                                column: 30
+                            },
+                            end: Position {
+                                index: 30,
+                                line: 1,
+                                column: 31
                             }
-                        },
+                        }
+                    },
+                    static_type: Type {
+                        name: TypeKind::I64,
                         location: Location {
                             start: Position {
-                                index: 59,
-                                line: 2,
-                                column: 42
+                                index: 32,
+                                line: 1,
+                                column: 33
                             },
                             end: Position {
-                                index: 93,
-                                line: 4,
-                                column: 2
+                                index: 35,
+                                line: 1,
+                                column: 36
                             }
                         }
                     },
+                    value: Expression::Identifier(Identifier {
+                        name: "N".to_string(),
+                        location: Location {
+                            start: Position {
+                                index: 38,
+                                line: 1,
+                                column: 39
+                            },
+                            end: Position {
+                                index: 39,
+                                line: 1,
+                                column: 40
+                            }
+                        }
+                    }),
                     location: Location {
                         start: Position {
-                            index: 21,
-                            line: 2,
-                            column: 4
+                            index: 18,
+                            line: 1,
+                            column: 19
                         },
                         end: Position {
-                            index: 93,
-                            line: 4,
-                            column: 2
+                            index: 40,
+                            line: 1,
+                            column: 41
                         }
-                    }
-                }]
+                    },
+                }],
             }
         );
     }
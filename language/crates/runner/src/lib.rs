@@ -0,0 +1,464 @@
+//! Runs compiled Shuiro components with wasmtime.
+//!
+//! Traps are reported as a stack trace of the Shuiro functions that were running, with their
+//! source positions read from the source map of the component.
+//!
+//! wasmtime does not build for WebAssembly, so the crate is empty on WebAssembly targets.
+#![cfg(not(target_family = "wasm"))]
+
+use std::fmt;
+
+use code_generator::SourceMap;
+use wasmtime::{
+    component::{Component, Linker},
+    Config, Engine, Store, Trap, WasmBacktrace,
+};
+use wasmtime_wasi::{bindings::sync::Command, ResourceTable, WasiCtx, WasiCtxBuilder, WasiView};
+
+pub use wasmtime_wasi::{stdin, stdout, StdinStream, StdoutStream};
+
+/// Name of the module of the component containing the functions of the program.
+const MAIN_MODULE: &str = "Main";
+
+/// Name of the module of the component containing the standard library.
+const STD_MODULE: &str = "Std";
+
+/// Largest number of runs of identical frames shown in a stack trace, which keeps the traces of
+/// unbounded recursion short.
+const MAX_FRAME_RUNS: usize = 16;
+
+/// Functions of the standard library that trap on purpose, with the message of their trap.
+const STD_TRAPS: &[(&str, &str)] = &[
+    ("out_of_memory", "out of memory"),
+    ("index_out_of_bounds", "index out of bounds"),
+    ("invalid_array_length", "invalid array length"),
+    ("end_of_input", "unexpected end of input"),
+    ("invalid_integer", "invalid integer in input"),
+    ("integer_out_of_range", "integer in input out of range"),
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0}")]
+    Trap(RuntimeError),
+
+    #[error("the program exited with an error")]
+    Failure,
+
+    #[error(transparent)]
+    Wasmtime(#[from] anyhow::Error),
+}
+
+/// A trap with the Shuiro functions that were running, innermost first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeError {
+    pub message: String,
+    pub frames: Vec<Frame>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub function: String,
+    pub file_name: String,
+
+    /// Line and column in the source, if the source map covers the instruction.
+    pub position: Option<(u32, u32)>,
+}
+
+impl fmt::Display for RuntimeError {
+    /// ```text
+    /// division by zero at main.shr:7:13 in `average`
+    ///     called at main.shr:12:15 in `main`
+    /// ```
+    ///
+    /// Identical frames following each other, as in a recursion, are shown once followed by the
+    /// number of repetitions, and only the first [`MAX_FRAME_RUNS`] runs of frames are shown:
+    /// ```text
+    /// stack overflow at main.shr:1:4 in `count`
+    ///     called at main.shr:2:5 in `count`
+    ///     ... (41562 more)
+    ///     called at main.shr:6:15 in `main`
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        let mut runs: Vec<(&Frame, usize)> = Vec::new();
+        for frame in &self.frames {
+            match runs.last_mut() {
+                Some((last, count)) if *last == frame => *count += 1,
+                _ => runs.push((frame, 1)),
+            }
+        }
+        for (index, (frame, count)) in runs.iter().take(MAX_FRAME_RUNS).enumerate() {
+            if index > 0 {
+                write!(f, "\n    called")?;
+            }
+            write!(f, " at {}", frame)?;
+            if *count > 1 {
+                write!(f, "\n    ... ({} more)", count - 1)?;
+            }
+        }
+        let hidden: usize = runs
+            .iter()
+            .skip(MAX_FRAME_RUNS)
+            .map(|(_, count)| count)
+            .sum();
+        if hidden > 0 {
+            write!(f, "\n    ... ({} more)", hidden)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(f, "{}:{}:{}", self.file_name, line, column)?,
+            None => write!(f, "{}", self.file_name)?,
+        }
+        write!(f, " in `{}`", self.function)
+    }
+}
+
+struct State {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl WasiView for State {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+/// Run the `wasi:cli/run` export of a compiled component, reading its input from `stdin` and
+/// writing its output to `stdout`.
+///
+/// `file_name` is the name of the source file shown in stack traces.
+pub fn run(
+    wasm: &[u8],
+    file_name: &str,
+    stdin: impl StdinStream + 'static,
+    stdout: impl StdoutStream + 'static,
+) -> Result<(), Error> {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    config.wasm_tail_call(true);
+    let engine = Engine::new(&config)?;
+
+    let mut linker = Linker::<State>::new(&engine);
+    wasmtime_wasi::add_to_linker_sync(&mut linker)?;
+
+    let mut builder = WasiCtxBuilder::new();
+    builder.stdin(stdin);
+    builder.stdout(stdout);
+    builder.inherit_stderr();
+    let mut store = Store::new(
+        &engine,
+        State {
+            ctx: builder.build(),
+            table: ResourceTable::new(),
+        },
+    );
+
+    let component = Component::from_binary(&engine, wasm)?;
+    let command = Command::instantiate(&mut store, &component, &linker)?;
+    match command.wasi_cli_run().call_run(&mut store) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(())) => Err(Error::Failure),
+        Err(error) => Err(match error.downcast_ref::<Trap>() {
+            Some(trap) => Error::Trap(RuntimeError {
+                message: std_trap_message(&error).unwrap_or_else(|| trap_message(*trap)),
+                frames: frames(&error, wasm, file_name)?,
+            }),
+            None => Error::Wasmtime(error),
+        }),
+    }
+}
+
+fn trap_message(trap: Trap) -> String {
+    match trap {
+        Trap::IntegerDivisionByZero => "division by zero".to_string(),
+        Trap::IntegerOverflow => "integer overflow".to_string(),
+        Trap::StackOverflow => "stack overflow".to_string(),
+        Trap::UnreachableCodeReached => "unreachable code reached".to_string(),
+        Trap::MemoryOutOfBounds => "out of bounds memory access".to_string(),
+        trap => trap.to_string(),
+    }
+}
+
+/// Message of a trap raised on purpose by the standard library, which is the innermost frame of
+/// the backtrace of the trap.
+fn std_trap_message(error: &anyhow::Error) -> Option<String> {
+    let frame = error.downcast_ref::<WasmBacktrace>()?.frames().first()?;
+    if frame.module().name() != Some(STD_MODULE) {
+        return None;
+    }
+    STD_TRAPS
+        .iter()
+        .find(|(function, _)| frame.func_name() == Some(function))
+        .map(|(_, message)| message.to_string())
+}
+
+/// Frames of the functions of the program in the backtrace of the trap.
+///
+/// Frames without an offset are at the definition of their function, which the source map maps
+/// offset 0 to.
+fn frames(error: &anyhow::Error, wasm: &[u8], file_name: &str) -> Result<Vec<Frame>, Error> {
+    let Some(backtrace) = error.downcast_ref::<WasmBacktrace>() else {
+        return Ok(Vec::new());
+    };
+    let source_map = SourceMap::find(wasm)?.unwrap_or_default();
+    Ok(backtrace
+        .frames()
+        .iter()
+        .filter(|frame| frame.module().name() == Some(MAIN_MODULE))
+        .map(|frame| {
+            let offset = frame.func_offset().unwrap_or(0) as u32;
+            let entry = source_map.lookup(frame.func_index(), offset);
+            Frame {
+                function: frame
+                    .func_name()
+                    .map_or_else(|| format!("#{}", frame.func_index()), str::to_string),
+                file_name: file_name.to_string(),
+                position: entry.map(|entry| (entry.line, entry.column)),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use wasmtime_wasi::pipe::{MemoryInputPipe, MemoryOutputPipe};
+
+    fn compile(source: &str) -> Vec<u8> {
+        let tokens = tokenizer::tokenize(source.to_string());
+        let ast = parser::parse(tokens);
+        let ir = code_generator::lower(&ast).unwrap();
+        code_generator::CodeGenerator::new(ir).generate().unwrap()
+    }
+
+    #[test]
+    fn run_reads_stdin_and_writes_stdout() {
+        let wasm = compile("fn main() -> i32 { print_int(read_int() * 2); 0 }");
+        let stdin = MemoryInputPipe::new("21\n");
+        let stdout = MemoryOutputPipe::new(1024);
+        run(&wasm, "main.shr", stdin, stdout.clone()).unwrap();
+        assert_eq!(&stdout.contents()[..], b"42");
+    }
+
+    #[test]
+    fn run_returns_stack_trace_of_trap() {
+        let source = indoc! {"
+            fn average(sum: i32, count: i32) -> i32 {
+                print_int(sum);
+                sum / count
+            }
+
+            fn main() -> i32 {
+                print_int(average(10, 0));
+                0
+            }
+        "};
+        let wasm = compile(source);
+        let Err(Error::Trap(error)) = run(
+            &wasm,
+            "main.shr",
+            MemoryInputPipe::new(""),
+            MemoryOutputPipe::new(1024),
+        ) else {
+            panic!("expected a trap");
+        };
+        assert_eq!(
+            error.to_string(),
+            "division by zero at main.shr:3:5 in `average`\n    called at main.shr:7:15 in `main`"
+        );
+    }
+
+    #[test]
+    fn run_collapses_frames_of_unbounded_recursion() {
+        let source = indoc! {"
+            fn count(n: i32) -> i32 {
+                count(n + 1) + 1
+            }
+
+            fn main() -> i32 {
+                print_int(count(0));
+                0
+            }
+        "};
+        let wasm = compile(source);
+        let Err(Error::Trap(error)) = run(
+            &wasm,
+            "main.shr",
+            MemoryInputPipe::new(""),
+            MemoryOutputPipe::new(1024),
+        ) else {
+            panic!("expected a trap");
+        };
+        let message = error.to_string();
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines.len(), 4, "{}", message);
+        assert_eq!(lines[0], "stack overflow at main.shr:1:4 in `count`");
+        assert_eq!(lines[1], "    called at main.shr:2:5 in `count`");
+        assert!(
+            lines[2].starts_with("    ... (") && lines[2].ends_with(" more)"),
+            "{}",
+            message
+        );
+        assert_eq!(lines[3], "    called at main.shr:6:15 in `main`");
+    }
+
+    #[test]
+    fn run_tail_calls_functions_returning_tuples() {
+        // the recursion only ends by indexing out of bounds, which would overflow the stack first
+        // without tail calls
+        let source = indoc! {"
+            fn ping(n: i32, steps: [i32]) -> (i32, i32) {
+                steps[n];
+                pong(n + 1, steps)
+            }
+
+            fn pong(n: i32, steps: [i32]) -> (i32, i32) {
+                ping(n + 1, steps)
+            }
+
+            fn main() -> i32 {
+                let (a, b) = ping(0, [0; 1000000]);
+                print_int(a + b);
+                0
+            }
+        "};
+        let wasm = compile(source);
+        let Err(Error::Trap(error)) = run(
+            &wasm,
+            "main.shr",
+            MemoryInputPipe::new(""),
+            MemoryOutputPipe::new(1024),
+        ) else {
+            panic!("expected a trap");
+        };
+        assert_eq!(
+            error.to_string(),
+            "index out of bounds at main.shr:2:5 in `ping`\n    called at main.shr:11:18 in `main`"
+        );
+    }
+
+    #[test]
+    fn run_reports_index_out_of_bounds() {
+        let source = indoc! {"
+            fn main() -> i32 {
+                let a: [i32; 3] = [1, 2, 3];
+                print_int(a[read_int()]);
+                0
+            }
+        "};
+        let wasm = compile(source);
+        let Err(Error::Trap(error)) = run(
+            &wasm,
+            "main.shr",
+            MemoryInputPipe::new("3"),
+            MemoryOutputPipe::new(1024),
+        ) else {
+            panic!("expected a trap");
+        };
+        assert_eq!(
+            error.to_string(),
+            "index out of bounds at main.shr:3:15 in `main`"
+        );
+    }
+
+    #[test]
+    fn run_reports_out_of_memory() {
+        let wasm = compile("fn main() -> i32 { alloc(-1); 0 }");
+        let Err(Error::Trap(error)) = run(
+            &wasm,
+            "main.shr",
+            MemoryInputPipe::new(""),
+            MemoryOutputPipe::new(1024),
+        ) else {
+            panic!("expected a trap");
+        };
+        assert_eq!(
+            error.to_string(),
+            "out of memory at main.shr:1:20 in `main`"
+        );
+    }
+
+    #[test]
+    fn run_reports_invalid_input() {
+        let cases = [
+            (
+                "read_int()",
+                "",
+                "unexpected end of input at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_int()",
+                " \n",
+                "unexpected end of input at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_i64() as i32",
+                "-",
+                "invalid integer in input at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_int()",
+                "- 1",
+                "invalid integer in input at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_int()",
+                "abc",
+                "invalid integer in input at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_int()",
+                "2147483648",
+                "integer in input out of range at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_int()",
+                "-2147483649",
+                "integer in input out of range at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_i64() as i32",
+                "9223372036854775808",
+                "integer in input out of range at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_i64() as i32",
+                "-9223372036854775809",
+                "integer in input out of range at main.shr:1:30 in `main`",
+            ),
+            (
+                "read_i64() as i32",
+                "99999999999999999999",
+                "integer in input out of range at main.shr:1:30 in `main`",
+            ),
+        ];
+        for (expression, input, message) in cases {
+            let wasm = compile(&format!(
+                "fn main() -> i32 {{ print_int({}); 0 }}",
+                expression
+            ));
+            let Err(Error::Trap(error)) = run(
+                &wasm,
+                "main.shr",
+                MemoryInputPipe::new(input),
+                MemoryOutputPipe::new(1024),
+            ) else {
+                panic!("expected a trap for {:?}", input);
+            };
+            assert_eq!(error.to_string(), message, "{:?}", input);
+        }
+    }
+}
@@ -50,10 +50,37 @@ impl Tokenizer {
             .or_else(|| self.tokenize_comment())
             .or_else(|| self.tokenize_delimiter())
             .or_else(|| self.tokenize_operator())
+            .or_else(|| self.tokenize_float())
             .or_else(|| self.tokenize_integer())
             .or_else(|| self.tokenize_identifier())
     }
 
+    /// Tokenize a float such as `1.5`, with digits on both sides of the dot so that ranges such
+    /// as `1..2` are left to the other tokenizers.
+    fn tokenize_float(&mut self) -> Option<Token> {
+        let digits = |tokenizer: &Self, start: usize| {
+            let mut length = 0;
+            while let Some(c) = tokenizer.source.peek_char(start + length) {
+                if c.is_ascii_digit() {
+                    length += 1;
+                } else {
+                    break;
+                }
+            }
+            length
+        };
+
+        let integer_length = digits(self, 0);
+        if integer_length == 0 || self.source.peek_char(integer_length) != Some(&'.') {
+            return None;
+        }
+        let fraction_length = digits(self, integer_length + 1);
+        if fraction_length == 0 {
+            return None;
+        }
+        Some(self.create_token(TokenKind::Float, integer_length + 1 + fraction_length))
+    }
+
     fn tokenize_integer(&mut self) -> Option<Token> {
         if let Some(c) = self.source.current_char() {
             if !c.is_ascii_digit() {
@@ -119,29 +146,35 @@ impl Tokenizer {
     fn tokenize_operator(&mut self) -> Option<Token> {
         match self.source.current_char()? {
             '=' => match self.source.peek_char(1) {
-                Some('=') => Some(self.create_token(TokenKind::Operator, 2)),
+                Some('=') | Some('>') => Some(self.create_token(TokenKind::Operator, 2)),
                 _ => Some(self.create_token(TokenKind::Operator, 1)),
             },
+            '.' => match (self.source.peek_char(1), self.source.peek_char(2)) {
+                (Some('.'), Some('=')) => Some(self.create_token(TokenKind::Operator, 3)),
+                (Some('.'), _) => Some(self.create_token(TokenKind::Operator, 2)),
+                _ => None,
+            },
             '!' => match self.source.peek_char(1) {
                 Some('=') => Some(self.create_token(TokenKind::Operator, 2)),
                 _ => Some(self.create_token(TokenKind::Operator, 1)),
             },
-            '+' | '*' | '/' => Some(self.create_token(TokenKind::Operator, 1)),
+            '+' | '*' | '/' | '%' => match self.source.peek_char(1) {
+                Some('=') => Some(self.create_token(TokenKind::Operator, 2)),
+                _ => Some(self.create_token(TokenKind::Operator, 1)),
+            },
+            '^' => Some(self.create_token(TokenKind::Operator, 1)),
             '-' => match self.source.peek_char(1) {
-                Some('>') => Some(self.create_token(TokenKind::Operator, 2)),
+                Some('>') | Some('=') => Some(self.create_token(TokenKind::Operator, 2)),
                 _ => Some(self.create_token(TokenKind::Operator, 1)),
             },
-            '>' | '<' => match self.source.peek_char(1) {
+            c @ ('>' | '<') => match self.source.peek_char(1) {
                 Some('=') => Some(self.create_token(TokenKind::Operator, 2)),
+                Some(next) if next == c => Some(self.create_token(TokenKind::Operator, 2)),
                 _ => Some(self.create_token(TokenKind::Operator, 1)),
             },
-            '&' => match self.source.peek_char(1) {
-                Some('&') => Some(self.create_token(TokenKind::Operator, 2)),
-                _ => None,
-            },
-            '|' => match self.source.peek_char(1) {
-                Some('|') => Some(self.create_token(TokenKind::Operator, 2)),
-                _ => None,
+            c @ ('&' | '|') => match self.source.peek_char(1) {
+                Some(next) if next == c => Some(self.create_token(TokenKind::Operator, 2)),
+                _ => Some(self.create_token(TokenKind::Operator, 1)),
             },
             _ => None,
         }
@@ -176,7 +209,8 @@ impl Tokenizer {
         };
 
         match keyword.as_str() {
-            "fn" | "let" | "var" | "if" | "else" | "while" | "for" | "return" | "as" => {
+            "fn" | "let" | "var" | "if" | "else" | "while" | "for" | "return" | "as" | "struct"
+            | "enum" | "match" | "true" | "false" | "const" | "static" => {
                 Some(self.create_token(TokenKind::Keyword, length))
             }
             _ => None,
@@ -185,7 +219,16 @@ impl Tokenizer {
 
     fn tokenize_delimiter(&mut self) -> Option<Token> {
         match self.source.current_char()? {
-            '(' | ')' | '{' | '}' | '[' | ']' | ',' | ';' | ':' => {
+            ':' => match self.source.peek_char(1) {
+                Some(':') => Some(self.create_token(TokenKind::Delimiter, 2)),
+                _ => Some(self.create_token(TokenKind::Delimiter, 1)),
+            },
+            // `..` and `..=` are operators
+            '.' => match self.source.peek_char(1) {
+                Some('.') => None,
+                _ => Some(self.create_token(TokenKind::Delimiter, 1)),
+            },
+            '(' | ')' | '{' | '}' | '[' | ']' | ',' | ';' => {
                 Some(self.create_token(TokenKind::Delimiter, 1))
             }
             _ => None,
@@ -295,6 +338,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokenize_float_returns_none_for_integer() {
+        let mut tokenizer = Tokenizer::new("123".to_string());
+        assert_eq!(tokenizer.tokenize_float(), None);
+    }
+
+    #[test]
+    fn tokenize_float_returns_none_for_range() {
+        let mut tokenizer = Tokenizer::new("1..2".to_string());
+        assert_eq!(tokenizer.tokenize_float(), None);
+    }
+
+    #[test]
+    fn tokenize_float_returns_float() {
+        let mut tokenizer = Tokenizer::new("3.25abc".to_string());
+        assert_eq!(
+            tokenizer.tokenize_float(),
+            Some(Token {
+                kind: TokenKind::Float,
+                value: "3.25".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(4, 1, 5),
+            })
+        );
+    }
+
     #[test]
     fn tokenize_identifier_returns_none_for_empty_source() {
         let mut tokenizer = Tokenizer::new("".to_string());
@@ -381,6 +450,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokenize_operator_returns_fat_arrow_operator() {
+        let mut tokenizer = Tokenizer::new("=>".to_string());
+        assert_eq!(
+            tokenizer.tokenize_operator(),
+            Some(Token {
+                kind: TokenKind::Operator,
+                value: "=>".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(2, 1, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_operator_returns_range_operator() {
+        let mut tokenizer = Tokenizer::new("..".to_string());
+        assert_eq!(
+            tokenizer.tokenize_operator(),
+            Some(Token {
+                kind: TokenKind::Operator,
+                value: "..".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(2, 1, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_operator_returns_inclusive_range_operator() {
+        let mut tokenizer = Tokenizer::new("..=".to_string());
+        assert_eq!(
+            tokenizer.tokenize_operator(),
+            Some(Token {
+                kind: TokenKind::Operator,
+                value: "..=".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(3, 1, 4),
+            })
+        );
+    }
+
     #[test]
     fn tokenize_operator_returns_assign_operator() {
         let mut tokenizer = Tokenizer::new("=".to_string());
@@ -577,6 +688,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokenize_operator_returns_bitwise_and_operator() {
+        let mut tokenizer = Tokenizer::new("&x".to_string());
+        assert_eq!(
+            tokenizer.tokenize_operator(),
+            Some(Token {
+                kind: TokenKind::Operator,
+                value: "&".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(1, 1, 2),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_operator_returns_shift_left_operator() {
+        let mut tokenizer = Tokenizer::new("<<".to_string());
+        assert_eq!(
+            tokenizer.tokenize_operator(),
+            Some(Token {
+                kind: TokenKind::Operator,
+                value: "<<".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(2, 1, 3),
+            })
+        );
+    }
+
     #[test]
     fn tokenize_keyword_returns_none_for_empty_source() {
         let mut tokenizer = Tokenizer::new("".to_string());
@@ -737,6 +876,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokenize_keyword_returns_struct_keyword() {
+        let mut tokenizer = Tokenizer::new("struct".to_string());
+        assert_eq!(
+            tokenizer.tokenize_keyword(),
+            Some(Token {
+                kind: TokenKind::Keyword,
+                value: "struct".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(6, 1, 7),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_keyword_returns_enum_keyword() {
+        let mut tokenizer = Tokenizer::new("enum".to_string());
+        assert_eq!(
+            tokenizer.tokenize_keyword(),
+            Some(Token {
+                kind: TokenKind::Keyword,
+                value: "enum".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(4, 1, 5),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_keyword_returns_static_keyword() {
+        let mut tokenizer = Tokenizer::new("static".to_string());
+        assert_eq!(
+            tokenizer.tokenize_keyword(),
+            Some(Token {
+                kind: TokenKind::Keyword,
+                value: "static".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(6, 1, 7),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_keyword_returns_match_keyword() {
+        let mut tokenizer = Tokenizer::new("match".to_string());
+        assert_eq!(
+            tokenizer.tokenize_keyword(),
+            Some(Token {
+                kind: TokenKind::Keyword,
+                value: "match".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(5, 1, 6),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_keyword_returns_true_keyword() {
+        let mut tokenizer = Tokenizer::new("true".to_string());
+        assert_eq!(
+            tokenizer.tokenize_keyword(),
+            Some(Token {
+                kind: TokenKind::Keyword,
+                value: "true".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(4, 1, 5),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_keyword_returns_false_keyword() {
+        let mut tokenizer = Tokenizer::new("false".to_string());
+        assert_eq!(
+            tokenizer.tokenize_keyword(),
+            Some(Token {
+                kind: TokenKind::Keyword,
+                value: "false".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(5, 1, 6),
+            })
+        );
+    }
+
     #[test]
     fn tokenize_delimiter_returns_none_for_empty_source() {
         let mut tokenizer = Tokenizer::new("".to_string());
@@ -897,6 +1120,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tokenize_delimiter_returns_dot() {
+        let mut tokenizer = Tokenizer::new(".".to_string());
+        assert_eq!(
+            tokenizer.tokenize_delimiter(),
+            Some(Token {
+                kind: TokenKind::Delimiter,
+                value: ".".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(1, 1, 2),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_delimiter_returns_path_separator() {
+        let mut tokenizer = Tokenizer::new("::".to_string());
+        assert_eq!(
+            tokenizer.tokenize_delimiter(),
+            Some(Token {
+                kind: TokenKind::Delimiter,
+                value: "::".to_string(),
+                start_position: Position::new(0, 1, 1),
+                end_position: Position::new(2, 1, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn tokenize_delimiter_returns_none_for_range() {
+        let mut tokenizer = Tokenizer::new("..".to_string());
+        assert_eq!(tokenizer.tokenize_delimiter(), None);
+    }
+
     #[test]
     fn tokenize_comment_returns_none_for_empty_source() {
         let mut tokenizer = Tokenizer::new("".to_string());
@@ -1044,7 +1301,8 @@ mod tests {
     #[test]
     fn tokenize_returns_operator() {
         let operators = [
-            "==", "!=", "=", "!", "+", "-", "*", "/", "->", "<", "<=", ">", ">=", "&&", "||",
+            "==", "!=", "=", "!", "+", "-", "*", "/", "%", "->", "<", "<=", ">", ">=", "&&", "||",
+            "&", "|", "^", "<<", ">>", "+=", "-=", "*=", "/=", "%=",
         ];
         for operator in operators.iter() {
             let mut tokenizer = Tokenizer::new(operator.to_string());
@@ -7,6 +7,7 @@ use super::position::Position;
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TokenKind {
     Integer,
+    Float,
     Identifier,
     Operator,
     Keyword,
@@ -18,6 +19,7 @@ impl fmt::Display for TokenKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             TokenKind::Integer => write!(f, "integer"),
+            TokenKind::Float => write!(f, "float"),
             TokenKind::Identifier => write!(f, "identifier"),
             TokenKind::Operator => write!(f, "operator"),
             TokenKind::Keyword => write!(f, "keyword"),
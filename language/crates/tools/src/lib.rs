@@ -3,18 +3,21 @@ mod bindings;
 
 use anyhow::Context;
 use bindings::Guest;
-use code_generator::CodeGenerator;
+use code_generator::{lower_with_types, CodeGenerator, CodegenError};
+use optimizer::{optimize, OptimizationLevel};
 use parser::parse;
 use tokenizer::tokenize;
 
-#[derive(Debug, thiserror::Error)]
-enum Error {
-    #[error(transparent)]
-    CodeGenerator(#[from] code_generator::Error),
-}
-
 impl std::convert::From<anyhow::Error> for bindings::Error {
     fn from(error: anyhow::Error) -> Self {
+        bindings::Error {
+            error: format!("{:#}", error),
+        }
+    }
+}
+
+impl std::convert::From<CodegenError> for bindings::Error {
+    fn from(error: CodegenError) -> Self {
         bindings::Error {
             error: error.to_string(),
         }
@@ -24,18 +27,32 @@ impl std::convert::From<anyhow::Error> for bindings::Error {
 struct Component;
 
 impl Guest for Component {
-    fn compile(source: String) -> Result<bindings::Output, bindings::Error> {
+    fn compile(
+        source: String,
+        optimization_level: u8,
+    ) -> Result<bindings::Output, bindings::Error> {
+        let optimization_level =
+            OptimizationLevel::try_from(optimization_level).map_err(anyhow::Error::msg)?;
         let tokens = tokenize(source);
         let ast = parse(tokens.clone());
-        let mut generator =
-            CodeGenerator::new(ast.clone()).with_context(|| "Failed to create code generator")?;
-        let mut wat = generator
-            .generate()
-            .with_context(|| "Failed to generate WAT")?;
-        let wasm = wat.encode().with_context(|| "Failed to encode WAT")?;
+        let (mut ir, types) = lower_with_types(&ast)?;
+        let types = types
+            .into_iter()
+            .map(|inferred| {
+                serde_json::json!({
+                    "name": inferred.name.name,
+                    "type": inferred.ty.to_string(),
+                    "location": inferred.name.location,
+                })
+            })
+            .collect::<Vec<_>>();
+        optimize(&mut ir, optimization_level);
+        let wasm = CodeGenerator::new(ir).generate()?;
         Ok(bindings::Output {
             tokens: serde_json::to_string(&tokens).with_context(|| "Failed to serialize tokens")?,
             ast: serde_json::to_string(&ast).with_context(|| "Failed to serialize AST")?,
+            types: serde_json::to_string(&types)
+                .with_context(|| "Failed to serialize inferred types")?,
             wasm,
         })
     }
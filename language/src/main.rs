@@ -1,5 +1,9 @@
+use std::{io::Write, process::ExitCode};
+
+use anyhow::{Context, Result};
 use clap::Parser;
-use code_generator::CodeGenerator;
+use code_generator::{check, lower, CodeGenerator};
+use optimizer::{optimize, OptimizationLevel};
 use parser::parse;
 use tokenizer::tokenize;
 
@@ -16,13 +20,24 @@ struct Args {
 
     #[arg(short, long)]
     output: Option<String>,
+
+    /// Output of the compile mode: component, main-module, wat or ir
+    #[arg(long, default_value_t = Emit::Component)]
+    emit: Emit,
+
+    /// Optimization level: 0, 1 or 2
+    #[arg(short = 'O', default_value_t = OptimizationLevel::O0)]
+    optimization_level: OptimizationLevel,
 }
 
 #[derive(Clone)]
 enum Mode {
     Tokenize,
     Parse,
+    Check,
+    Ir,
     Compile,
+    Run,
 }
 
 impl std::str::FromStr for Mode {
@@ -32,7 +47,10 @@ impl std::str::FromStr for Mode {
         match s {
             "tokenize" => Ok(Self::Tokenize),
             "parse" => Ok(Self::Parse),
+            "check" => Ok(Self::Check),
+            "ir" => Ok(Self::Ir),
             "compile" => Ok(Self::Compile),
+            "run" => Ok(Self::Run),
             _ => Err("Invalid mode".to_string()),
         }
     }
@@ -43,48 +61,158 @@ impl std::fmt::Display for Mode {
         match self {
             Self::Parse => write!(f, "parse"),
             Self::Tokenize => write!(f, "tokenize"),
+            Self::Check => write!(f, "check"),
+            Self::Ir => write!(f, "ir"),
             Self::Compile => write!(f, "compile"),
+            Self::Run => write!(f, "run"),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Emit {
+    /// WebAssembly component running on WASI
+    Component,
+    /// Core WebAssembly module of the program alone, importing its memory and the standard
+    /// library from the host
+    MainModule,
+    /// WebAssembly text of the component
+    Wat,
+    /// Textual dump of the IR
+    Ir,
+}
+
+impl std::str::FromStr for Emit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "component" => Ok(Self::Component),
+            "main-module" => Ok(Self::MainModule),
+            "wat" => Ok(Self::Wat),
+            "ir" => Ok(Self::Ir),
+            _ => Err("Invalid emit target".to_string()),
         }
     }
 }
 
-fn main() {
-    let args = Args::parse();
+impl std::fmt::Display for Emit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Component => write!(f, "component"),
+            Self::MainModule => write!(f, "main-module"),
+            Self::Wat => write!(f, "wat"),
+            Self::Ir => write!(f, "ir"),
+        }
+    }
+}
 
-    let source = if args.command {
-        args.source
+/// Write the output to the file, or to stdout if no file is given.
+fn write_output(output: Option<String>, contents: &[u8]) -> Result<()> {
+    if let Some(output) = output {
+        std::fs::write(&output, contents)
+            .with_context(|| format!("Failed to write output to `{}`", output))?;
     } else {
-        std::fs::read_to_string(&args.source).expect("Failed to read source file")
+        std::io::stdout()
+            .write_all(contents)
+            .context("Failed to write output")?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {:#}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let (source, file_name) = if args.command {
+        (args.source, "<command>".to_string())
+    } else {
+        let source = std::fs::read_to_string(&args.source)
+            .with_context(|| format!("Failed to read source file `{}`", args.source))?;
+        (source, args.source)
+    };
+    // `-m ir` is an alias of `--emit ir`
+    let (mode, emit) = match args.mode {
+        Mode::Ir => (Mode::Compile, Emit::Ir),
+        mode => (mode, args.emit),
     };
-    match args.mode {
+    match mode {
         Mode::Tokenize => {
             let tokens = tokenize(source);
-            if let Some(output) = args.output {
-                std::fs::write(output, format!("{:#?}", tokens)).expect("Failed to write output");
-            } else {
-                println!("{:#?}", tokens);
-            }
+            write_output(args.output, format!("{:#?}\n", tokens).as_bytes())?;
         }
         Mode::Parse => {
             let tokens = tokenize(source);
             let ast = parse(tokens);
-            if let Some(output) = args.output {
-                std::fs::write(output, format!("{:#?}", ast)).expect("Failed to write output");
-            } else {
-                println!("{:#?}", ast);
+            write_output(args.output, format!("{:#?}\n", ast).as_bytes())?;
+        }
+        // the types of the variables defined without one, as `file:line:column: name: type`
+        Mode::Check => {
+            let tokens = tokenize(source);
+            let ast = parse(tokens);
+            let mut output = String::new();
+            for inferred in check(&ast)? {
+                output += &format!(
+                    "{}:{}:{}: {}: {}\n",
+                    file_name,
+                    inferred.name.location.start.line,
+                    inferred.name.location.start.column,
+                    inferred.name.name,
+                    inferred.ty
+                );
             }
+            write_output(args.output, output.as_bytes())?;
         }
-        Mode::Compile => {
+        Mode::Ir | Mode::Compile => {
             let tokens = tokenize(source);
             let ast = parse(tokens);
-            let mut generator = CodeGenerator::new(ast).unwrap();
-            let mut wat = generator.generate().unwrap();
-            let wasm = wat.encode().unwrap();
-            if let Some(output) = args.output {
-                std::fs::write(output, &wasm).expect("Failed to write output");
-            } else {
-                println!("{:#?}", wat);
+            let mut ir = lower(&ast)?;
+            optimize(&mut ir, args.optimization_level);
+            match emit {
+                Emit::Ir => write_output(args.output, ir.to_string().as_bytes())?,
+                Emit::Component => {
+                    let wasm = CodeGenerator::new(ir).generate()?;
+                    write_output(args.output, &wasm)?;
+                }
+                Emit::MainModule => {
+                    let wasm = CodeGenerator::new(ir).generate_main_module()?;
+                    write_output(args.output, &wasm)?;
+                }
+                Emit::Wat => {
+                    let wasm = CodeGenerator::new(ir).generate()?;
+                    let wat = wasmprinter::print_bytes(&wasm).context("Failed to print WAT")?;
+                    write_output(args.output, wat.as_bytes())?;
+                }
             }
         }
+        Mode::Run => {
+            let tokens = tokenize(source);
+            let ast = parse(tokens);
+            let mut ir = lower(&ast)?;
+            optimize(&mut ir, args.optimization_level);
+            let wasm = CodeGenerator::new(ir).generate()?;
+            run_component(&wasm, &file_name)?;
+        }
     }
+    Ok(())
+}
+
+/// Run a compiled component, reading from stdin and writing to stdout.
+#[cfg(not(target_family = "wasm"))]
+fn run_component(wasm: &[u8], file_name: &str) -> Result<()> {
+    runner::run(wasm, file_name, runner::stdin(), runner::stdout())?;
+    Ok(())
+}
+
+/// Programs cannot be run on WebAssembly, which wasmtime does not build for.
+#[cfg(target_family = "wasm")]
+fn run_component(_wasm: &[u8], _file_name: &str) -> Result<()> {
+    anyhow::bail!("The run mode is not supported on WebAssembly")
 }
@@ -1,28 +1,65 @@
 import { Button } from "@/components/ui/button"
 import { cn } from "@/lib/utils/classes"
-import MonacoEditor from "@monaco-editor/react"
-import { type FC, memo } from "react"
+import MonacoEditor, { type Monaco } from "@monaco-editor/react"
+import { type FC, memo, useEffect, useRef } from "react"
+
+import { type InferredType } from "../hooks/use-playground"
+
+type Disposable = ReturnType<Monaco["languages"]["registerHoverProvider"]>
 
 export type EditorProps = {
   className?: string
   code: string
   run: () => void
   setCode: (code: string) => void
+  types?: InferredType[]
 }
 
 export const Editor: FC<EditorProps> = memo(
-  ({ className, code, run, setCode }) => {
+  ({ className, code, run, setCode, types }) => {
+    // the hover provider is registered once, so it reads the latest types from a ref
+    const typesRef = useRef(types)
+    const hoverProviderRef = useRef<Disposable>()
+    useEffect(() => {
+      typesRef.current = types
+    }, [types])
+    useEffect(() => () => hoverProviderRef.current?.dispose(), [])
+
     return (
       <div className={cn("relative h-full flex-1", className)}>
         <MonacoEditor
           height="100%"
           language="plaintext"
           onChange={(value) => setCode(value ?? "")}
-          onMount={(editor) => {
+          onMount={(editor, monaco) => {
             editor.updateOptions({
               minimap: { enabled: false },
               theme: "vs-dark",
             })
+            hoverProviderRef.current =
+              monaco.languages.registerHoverProvider("plaintext", {
+                provideHover: (_model, position) => {
+                  const inferred = typesRef.current?.find(
+                    ({ location: { end, start } }) =>
+                      start.line === position.lineNumber &&
+                      start.column <= position.column &&
+                      position.column <= end.column,
+                  )
+                  if (!inferred) return
+                  const { end, start } = inferred.location
+                  return {
+                    contents: [
+                      { value: `\`${inferred.name}: ${inferred.type}\`` },
+                    ],
+                    range: new monaco.Range(
+                      start.line,
+                      start.column,
+                      end.line,
+                      end.column,
+                    ),
+                  }
+                },
+              })
           }}
           value={code}
         />
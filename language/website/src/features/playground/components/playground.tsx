@@ -8,7 +8,12 @@ export const Playground: FC = () => {
   const { code, output, run, setCode } = usePlayground()
   return (
     <div className="dark flex h-full flex-row divide-x bg-bg text-fg">
-      <Editor code={code} run={run} setCode={setCode} />
+      <Editor
+        code={code}
+        run={run}
+        setCode={setCode}
+        types={output?.types}
+      />
       <Output output={output} />
     </div>
   )
@@ -3,10 +3,24 @@ import { useState } from "react"
 import { compile } from "../../../../generated/tools/tools"
 import { runCli, transpile } from "../lib/wasm"
 
+type Position = {
+  column: number
+  index: number
+  line: number
+}
+
+/** Type inferred for a variable defined without one. */
+export type InferredType = {
+  location: { end: Position; start: Position }
+  name: string
+  type: string
+}
+
 export type Output = {
   ast: unknown
   output: string
   tokens: unknown
+  types: InferredType[]
 }
 
 type WasiCliRun = {
@@ -20,7 +34,7 @@ export const usePlayground = () => {
   const [output, setOutput] = useState<Output | undefined>()
 
   const run = async () => {
-    const { ast, tokens, wasm } = compile(code)
+    const { ast, tokens, types, wasm } = compile(code, 0)
     const { "wasi:cli/run@0.2.2": entrypoint } = await transpile<WasiCliRun>(
       wasm,
       "main",
@@ -30,6 +44,7 @@ export const usePlayground = () => {
       ast: JSON.parse(ast),
       output,
       tokens: JSON.parse(tokens),
+      types: JSON.parse(types),
     })
   }
 